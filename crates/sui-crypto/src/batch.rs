@@ -0,0 +1,326 @@
+//! Verifying many signatures at once.
+//!
+//! [`SimpleVerifier::verify_batch`] and [`UserSignatureVerifier::verify_batch`] verify a batch of
+//! `(message, signature)` pairs, where each message is the signing message of the transaction or
+//! personal message the signature is over (as would be passed to [`Verifier::verify`]).
+//!
+//! - Ed25519 signatures are verified together using ed25519 batch verification.
+//! - All other signatures are verified individually. With the `rayon` feature enabled they are
+//!   verified in parallel.
+//!
+//! When any signature fails to verify a [`BatchVerificationError`] is returned which reports
+//! exactly which signatures failed.
+
+use crate::simple::SimpleVerifier;
+use crate::SignatureError;
+use crate::UserSignatureVerifier;
+use crate::Verifier;
+use sui_sdk_types::UserSignature;
+
+/// An error reporting which signatures in a batch failed to verify.
+#[derive(Debug)]
+pub struct BatchVerificationError {
+    failures: Vec<(usize, SignatureError)>,
+}
+
+impl BatchVerificationError {
+    /// The indices, in ascending order, of the signatures which failed to verify.
+    pub fn failed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.failures.iter().map(|(index, _)| *index)
+    }
+
+    /// The index and error of each signature which failed to verify, in ascending order of index.
+    pub fn failures(&self) -> &[(usize, SignatureError)] {
+        &self.failures
+    }
+
+    /// Consume the error, returning the index and error of each signature which failed to
+    /// verify, in ascending order of index.
+    pub fn into_failures(self) -> Vec<(usize, SignatureError)> {
+        self.failures
+    }
+}
+
+impl std::fmt::Display for BatchVerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} signature(s) failed to verify at indices {:?}",
+            self.failures.len(),
+            self.failed_indices().collect::<Vec<_>>()
+        )
+    }
+}
+
+impl std::error::Error for BatchVerificationError {}
+
+impl SimpleVerifier {
+    /// Verify a batch of `(message, signature)` pairs, reporting which signatures failed.
+    ///
+    /// Signatures other than simple signatures are reported as failures.
+    pub fn verify_batch<M>(
+        &self,
+        batch: &[(M, UserSignature)],
+    ) -> Result<(), BatchVerificationError>
+    where
+        M: AsRef<[u8]> + Sync,
+    {
+        verify_batch(self, batch)
+    }
+}
+
+impl UserSignatureVerifier {
+    /// Verify a batch of `(message, signature)` pairs, reporting which signatures failed.
+    pub fn verify_batch<M>(
+        &self,
+        batch: &[(M, UserSignature)],
+    ) -> Result<(), BatchVerificationError>
+    where
+        M: AsRef<[u8]> + Sync,
+    {
+        verify_batch(self, batch)
+    }
+}
+
+fn verify_batch<V, M>(
+    verifier: &V,
+    batch: &[(M, UserSignature)],
+) -> Result<(), BatchVerificationError>
+where
+    V: Verifier<UserSignature> + Sync,
+    M: AsRef<[u8]> + Sync,
+{
+    #[cfg(feature = "ed25519")]
+    let (mut failures, remaining) = ed25519::verify_batch(verifier, batch);
+    #[cfg(not(feature = "ed25519"))]
+    let (mut failures, remaining) = (Vec::new(), (0..batch.len()).collect::<Vec<_>>());
+
+    let verify = |index: usize| {
+        let (message, signature) = &batch[index];
+        verifier
+            .verify(message.as_ref(), signature)
+            .err()
+            .map(|e| (index, e))
+    };
+
+    #[cfg(feature = "rayon")]
+    failures.extend({
+        use rayon::prelude::*;
+        remaining
+            .into_par_iter()
+            .filter_map(verify)
+            .collect::<Vec<_>>()
+    });
+    #[cfg(not(feature = "rayon"))]
+    failures.extend(remaining.into_iter().filter_map(verify));
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        failures.sort_by_key(|(index, _)| *index);
+        Err(BatchVerificationError { failures })
+    }
+}
+
+#[cfg(feature = "ed25519")]
+mod ed25519 {
+    use super::*;
+    use sui_sdk_types::SimpleSignature;
+
+    /// Batch verify all of the ed25519 signatures in `batch`, returning the failures along with the
+    /// indices of the signatures which still need to be verified.
+    ///
+    /// Batch verification uses the cofactored verification equation and doesn't check how points
+    /// are encoded, unlike individual verification which uses `verify_strict`. The two only differ
+    /// for adversarially constructed signatures involving points of small order or non-canonical
+    /// encodings, so signatures with such a public key or `R` are left to be verified
+    /// individually.
+    pub(super) fn verify_batch<V, M>(
+        verifier: &V,
+        batch: &[(M, UserSignature)],
+    ) -> (Vec<(usize, SignatureError)>, Vec<usize>)
+    where
+        V: Verifier<UserSignature>,
+        M: AsRef<[u8]>,
+    {
+        let mut failures = Vec::new();
+        let mut remaining = Vec::new();
+        let mut indices = Vec::new();
+        let mut messages = Vec::new();
+        let mut signatures = Vec::new();
+        let mut verifying_keys = Vec::new();
+
+        for (index, (message, signature)) in batch.iter().enumerate() {
+            let UserSignature::Simple(SimpleSignature::Ed25519 {
+                signature,
+                public_key,
+            }) = signature
+            else {
+                remaining.push(index);
+                continue;
+            };
+
+            let signature = ed25519_dalek::Signature::from_bytes(signature.inner());
+            match ed25519_dalek::VerifyingKey::from_bytes(public_key.inner()) {
+                Ok(verifying_key)
+                    if is_strict_point(public_key.inner())
+                        && is_strict_point(signature.r_bytes()) =>
+                {
+                    indices.push(index);
+                    messages.push(message.as_ref());
+                    signatures.push(signature);
+                    verifying_keys.push(verifying_key);
+                }
+                Ok(_) => remaining.push(index),
+                Err(e) => failures.push((index, e)),
+            }
+        }
+
+        // A batch failure doesn't indicate which signatures are invalid so fall back to verifying
+        // each signature individually
+        if !indices.is_empty()
+            && ed25519_dalek::verify_batch(&messages, &signatures, &verifying_keys).is_err()
+        {
+            failures.extend(indices.into_iter().filter_map(|index| {
+                let (message, signature) = &batch[index];
+                verifier
+                    .verify(message.as_ref(), signature)
+                    .err()
+                    .map(|e| (index, e))
+            }));
+        }
+
+        (failures, remaining)
+    }
+
+    /// Returns true if `bytes` is the canonical encoding of a point which is not of small order.
+    fn is_strict_point(bytes: &[u8; 32]) -> bool {
+        curve25519_dalek::edwards::CompressedEdwardsY(*bytes)
+            .decompress()
+            .is_some_and(|point| !point.is_small_order() && point.compress().as_bytes() == bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::Ed25519PrivateKey;
+    use crate::secp256k1::Secp256k1PrivateKey;
+    use crate::secp256r1::Secp256r1PrivateKey;
+    use crate::SuiSigner;
+    use sui_sdk_types::PersonalMessage;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn signed_batch() -> Vec<(Vec<u8>, UserSignature)> {
+        (1..=12)
+            .map(|i| {
+                let message = PersonalMessage(vec![i].into());
+                let signature = match i % 3 {
+                    0 => Ed25519PrivateKey::new([i; 32]).sign_personal_message(&message),
+                    1 => Secp256k1PrivateKey::new([i; 32])
+                        .unwrap()
+                        .sign_personal_message(&message),
+                    _ => Secp256r1PrivateKey::new([i; 32]).sign_personal_message(&message),
+                };
+                (message.signing_digest().to_vec(), signature.unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verify_valid_batch() {
+        let batch = signed_batch();
+        SimpleVerifier.verify_batch(&batch).unwrap();
+        UserSignatureVerifier::new().verify_batch(&batch).unwrap();
+        UserSignatureVerifier::new()
+            .verify_batch::<Vec<u8>>(&[])
+            .unwrap();
+    }
+
+    #[test]
+    fn report_failed_indices() {
+        let mut batch = signed_batch();
+        // Swap the messages of an ed25519 and a secp256r1 signature
+        let message = batch[2].0.clone();
+        batch[2].0 = batch[4].0.clone();
+        batch[4].0 = message;
+        // Swap the signatures of two ed25519 signatures
+        let signature = batch[8].1.clone();
+        batch[8].1 = batch[11].1.clone();
+        batch[11].1 = signature;
+
+        let err = UserSignatureVerifier::new()
+            .verify_batch(&batch)
+            .unwrap_err();
+        assert_eq!(err.failed_indices().collect::<Vec<_>>(), [2, 4, 8, 11]);
+    }
+
+    #[test]
+    fn reject_small_order_and_non_canonical_r() {
+        use curve25519_dalek::Scalar;
+        use sha2::Digest;
+        use sui_sdk_types::Ed25519PublicKey;
+        use sui_sdk_types::Ed25519Signature;
+        use sui_sdk_types::SimpleSignature;
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let public_key = key.verifying_key().to_bytes();
+        let message = [1; 32];
+
+        // With R the identity, s = H(R || A || M) * a satisfies the verification equation without
+        // knowing the signing nonce. verify_strict rejects it as R is of small order, or as R is
+        // not canonically encoded when the identity is encoded as y = p + 1
+        let identity = {
+            let mut bytes = [0; 32];
+            bytes[0] = 1;
+            bytes
+        };
+        let non_canonical_identity = {
+            let mut bytes = [0xff; 32];
+            bytes[0] = 0xee;
+            bytes[31] = 0x7f;
+            bytes
+        };
+
+        for r in [identity, non_canonical_identity] {
+            let k = Scalar::from_hash(
+                sha2::Sha512::new()
+                    .chain_update(r)
+                    .chain_update(public_key)
+                    .chain_update(message),
+            );
+            let s = k * key.to_scalar();
+            let mut signature = [0; 64];
+            signature[..32].copy_from_slice(&r);
+            signature[32..].copy_from_slice(s.as_bytes());
+
+            // The signature is accepted by the cofactored batch equation on its own
+            ed25519_dalek::verify_batch(
+                &[&message],
+                &[ed25519_dalek::Signature::from_bytes(&signature)],
+                &[key.verifying_key()],
+            )
+            .unwrap();
+
+            let signature = UserSignature::Simple(SimpleSignature::Ed25519 {
+                signature: Ed25519Signature::new(signature),
+                public_key: Ed25519PublicKey::new(public_key),
+            });
+
+            // But fails individually and in a batch
+            let mut batch = signed_batch();
+            batch.push((message.to_vec(), signature.clone()));
+            UserSignatureVerifier::new()
+                .verify(&message, &signature)
+                .unwrap_err();
+            let err = UserSignatureVerifier::new()
+                .verify_batch(&batch)
+                .unwrap_err();
+            assert_eq!(err.failed_indices().collect::<Vec<_>>(), [12]);
+            let err = SimpleVerifier.verify_batch(&batch).unwrap_err();
+            assert_eq!(err.failed_indices().collect::<Vec<_>>(), [12]);
+        }
+    }
+}
@@ -31,6 +31,10 @@ pub mod passkey;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "zklogin")))]
 pub mod zklogin;
 
+#[cfg(feature = "policy")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "policy")))]
+pub mod policy;
+
 #[cfg(any(
     feature = "ed25519",
     feature = "secp256r1",
@@ -83,6 +87,40 @@ pub mod multisig;
 #[doc(inline)]
 pub use multisig::UserSignatureVerifier;
 
+#[cfg(any(
+    feature = "ed25519",
+    feature = "secp256r1",
+    feature = "secp256k1",
+    feature = "zklogin"
+))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(
+        feature = "ed25519",
+        feature = "secp256r1",
+        feature = "secp256k1",
+        feature = "zklogin"
+    )))
+)]
+pub mod batch;
+
+#[cfg(any(
+    feature = "ed25519",
+    feature = "secp256r1",
+    feature = "secp256k1",
+    feature = "zklogin"
+))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(
+        feature = "ed25519",
+        feature = "secp256r1",
+        feature = "secp256k1",
+        feature = "zklogin"
+    )))
+)]
+pub mod personal_message;
+
 /// Interface for signing user transactions and messages in Sui
 ///
 /// # Note
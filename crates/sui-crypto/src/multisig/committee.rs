@@ -0,0 +1,558 @@
+use sui_sdk_types::Address;
+use sui_sdk_types::MultisigCommittee;
+use sui_sdk_types::MultisigMember;
+use sui_sdk_types::MultisigMemberPublicKey;
+use sui_sdk_types::UserSignature;
+
+use super::multisig_pubkey_and_signature_from_user_signature;
+
+/// The maximum number of members a committee can have.
+const MAX_COMMITTEE_SIZE: usize = 10;
+
+/// An error encountered while building a [`MultisigCommittee`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MultisigCommitteeError {
+    /// The committee has no members.
+    NoMembers,
+    /// The committee has more members than the protocol allows.
+    TooManyMembers { count: usize },
+    /// The member at `index` has a weight of 0.
+    ZeroWeight { index: usize },
+    /// The member at `index` has the same public key as an earlier member.
+    DuplicateMember { index: usize },
+    /// No threshold was provided.
+    MissingThreshold,
+    /// The threshold is 0.
+    ZeroThreshold,
+    /// The threshold is larger than the total weight of all members, making it unreachable.
+    UnreachableThreshold { threshold: u16, total_weight: u32 },
+    /// A public key could not be decoded or derived from a signature.
+    InvalidPublicKey { reason: String },
+    /// An imported address does not match the address derived from the committee.
+    AddressMismatch { expected: Address, derived: Address },
+    /// The JSON representation of a committee could not be parsed.
+    #[cfg(feature = "multisig-json")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "multisig-json")))]
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for MultisigCommitteeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoMembers => f.write_str("a multisig committee must have at least one member"),
+            Self::TooManyMembers { count } => write!(
+                f,
+                "a multisig committee can have at most {MAX_COMMITTEE_SIZE} members, found {count}"
+            ),
+            Self::ZeroWeight { index } => {
+                write!(f, "member {index} has a weight of 0, weights must be at least 1")
+            }
+            Self::DuplicateMember { index } => {
+                write!(f, "member {index} has the same public key as an earlier member")
+            }
+            Self::MissingThreshold => f.write_str("no threshold was provided"),
+            Self::ZeroThreshold => f.write_str("threshold must be at least 1"),
+            Self::UnreachableThreshold {
+                threshold,
+                total_weight,
+            } => write!(
+                f,
+                "threshold {threshold} is larger than the total weight of all members {total_weight}"
+            ),
+            Self::InvalidPublicKey { reason } => write!(f, "invalid public key: {reason}"),
+            Self::AddressMismatch { expected, derived } => write!(
+                f,
+                "expected address {expected} but the committee derives address {derived}"
+            ),
+            #[cfg(feature = "multisig-json")]
+            Self::Json(e) => write!(f, "invalid multisig committee json: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MultisigCommitteeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "multisig-json")]
+            Self::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A builder for a [`MultisigCommittee`] which enforces the protocol's limits on committees.
+///
+/// Members can be added using any public key, verifying key or signature from a supported
+/// signature scheme:
+///
+/// ```rust,ignore
+/// let committee = MultisigCommitteeBuilder::new()
+///     .with_member(ed25519_key.public_key(), 1)
+///     .with_member(&secp256r1_verifying_key, 1)
+///     .with_member_from_signature(&zklogin_signature, 2)?
+///     .with_threshold(2)
+///     .build()?;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultisigCommitteeBuilder {
+    members: Vec<MultisigMember>,
+    threshold: Option<u16>,
+}
+
+impl MultisigCommitteeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the members and threshold of an existing committee.
+    pub fn from_committee(committee: &MultisigCommittee) -> Self {
+        Self {
+            members: committee.members().to_vec(),
+            threshold: Some(committee.threshold()),
+        }
+    }
+
+    /// Add a member with the provided `weight`.
+    ///
+    /// Note that the order of the members is significant towards deriving the `Address` governed
+    /// by the committee.
+    pub fn with_member<K: Into<MultisigMemberPublicKey>>(
+        mut self,
+        public_key: K,
+        weight: u8,
+    ) -> Self {
+        self.members
+            .push(MultisigMember::new(public_key.into(), weight));
+        self
+    }
+
+    /// Add the member who produced `signature` with the provided `weight`.
+    ///
+    /// This is primarily useful for zklogin and passkey members, whose public identifiers are most
+    /// easily obtained from a signature.
+    pub fn with_member_from_signature(
+        self,
+        signature: &UserSignature,
+        weight: u8,
+    ) -> Result<Self, MultisigCommitteeError> {
+        let (public_key, _) = multisig_pubkey_and_signature_from_user_signature(signature.clone())
+            .map_err(|e| MultisigCommitteeError::InvalidPublicKey {
+                reason: e.to_string(),
+            })?;
+        Ok(self.with_member(public_key, weight))
+    }
+
+    pub fn with_threshold(mut self, threshold: u16) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    pub fn members(&self) -> &[MultisigMember] {
+        &self.members
+    }
+
+    pub fn threshold(&self) -> Option<u16> {
+        self.threshold
+    }
+
+    /// The sum of the weights of all members.
+    pub fn total_weight(&self) -> u32 {
+        self.members
+            .iter()
+            .map(|member| member.weight() as u32)
+            .sum()
+    }
+
+    /// Check the committee against the protocol's limits.
+    pub fn validate(&self) -> Result<(), MultisigCommitteeError> {
+        if self.members.is_empty() {
+            return Err(MultisigCommitteeError::NoMembers);
+        }
+
+        if self.members.len() > MAX_COMMITTEE_SIZE {
+            return Err(MultisigCommitteeError::TooManyMembers {
+                count: self.members.len(),
+            });
+        }
+
+        for (index, member) in self.members.iter().enumerate() {
+            if member.weight() == 0 {
+                return Err(MultisigCommitteeError::ZeroWeight { index });
+            }
+
+            if self.members[..index]
+                .iter()
+                .any(|m| m.public_key() == member.public_key())
+            {
+                return Err(MultisigCommitteeError::DuplicateMember { index });
+            }
+        }
+
+        let threshold = self
+            .threshold
+            .ok_or(MultisigCommitteeError::MissingThreshold)?;
+        if threshold == 0 {
+            return Err(MultisigCommitteeError::ZeroThreshold);
+        }
+
+        let total_weight = self.total_weight();
+        if threshold as u32 > total_weight {
+            return Err(MultisigCommitteeError::UnreachableThreshold {
+                threshold,
+                total_weight,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validate and build the committee.
+    pub fn build(&self) -> Result<MultisigCommittee, MultisigCommitteeError> {
+        self.validate()?;
+        let threshold = self
+            .threshold
+            .ok_or(MultisigCommitteeError::MissingThreshold)?;
+        Ok(MultisigCommittee::new(self.members.clone(), threshold))
+    }
+
+    /// Preview the `Address` which would be governed by the committee.
+    pub fn address(&self) -> Result<Address, MultisigCommitteeError> {
+        self.build().map(|committee| committee.derive_address())
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl From<&crate::ed25519::Ed25519VerifyingKey> for MultisigMemberPublicKey {
+    fn from(verifying_key: &crate::ed25519::Ed25519VerifyingKey) -> Self {
+        Self::Ed25519(verifying_key.public_key())
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl From<&crate::secp256k1::Secp256k1VerifyingKey> for MultisigMemberPublicKey {
+    fn from(verifying_key: &crate::secp256k1::Secp256k1VerifyingKey) -> Self {
+        Self::Secp256k1(verifying_key.public_key())
+    }
+}
+
+#[cfg(feature = "secp256r1")]
+impl From<&crate::secp256r1::Secp256r1VerifyingKey> for MultisigMemberPublicKey {
+    fn from(verifying_key: &crate::secp256r1::Secp256r1VerifyingKey) -> Self {
+        Self::Secp256r1(verifying_key.public_key())
+    }
+}
+
+#[cfg(feature = "multisig-json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "multisig-json")))]
+mod json {
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use sui_sdk_types::Bn254FieldElement;
+    use sui_sdk_types::Ed25519PublicKey;
+    use sui_sdk_types::PasskeyPublicKey;
+    use sui_sdk_types::Secp256k1PublicKey;
+    use sui_sdk_types::Secp256r1PublicKey;
+    use sui_sdk_types::SignatureScheme;
+    use sui_sdk_types::ZkLoginPublicIdentifier;
+
+    use super::*;
+
+    /// The format output by `sui keytool multi-sig-address`, extended with the committee's
+    /// threshold.
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct MultisigAddressJson {
+        multisig_address: Address,
+        multisig: Vec<MultisigMemberJson>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threshold: Option<u16>,
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct MultisigMemberJson {
+        address: Address,
+        public_base64_key: String,
+        weight: u8,
+    }
+
+    impl MultisigCommitteeBuilder {
+        /// Export the committee as JSON in the format output by `sui keytool multi-sig-address`,
+        /// with the addition of a `threshold` field.
+        pub fn to_json(&self) -> Result<String, MultisigCommitteeError> {
+            let committee = self.build()?;
+            let json = MultisigAddressJson {
+                multisig_address: committee.derive_address(),
+                multisig: committee
+                    .members()
+                    .iter()
+                    .map(|member| MultisigMemberJson {
+                        address: member.public_key().derive_address(),
+                        public_base64_key: Base64::encode_string(&encode_public_key(
+                            member.public_key(),
+                        )),
+                        weight: member.weight(),
+                    })
+                    .collect(),
+                threshold: Some(committee.threshold()),
+            };
+
+            serde_json::to_string_pretty(&json).map_err(MultisigCommitteeError::Json)
+        }
+
+        /// Import a committee from JSON in the format output by `sui keytool multi-sig-address`.
+        ///
+        /// The `address` of each member is checked against its public key. The reference CLI does
+        /// not include the committee's threshold in its output, so if the `threshold` field is
+        /// missing it must be provided via [`MultisigCommitteeBuilder::with_threshold`]. When a
+        /// threshold is present the `multisigAddress` is checked against the committee as well.
+        pub fn from_json(json: &str) -> Result<Self, MultisigCommitteeError> {
+            let json: MultisigAddressJson =
+                serde_json::from_str(json).map_err(MultisigCommitteeError::Json)?;
+
+            let mut builder = Self::new();
+            for member in json.multisig {
+                let bytes = Base64::decode_vec(&member.public_base64_key).map_err(|e| {
+                    MultisigCommitteeError::InvalidPublicKey {
+                        reason: e.to_string(),
+                    }
+                })?;
+                let public_key = decode_public_key(&bytes)?;
+
+                let derived = public_key.derive_address();
+                if derived != member.address {
+                    return Err(MultisigCommitteeError::AddressMismatch {
+                        expected: member.address,
+                        derived,
+                    });
+                }
+
+                builder = builder.with_member(public_key, member.weight);
+            }
+
+            if let Some(threshold) = json.threshold {
+                builder = builder.with_threshold(threshold);
+                let derived = builder.address()?;
+                if derived != json.multisig_address {
+                    return Err(MultisigCommitteeError::AddressMismatch {
+                        expected: json.multisig_address,
+                        derived,
+                    });
+                }
+            }
+
+            Ok(builder)
+        }
+    }
+
+    /// Encode a public key as its signature scheme flag followed by its bytes.
+    fn encode_public_key(public_key: &MultisigMemberPublicKey) -> Vec<u8> {
+        let mut bytes = vec![public_key.scheme().to_u8()];
+        match public_key {
+            MultisigMemberPublicKey::Ed25519(p) => bytes.extend_from_slice(p.inner()),
+            MultisigMemberPublicKey::Secp256k1(p) => bytes.extend_from_slice(p.inner()),
+            MultisigMemberPublicKey::Secp256r1(p) => bytes.extend_from_slice(p.inner()),
+            MultisigMemberPublicKey::ZkLogin(p) => {
+                bytes.push(p.iss().len() as u8);
+                bytes.extend_from_slice(p.iss().as_bytes());
+                bytes.extend_from_slice(p.address_seed().padded());
+            }
+            MultisigMemberPublicKey::Passkey(p) => bytes.extend_from_slice(p.inner().inner()),
+        }
+        bytes
+    }
+
+    fn decode_public_key(bytes: &[u8]) -> Result<MultisigMemberPublicKey, MultisigCommitteeError> {
+        fn invalid<E: std::fmt::Display>(e: E) -> MultisigCommitteeError {
+            MultisigCommitteeError::InvalidPublicKey {
+                reason: e.to_string(),
+            }
+        }
+
+        let (flag, key) = bytes
+            .split_first()
+            .ok_or_else(|| invalid("empty public key"))?;
+
+        let public_key = match SignatureScheme::from_byte(*flag).map_err(invalid)? {
+            SignatureScheme::Ed25519 => {
+                Ed25519PublicKey::new(key.try_into().map_err(invalid)?).into()
+            }
+            SignatureScheme::Secp256k1 => {
+                Secp256k1PublicKey::new(key.try_into().map_err(invalid)?).into()
+            }
+            SignatureScheme::Secp256r1 => {
+                Secp256r1PublicKey::new(key.try_into().map_err(invalid)?).into()
+            }
+            SignatureScheme::Passkey => {
+                PasskeyPublicKey::new(Secp256r1PublicKey::new(key.try_into().map_err(invalid)?))
+                    .into()
+            }
+            SignatureScheme::ZkLogin => {
+                let (iss_len, rest) = key
+                    .split_first()
+                    .ok_or_else(|| invalid("missing zklogin iss length"))?;
+                let iss_len = *iss_len as usize;
+                if rest.len() != iss_len + 32 {
+                    return Err(invalid("invalid zklogin public identifier length"));
+                }
+                let (iss, address_seed) = rest.split_at(iss_len);
+                let iss = std::str::from_utf8(iss).map_err(invalid)?;
+                ZkLoginPublicIdentifier::new(
+                    iss.to_owned(),
+                    Bn254FieldElement::new(address_seed.try_into().map_err(invalid)?),
+                )
+                .ok_or_else(|| invalid("invalid zklogin public identifier"))?
+                .into()
+            }
+            scheme => {
+                return Err(invalid(format!(
+                    "{scheme:?} keys cannot be members of a multisig committee"
+                )))
+            }
+        };
+
+        Ok(public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::Ed25519PrivateKey;
+    use crate::secp256r1::Secp256r1PrivateKey;
+    use crate::SuiSigner;
+    use sui_sdk_types::PersonalMessage;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn ed25519_key(i: u8) -> Ed25519PrivateKey {
+        Ed25519PrivateKey::new([i; Ed25519PrivateKey::LENGTH])
+    }
+
+    fn test_builder() -> MultisigCommitteeBuilder {
+        let secp256r1 = Secp256r1PrivateKey::new([2; Secp256r1PrivateKey::LENGTH]);
+        let signature = ed25519_key(3)
+            .sign_personal_message(&PersonalMessage(b"hello".into()))
+            .unwrap();
+
+        MultisigCommitteeBuilder::new()
+            .with_member(ed25519_key(1).public_key(), 1)
+            .with_member(&secp256r1.verifying_key(), 1)
+            .with_member_from_signature(&signature, 2)
+            .unwrap()
+            .with_threshold(2)
+    }
+
+    #[test]
+    fn build_mixed_committee() {
+        let builder = test_builder();
+        assert_eq!(builder.total_weight(), 4);
+
+        let committee = builder.build().unwrap();
+        assert_eq!(committee.members().len(), 3);
+        assert_eq!(committee.threshold(), 2);
+        assert_eq!(
+            committee.members()[2].public_key(),
+            &MultisigMemberPublicKey::Ed25519(ed25519_key(3).public_key())
+        );
+        assert_eq!(builder.address().unwrap(), committee.derive_address());
+        assert_eq!(
+            MultisigCommitteeBuilder::from_committee(&committee)
+                .build()
+                .unwrap(),
+            committee
+        );
+    }
+
+    #[test]
+    fn enforce_protocol_limits() {
+        let err = MultisigCommitteeBuilder::new()
+            .with_threshold(1)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, MultisigCommitteeError::NoMembers));
+
+        let err = (1..=11)
+            .fold(MultisigCommitteeBuilder::new(), |builder, i| {
+                builder.with_member(ed25519_key(i).public_key(), 1)
+            })
+            .with_threshold(1)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MultisigCommitteeError::TooManyMembers { count: 11 }
+        ));
+
+        let err = test_builder()
+            .with_member(ed25519_key(4).public_key(), 0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MultisigCommitteeError::ZeroWeight { index: 3 }
+        ));
+
+        let err = test_builder()
+            .with_member(ed25519_key(1).public_key(), 1)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MultisigCommitteeError::DuplicateMember { index: 3 }
+        ));
+
+        let mut builder = test_builder();
+        builder.threshold = None;
+        let err = builder.build().unwrap_err();
+        assert!(matches!(err, MultisigCommitteeError::MissingThreshold));
+
+        let err = test_builder().with_threshold(0).build().unwrap_err();
+        assert!(matches!(err, MultisigCommitteeError::ZeroThreshold));
+
+        let err = test_builder().with_threshold(5).build().unwrap_err();
+        assert!(matches!(
+            err,
+            MultisigCommitteeError::UnreachableThreshold {
+                threshold: 5,
+                total_weight: 4,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "threshold 5 is larger than the total weight of all members 4"
+        );
+    }
+
+    #[cfg(feature = "multisig-json")]
+    #[test]
+    fn json_round_trip() {
+        let builder = test_builder();
+        let json = builder.to_json().unwrap();
+        assert_eq!(MultisigCommitteeBuilder::from_json(&json).unwrap(), builder);
+
+        // The reference CLI omits the threshold
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("threshold");
+        let imported = MultisigCommitteeBuilder::from_json(&value.to_string()).unwrap();
+        assert_eq!(imported.threshold(), None);
+        assert_eq!(imported.with_threshold(2), builder);
+
+        // A multisig address which doesn't match the committee
+        let err = MultisigCommitteeBuilder::from_json(
+            &builder
+                .clone()
+                .with_threshold(3)
+                .to_json()
+                .unwrap()
+                .replace("\"threshold\": 3", "\"threshold\": 2"),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            MultisigCommitteeError::AddressMismatch { .. }
+        ));
+    }
+}
@@ -6,6 +6,21 @@ use sui_sdk_types::MultisigMemberPublicKey;
 use sui_sdk_types::MultisigMemberSignature;
 use sui_sdk_types::UserSignature;
 
+mod committee;
+#[cfg(feature = "multisig-session")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "multisig-session")))]
+mod session;
+
+pub use committee::MultisigCommitteeBuilder;
+pub use committee::MultisigCommitteeError;
+
+#[cfg(feature = "multisig-session")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "multisig-session")))]
+pub use session::MultisigSession;
+#[cfg(feature = "multisig-session")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "multisig-session")))]
+pub use session::SigningPayload;
+
 #[derive(Default)]
 pub struct MultisigVerifier {
     #[cfg(feature = "zklogin")]
@@ -0,0 +1,526 @@
+use std::collections::BTreeMap;
+
+use signature::Signer;
+use sui_sdk_types::MultisigAggregatedSignature;
+use sui_sdk_types::MultisigCommittee;
+use sui_sdk_types::MultisigMember;
+use sui_sdk_types::MultisigMemberSignature;
+use sui_sdk_types::PersonalMessage;
+use sui_sdk_types::Transaction;
+use sui_sdk_types::UserSignature;
+
+use super::multisig_pubkey_and_signature_from_user_signature;
+use super::MultisigVerifier;
+use crate::SignatureError;
+
+/// The payload being signed by the members of a multisig committee.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+pub enum SigningPayload {
+    /// A transaction, included in full so that members can inspect it before signing.
+    Transaction(Transaction),
+    /// The bytes of a personal message.
+    PersonalMessage(Vec<u8>),
+    /// A precomputed signing digest, for when the payload itself is not being shared.
+    SigningDigest([u8; 32]),
+}
+
+impl SigningPayload {
+    /// The digest which members of the committee sign.
+    pub fn signing_digest(&self) -> [u8; 32] {
+        match self {
+            Self::Transaction(transaction) => transaction.signing_digest(),
+            Self::PersonalMessage(message) => {
+                PersonalMessage(message.as_slice().into()).signing_digest()
+            }
+            Self::SigningDigest(digest) => *digest,
+        }
+    }
+}
+
+/// A serializable multisig signing session.
+///
+/// Unlike [`MultisigAggregator`](super::MultisigAggregator), which collects signatures in memory,
+/// a `MultisigSession` is intended to be passed between the members of a committee, each of whom
+/// may be on a different machine. A session contains the committee, the payload being signed and
+/// the partial signatures collected so far, and can be written to and read from a file as JSON or
+/// BCS. Sessions signed independently by several members can be merged together and, once enough
+/// weight has been collected, turned into the final [`MultisigAggregatedSignature`].
+///
+/// Signatures added via [`MultisigSession::add_signature`] or [`MultisigSession::merge`] are
+/// verified before being accepted. A session read from a file is checked to have a valid committee
+/// and only signatures from its members, but the signatures themselves are not verified until
+/// [`MultisigSession::verify_signatures`] or [`MultisigSession::finish`] is called.
+///
+/// ```rust,ignore
+/// // Coordinator
+/// let session = MultisigSession::new_with_transaction(committee, transaction);
+/// std::fs::write("session.json", session.to_json()?)?;
+///
+/// // Each member
+/// let mut session = MultisigSession::from_json(&std::fs::read_to_string("session.json")?)?;
+/// session.sign(&private_key)?;
+/// std::fs::write("session-alice.json", session.to_json()?)?;
+///
+/// // Coordinator
+/// session.merge(alice_session)?;
+/// session.merge(bob_session)?;
+/// let signature = session.finish()?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(try_from = "UncheckedMultisigSession")]
+pub struct MultisigSession {
+    committee: MultisigCommittee,
+    payload: SigningPayload,
+    /// Collected signatures, keyed by the index of the signing member in the committee.
+    signatures: BTreeMap<u8, MultisigMemberSignature>,
+}
+
+/// A [`MultisigSession`] as read from a file, before its committee and signers are checked.
+#[derive(serde_derive::Deserialize)]
+struct UncheckedMultisigSession {
+    committee: MultisigCommittee,
+    payload: SigningPayload,
+    signatures: BTreeMap<u8, MultisigMemberSignature>,
+}
+
+impl TryFrom<UncheckedMultisigSession> for MultisigSession {
+    type Error = SignatureError;
+
+    fn try_from(
+        UncheckedMultisigSession {
+            committee,
+            payload,
+            signatures,
+        }: UncheckedMultisigSession,
+    ) -> Result<Self, Self::Error> {
+        if !committee.is_valid() {
+            return Err(SignatureError::from_source("invalid multisig committee"));
+        }
+
+        let session = Self {
+            committee,
+            payload,
+            signatures,
+        };
+        for member_idx in session.signatures.keys() {
+            session.member(*member_idx)?;
+        }
+        Ok(session)
+    }
+}
+
+impl MultisigSession {
+    /// Start a new session for `committee` to sign `payload`.
+    ///
+    /// `committee` is expected to be valid, see [`MultisigCommittee::is_valid`].
+    pub fn new(committee: MultisigCommittee, payload: SigningPayload) -> Self {
+        Self {
+            committee,
+            payload,
+            signatures: Default::default(),
+        }
+    }
+
+    pub fn new_with_transaction(committee: MultisigCommittee, transaction: Transaction) -> Self {
+        Self::new(committee, SigningPayload::Transaction(transaction))
+    }
+
+    pub fn new_with_message(committee: MultisigCommittee, message: &PersonalMessage<'_>) -> Self {
+        Self::new(
+            committee,
+            SigningPayload::PersonalMessage(message.0.to_vec()),
+        )
+    }
+
+    pub fn committee(&self) -> &MultisigCommittee {
+        &self.committee
+    }
+
+    pub fn payload(&self) -> &SigningPayload {
+        &self.payload
+    }
+
+    /// The digest which members of the committee sign.
+    pub fn signing_digest(&self) -> [u8; 32] {
+        self.payload.signing_digest()
+    }
+
+    /// The total weight of the members who have signed.
+    pub fn signed_weight(&self) -> u16 {
+        self.signers().map(|member| member.weight() as u16).sum()
+    }
+
+    /// The additional weight needed to reach the committee's threshold.
+    pub fn remaining_weight(&self) -> u16 {
+        self.committee
+            .threshold()
+            .saturating_sub(self.signed_weight())
+    }
+
+    /// Returns true if enough weight has been collected to produce the final signature.
+    pub fn is_complete(&self) -> bool {
+        self.remaining_weight() == 0
+    }
+
+    /// The members of the committee who have signed.
+    pub fn signers(&self) -> impl Iterator<Item = &MultisigMember> {
+        self.signatures
+            .keys()
+            .filter_map(|idx| self.committee.members().get(*idx as usize))
+    }
+
+    /// The members of the committee who have not yet signed.
+    pub fn pending_members(&self) -> impl Iterator<Item = &MultisigMember> {
+        self.committee
+            .members()
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.signatures.contains_key(&(*idx as u8)))
+            .map(|(_, member)| member)
+    }
+
+    /// Sign the session's payload with `signer`, who must be a member of the committee.
+    pub fn sign<S: Signer<UserSignature>>(&mut self, signer: &S) -> Result<(), SignatureError> {
+        let signature = signer.try_sign(&self.signing_digest())?;
+        self.add_signature(signature)
+    }
+
+    /// Verify and add a signature from a member of the committee.
+    pub fn add_signature(&mut self, signature: UserSignature) -> Result<(), SignatureError> {
+        self.add_signature_with_verifier(signature, &MultisigVerifier::default())
+    }
+
+    /// Verify, using `verifier`, and add a signature from a member of the committee.
+    ///
+    /// A custom verifier is required in order to verify signatures from zklogin members.
+    pub fn add_signature_with_verifier(
+        &mut self,
+        signature: UserSignature,
+        verifier: &MultisigVerifier,
+    ) -> Result<(), SignatureError> {
+        let (public_key, signature) = multisig_pubkey_and_signature_from_user_signature(signature)?;
+        let member_idx = self
+            .committee
+            .members()
+            .iter()
+            .position(|member| member.public_key() == &public_key)
+            .ok_or_else(|| {
+                SignatureError::from_source(
+                    "provided signature does not belong to committee member",
+                )
+            })?;
+
+        self.insert_signature(member_idx as u8, signature, verifier)
+    }
+
+    /// Merge the signatures collected by `other` into this session.
+    ///
+    /// Both sessions must be for the same committee and payload. Signatures from `other` are
+    /// verified before being added, while signatures from members who have already signed this
+    /// session are ignored.
+    pub fn merge(&mut self, other: MultisigSession) -> Result<(), SignatureError> {
+        self.merge_with_verifier(other, &MultisigVerifier::default())
+    }
+
+    /// Merge the signatures collected by `other` into this session, verifying them using
+    /// `verifier`.
+    pub fn merge_with_verifier(
+        &mut self,
+        other: MultisigSession,
+        verifier: &MultisigVerifier,
+    ) -> Result<(), SignatureError> {
+        if other.committee != self.committee {
+            return Err(SignatureError::from_source(
+                "cannot merge sessions for different committees",
+            ));
+        }
+        if other.payload != self.payload {
+            return Err(SignatureError::from_source(
+                "cannot merge sessions for different payloads",
+            ));
+        }
+
+        for (member_idx, signature) in other.signatures {
+            if !self.signatures.contains_key(&member_idx) {
+                self.insert_signature(member_idx, signature, verifier)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify all of the signatures collected so far, for example after reading a session from a
+    /// file.
+    pub fn verify_signatures(&self, verifier: &MultisigVerifier) -> Result<(), SignatureError> {
+        let message = self.signing_digest();
+        for (member_idx, signature) in &self.signatures {
+            let member = self.member(*member_idx)?;
+            verifier.verify_member_signature(&message, member.public_key(), signature)?;
+        }
+        Ok(())
+    }
+
+    /// Produce the final multisig signature once enough weight has been collected.
+    ///
+    /// Every collected signature is verified against the session's payload first.
+    pub fn finish(&self) -> Result<MultisigAggregatedSignature, SignatureError> {
+        self.finish_with_verifier(&MultisigVerifier::default())
+    }
+
+    /// Produce the final multisig signature once enough weight has been collected, verifying the
+    /// collected signatures using `verifier`.
+    pub fn finish_with_verifier(
+        &self,
+        verifier: &MultisigVerifier,
+    ) -> Result<MultisigAggregatedSignature, SignatureError> {
+        if !self.is_complete() {
+            return Err(SignatureError::from_source(format!(
+                "insufficient signature weight to reach threshold: {} more needed",
+                self.remaining_weight()
+            )));
+        }
+        self.verify_signatures(verifier)?;
+
+        let (signatures, bitmap) = self.signatures.iter().fold(
+            (Vec::new(), 0),
+            |(mut signatures, mut bitmap), (member_idx, signature)| {
+                bitmap |= 1 << member_idx;
+                signatures.push(signature.clone());
+                (signatures, bitmap)
+            },
+        );
+
+        Ok(MultisigAggregatedSignature::new(
+            self.committee.clone(),
+            signatures,
+            bitmap,
+        ))
+    }
+
+    /// Serialize this session as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, SignatureError> {
+        serde_json::to_string_pretty(self).map_err(SignatureError::from_source)
+    }
+
+    /// Deserialize a session from JSON.
+    pub fn from_json(json: &str) -> Result<Self, SignatureError> {
+        serde_json::from_str(json).map_err(SignatureError::from_source)
+    }
+
+    /// Serialize this session as BCS.
+    pub fn to_bcs(&self) -> Result<Vec<u8>, SignatureError> {
+        bcs::to_bytes(self).map_err(SignatureError::from_source)
+    }
+
+    /// Deserialize a session from BCS.
+    pub fn from_bcs(bytes: &[u8]) -> Result<Self, SignatureError> {
+        bcs::from_bytes(bytes).map_err(SignatureError::from_source)
+    }
+
+    fn member(&self, member_idx: u8) -> Result<&MultisigMember, SignatureError> {
+        self.committee
+            .members()
+            .get(member_idx as usize)
+            .ok_or_else(|| {
+                SignatureError::from_source(format!(
+                    "signature for member {member_idx} which is not part of the committee"
+                ))
+            })
+    }
+
+    fn insert_signature(
+        &mut self,
+        member_idx: u8,
+        signature: MultisigMemberSignature,
+        verifier: &MultisigVerifier,
+    ) -> Result<(), SignatureError> {
+        use std::collections::btree_map::Entry;
+
+        let member = self.member(member_idx)?;
+        verifier.verify_member_signature(
+            &self.signing_digest(),
+            member.public_key(),
+            &signature,
+        )?;
+
+        match self.signatures.entry(member_idx) {
+            Entry::Vacant(v) => {
+                v.insert(signature);
+                Ok(())
+            }
+            Entry::Occupied(_) => Err(SignatureError::from_source(
+                "duplicate signature from same committee member",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::Ed25519PrivateKey;
+    use crate::SuiVerifier;
+    use sui_sdk_types::MultisigMemberPublicKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn test_committee() -> (Vec<Ed25519PrivateKey>, MultisigCommittee) {
+        let keys = (1..=3)
+            .map(|i| Ed25519PrivateKey::new([i; Ed25519PrivateKey::LENGTH]))
+            .collect::<Vec<_>>();
+        let members = keys
+            .iter()
+            .map(|key| MultisigMember::new(MultisigMemberPublicKey::Ed25519(key.public_key()), 1))
+            .collect();
+        (keys, MultisigCommittee::new(members, 2))
+    }
+
+    #[test]
+    fn collect_signatures_across_sessions() {
+        let (keys, committee) = test_committee();
+        let message = PersonalMessage(b"hello world".into());
+        let session = MultisigSession::new_with_message(committee.clone(), &message);
+        assert_eq!(session.remaining_weight(), 2);
+        assert_eq!(session.pending_members().count(), 3);
+
+        // Each member signs their own copy of the session read from a file
+        let json = session.to_json().unwrap();
+        let mut alice = MultisigSession::from_json(&json).unwrap();
+        alice.sign(&keys[0]).unwrap();
+        let mut carol = MultisigSession::from_bcs(&session.to_bcs().unwrap()).unwrap();
+        carol.sign(&keys[2]).unwrap();
+        carol.finish().unwrap_err();
+
+        let mut session = session;
+        session
+            .merge(MultisigSession::from_json(&alice.to_json().unwrap()).unwrap())
+            .unwrap();
+        assert_eq!(session.remaining_weight(), 1);
+        assert!(!session.is_complete());
+        session.merge(carol.clone()).unwrap();
+        // Merging the same signatures again is a no-op
+        session.merge(carol).unwrap();
+
+        assert!(session.is_complete());
+        assert_eq!(
+            session.signers().collect::<Vec<_>>(),
+            [&committee.members()[0], &committee.members()[2]]
+        );
+        assert_eq!(
+            session.pending_members().collect::<Vec<_>>(),
+            [&committee.members()[1]]
+        );
+        session
+            .verify_signatures(&MultisigVerifier::default())
+            .unwrap();
+
+        let signature = UserSignature::Multisig(session.finish().unwrap());
+        MultisigVerifier::new()
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_signatures() {
+        let (keys, committee) = test_committee();
+        let message = PersonalMessage(b"hello world".into());
+        let mut session = MultisigSession::new_with_message(committee.clone(), &message);
+
+        // Not a member of the committee
+        session
+            .sign(&Ed25519PrivateKey::new([9; Ed25519PrivateKey::LENGTH]))
+            .unwrap_err();
+
+        // A session for a different message
+        let other = PersonalMessage(b"goodbye".into());
+        let mut other_session = MultisigSession::new_with_message(committee.clone(), &other);
+        other_session.sign(&keys[0]).unwrap();
+        session.merge(other_session.clone()).unwrap_err();
+
+        // A tampered session file
+        let mut tampered = session.clone();
+        tampered.signatures = other_session.signatures;
+        tampered.sign(&keys[2]).unwrap();
+        let tampered = MultisigSession::from_json(&tampered.to_json().unwrap()).unwrap();
+        assert!(tampered.is_complete());
+        tampered
+            .verify_signatures(&MultisigVerifier::default())
+            .unwrap_err();
+        tampered.finish().unwrap_err();
+        session.merge(tampered).unwrap_err();
+
+        // Sessions for a different committee
+        let (_, mut other_committee) = test_committee();
+        other_committee = MultisigCommittee::new(other_committee.members().to_vec(), 1);
+        session
+            .merge(MultisigSession::new_with_message(other_committee, &message))
+            .unwrap_err();
+
+        session.sign(&keys[1]).unwrap();
+        session.sign(&keys[1]).unwrap_err();
+    }
+
+    #[test]
+    fn reject_invalid_session_files() {
+        let (keys, committee) = test_committee();
+        let message = PersonalMessage(b"hello world".into());
+        let mut session = MultisigSession::new_with_message(committee.clone(), &message);
+        session.sign(&keys[0]).unwrap();
+
+        // A signature from a member index outside of the committee
+        let mut json: serde_json::Value =
+            serde_json::from_str(&session.to_json().unwrap()).unwrap();
+        let signature = json["signatures"]["0"].clone();
+        json["signatures"]["7"] = signature;
+        MultisigSession::from_json(&json.to_string()).unwrap_err();
+
+        // Committees which can never reach their threshold, or with zero weight members
+        for (members, threshold) in [
+            (committee.members().to_vec(), 4),
+            (committee.members().to_vec(), 0),
+            (
+                vec![MultisigMember::new(
+                    committee.members()[0].public_key().clone(),
+                    0,
+                )],
+                1,
+            ),
+        ] {
+            let invalid = MultisigSession {
+                committee: MultisigCommittee::new(members, threshold),
+                payload: session.payload.clone(),
+                signatures: Default::default(),
+            };
+            MultisigSession::from_json(&invalid.to_json().unwrap()).unwrap_err();
+            MultisigSession::from_bcs(&invalid.to_bcs().unwrap()).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn transaction_session_round_trip() {
+        let transaction = "AAAAACdZawPnpJRjmVcwDu6xrIumtq5NLO+6GHbs0iGdCoD7AQ0T0TolicYERdSvyCRjSSduDZLbSpBsZBoib+lF48EBcgAAAAAAAAAgpQr/Mudl9BdzyBdkbqTlqBw4/aJ21kAD/jpJKa05im4nWWsD56SUY5lXMA7usayLprauTSzvuhh27NIhnQqA++gDAAAAAAAAgIQeAAAAAAAA";
+        let transaction: Transaction = {
+            use base64ct::Encoding;
+            let bytes = base64ct::Base64::decode_vec(transaction).unwrap();
+            bcs::from_bytes(&bytes).unwrap()
+        };
+
+        let (keys, committee) = test_committee();
+        let mut session = MultisigSession::new_with_transaction(committee, transaction.clone());
+        session.sign(&keys[0]).unwrap();
+        session.sign(&keys[1]).unwrap();
+
+        let from_json = MultisigSession::from_json(&session.to_json().unwrap()).unwrap();
+        let from_bcs = MultisigSession::from_bcs(&session.to_bcs().unwrap()).unwrap();
+        assert_eq!(from_json, session);
+        assert_eq!(from_bcs, session);
+
+        let signature = UserSignature::Multisig(from_json.finish().unwrap());
+        MultisigVerifier::new()
+            .verify_transaction(&transaction, &signature)
+            .unwrap();
+    }
+}
@@ -1,7 +1,11 @@
+use crate::secp256r1::Secp256r1PrivateKey;
 use crate::secp256r1::Secp256r1VerifyingKey;
 use crate::SignatureError;
+use signature::Signer;
 use signature::Verifier;
 use sui_sdk_types::PasskeyAuthenticator;
+use sui_sdk_types::PasskeyPublicKey;
+use sui_sdk_types::Secp256r1Signature;
 use sui_sdk_types::SimpleSignature;
 use sui_sdk_types::UserSignature;
 
@@ -34,16 +38,10 @@ impl Verifier<PasskeyAuthenticator> for PasskeyVerifier {
             ));
         }
 
-        // Construct passkey signing message = authenticator_data || sha256(client_data_json).
-        let mut message = authenticator.authenticator_data().to_owned();
-        let client_data_hash = {
-            use sha2::Digest;
-
-            let mut hasher = sha2::Sha256::new();
-            hasher.update(authenticator.client_data_json().as_bytes());
-            hasher.finalize()
-        };
-        message.extend_from_slice(&client_data_hash);
+        let message = passkey_signing_message(
+            authenticator.authenticator_data(),
+            authenticator.client_data_json(),
+        );
 
         let verifying_key = Secp256r1VerifyingKey::new(&public_key)?;
 
@@ -61,6 +59,178 @@ impl Verifier<UserSignature> for PasskeyVerifier {
     }
 }
 
+/// Construct passkey signing message = authenticator_data || sha256(client_data_json).
+fn passkey_signing_message(authenticator_data: &[u8], client_data_json: &str) -> Vec<u8> {
+    use sha2::Digest;
+
+    let mut message = authenticator_data.to_owned();
+    message.extend_from_slice(&sha2::Sha256::digest(client_data_json.as_bytes()));
+    message
+}
+
+/// A software passkey authenticator.
+///
+/// This emulates a WebAuthn authenticator backed by a secp256r1 key held in memory, producing
+/// `PasskeyAuthenticator`s whose challenge is the message being signed. This is useful for tests
+/// as well as for custodial services which need to sign on behalf of a passkey account.
+#[derive(Debug)]
+pub struct SoftwarePasskey {
+    private_key: Secp256r1PrivateKey,
+    rp_id: String,
+    origin: String,
+}
+
+impl SoftwarePasskey {
+    /// User Present flag of the authenticator data.
+    const FLAG_USER_PRESENT: u8 = 0x01;
+    /// User Verified flag of the authenticator data.
+    const FLAG_USER_VERIFIED: u8 = 0x04;
+
+    /// Construct a passkey for the relying party `rp_id`, with an origin of `https://<rp_id>`.
+    pub fn new<S: Into<String>>(private_key: Secp256r1PrivateKey, rp_id: S) -> Self {
+        let rp_id = rp_id.into();
+        let origin = format!("https://{rp_id}");
+        Self {
+            private_key,
+            rp_id,
+            origin,
+        }
+    }
+
+    /// Set the origin reported in the `client_data_json`.
+    pub fn with_origin<S: Into<String>>(mut self, origin: S) -> Self {
+        self.origin = origin.into();
+        self
+    }
+
+    pub fn rp_id(&self) -> &str {
+        &self.rp_id
+    }
+
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    pub fn public_key(&self) -> PasskeyPublicKey {
+        PasskeyPublicKey::new(self.private_key.public_key())
+    }
+
+    /// The authenticator data for an assertion by this passkey.
+    ///
+    /// This is `sha256(rp_id) || flags || sign_count` with the user present and user verified
+    /// flags set and a signature counter of 0.
+    pub fn authenticator_data(&self) -> Vec<u8> {
+        use sha2::Digest;
+
+        let mut authenticator_data = sha2::Sha256::digest(self.rp_id.as_bytes()).to_vec();
+        authenticator_data.push(Self::FLAG_USER_PRESENT | Self::FLAG_USER_VERIFIED);
+        authenticator_data.extend_from_slice(&0u32.to_be_bytes());
+        authenticator_data
+    }
+
+    /// The `client_data_json` for an assertion of the provided `challenge`.
+    pub fn client_data_json(&self, challenge: &[u8]) -> String {
+        use base64ct::Encoding;
+
+        format!(
+            r#"{{"type":"webauthn.get","challenge":"{}","origin":{},"crossOrigin":false}}"#,
+            base64ct::Base64UrlUnpadded::encode_string(challenge),
+            serde_json::Value::from(self.origin.as_str()),
+        )
+    }
+}
+
+impl Signer<PasskeyAuthenticator> for SoftwarePasskey {
+    fn try_sign(&self, msg: &[u8]) -> Result<PasskeyAuthenticator, SignatureError> {
+        let authenticator_data = self.authenticator_data();
+        let client_data_json = self.client_data_json(msg);
+
+        let message = passkey_signing_message(&authenticator_data, &client_data_json);
+        let signature: Secp256r1Signature = self.private_key.try_sign(&message)?;
+
+        PasskeyAuthenticator::new(
+            authenticator_data,
+            client_data_json,
+            SimpleSignature::Secp256r1 {
+                signature: normalize_s(signature)?,
+                public_key: self.private_key.public_key(),
+            },
+        )
+        .ok_or_else(|| SignatureError::from_source("unable to construct passkey authenticator"))
+    }
+}
+
+impl Signer<UserSignature> for SoftwarePasskey {
+    fn try_sign(&self, msg: &[u8]) -> Result<UserSignature, SignatureError> {
+        <Self as Signer<PasskeyAuthenticator>>::try_sign(self, msg).map(UserSignature::Passkey)
+    }
+}
+
+/// Normalize the `s` component of a signature to be in the lower half of the curve order, which
+/// is required by Sui.
+fn normalize_s(signature: Secp256r1Signature) -> Result<Secp256r1Signature, SignatureError> {
+    let signature = p256::ecdsa::Signature::from_bytes(signature.inner().into())?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    Ok(Secp256r1Signature::new(signature.to_bytes().into()))
+}
+
+/// Parse the JSON encoding of a browser's WebAuthn `navigator.credentials.get()` response into a
+/// `PasskeyAuthenticator`.
+///
+/// The `json` can either be the full `PublicKeyCredential`, as produced by its `toJSON()` method,
+/// or only its `AuthenticatorAssertionResponse`. Binary fields are expected to be base64url
+/// encoded and the DER encoded signature is converted to its fixed size form.
+///
+/// An assertion doesn't include the passkey's public key so it must be provided by the caller,
+/// typically having been recorded when the passkey was registered.
+pub fn parse_webauthn_get_response(
+    json: &str,
+    public_key: PasskeyPublicKey,
+) -> Result<PasskeyAuthenticator, SignatureError> {
+    use base64ct::Base64UrlUnpadded;
+    use base64ct::Encoding;
+
+    #[derive(serde_derive::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AssertionResponse {
+        authenticator_data: String,
+        #[serde(rename = "clientDataJSON")]
+        client_data_json: String,
+        signature: String,
+    }
+
+    #[derive(serde_derive::Deserialize)]
+    #[serde(untagged)]
+    enum Response {
+        Credential { response: AssertionResponse },
+        Assertion(AssertionResponse),
+    }
+
+    fn decode(field: &str, value: &str) -> Result<Vec<u8>, SignatureError> {
+        Base64UrlUnpadded::decode_vec(value.trim_end_matches('='))
+            .map_err(|e| SignatureError::from_source(format!("invalid {field}: {e}")))
+    }
+
+    let (Response::Credential { response } | Response::Assertion(response)) =
+        serde_json::from_str(json).map_err(SignatureError::from_source)?;
+
+    let authenticator_data = decode("authenticatorData", &response.authenticator_data)?;
+    let client_data_json = String::from_utf8(decode("clientDataJSON", &response.client_data_json)?)
+        .map_err(SignatureError::from_source)?;
+    let signature = p256::ecdsa::Signature::from_der(&decode("signature", &response.signature)?)?;
+    let signature = Secp256r1Signature::new(signature.to_bytes().into());
+
+    PasskeyAuthenticator::new(
+        authenticator_data,
+        client_data_json,
+        SimpleSignature::Secp256r1 {
+            signature: normalize_s(signature)?,
+            public_key: *public_key.inner(),
+        },
+    )
+    .ok_or_else(|| SignatureError::from_source("invalid webauthn client data"))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -87,4 +257,75 @@ mod test {
             .verify_transaction(&transaction, &signature)
             .unwrap();
     }
+
+    #[test]
+    fn software_passkey() {
+        let passkey = SoftwarePasskey::new(Secp256r1PrivateKey::new([7; 32]), "example.com");
+        assert_eq!(passkey.origin(), "https://example.com");
+
+        let message = sui_sdk_types::PersonalMessage(b"hello world".into());
+        let signature = crate::SuiSigner::sign_personal_message(&passkey, &message).unwrap();
+        let UserSignature::Passkey(authenticator) = &signature else {
+            panic!("expected a passkey signature");
+        };
+        assert_eq!(authenticator.public_key(), passkey.public_key());
+        assert_eq!(authenticator.challenge(), message.signing_digest());
+
+        let verifier = PasskeyVerifier::new();
+        verifier
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+
+        // Round trip through the wire format
+        let signature = UserSignature::from_base64(&signature.to_base64()).unwrap();
+        verifier
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+
+        // A different message is rejected
+        let other = sui_sdk_types::PersonalMessage(b"goodbye".into());
+        verifier
+            .verify_personal_message(&other, &signature)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn webauthn_get_response() {
+        use base64ct::Base64UrlUnpadded;
+        use base64ct::Encoding;
+
+        let passkey = SoftwarePasskey::new(Secp256r1PrivateKey::new([9; 32]), "localhost")
+            .with_origin("http://localhost:5173");
+        let digest = [3; 32];
+        let authenticator: PasskeyAuthenticator = passkey.try_sign(&digest).unwrap();
+        let SimpleSignature::Secp256r1 { signature, .. } = authenticator.signature() else {
+            unreachable!()
+        };
+        let der = p256::ecdsa::Signature::from_bytes(signature.inner().into())
+            .unwrap()
+            .to_der();
+
+        let response = serde_json::json!({
+            "authenticatorData": Base64UrlUnpadded::encode_string(authenticator.authenticator_data()),
+            "clientDataJSON": Base64UrlUnpadded::encode_string(authenticator.client_data_json().as_bytes()),
+            "signature": Base64UrlUnpadded::encode_string(der.as_bytes()),
+            "userHandle": null,
+        });
+        let credential = serde_json::json!({
+            "id": "abc",
+            "rawId": "abc",
+            "type": "public-key",
+            "response": response,
+            "clientExtensionResults": {},
+        });
+
+        for json in [response, credential] {
+            let parsed =
+                parse_webauthn_get_response(&json.to_string(), passkey.public_key()).unwrap();
+            assert_eq!(parsed, authenticator);
+            PasskeyVerifier::new().verify(&digest, &parsed).unwrap();
+        }
+
+        parse_webauthn_get_response("{}", passkey.public_key()).unwrap_err();
+    }
 }
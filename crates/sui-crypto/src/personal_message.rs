@@ -0,0 +1,447 @@
+//! Helpers for signing and verifying personal messages the way wallets do.
+//!
+//! Wallets exchange personal message signatures as the base64 encoding of the serialized
+//! [`UserSignature`], and callers generally want to know not only that a signature is valid but
+//! that it was produced by a particular [`Address`]. On top of this, [`SignInChallenge`] defines a
+//! "sign-in with wallet" message which binds a signature to a domain, nonce, chain and expiry.
+
+use crate::SignatureError;
+use crate::SuiSigner;
+use crate::SuiVerifier;
+use crate::UserSignatureVerifier;
+use sui_sdk_types::Address;
+use sui_sdk_types::PersonalMessage;
+use sui_sdk_types::UserSignature;
+
+/// Sign `message` as a personal message, returning the signature in the base64 serialized format
+/// used by wallets.
+pub fn sign_personal_message_base64<S: SuiSigner + ?Sized>(
+    signer: &S,
+    message: &PersonalMessage<'_>,
+) -> Result<String, SignatureError> {
+    signer
+        .sign_personal_message(message)
+        .map(|signature| signature.to_base64())
+}
+
+impl UserSignatureVerifier {
+    /// Verify that `signature` is a valid signature over `message` produced by `address`.
+    pub fn verify_personal_message_from(
+        &self,
+        message: &PersonalMessage<'_>,
+        signature: &UserSignature,
+        address: &Address,
+    ) -> Result<(), SignatureError> {
+        if !signature
+            .derive_addresses()
+            .map_err(SignatureError::from_source)?
+            .contains(address)
+        {
+            return Err(SignatureError::from_source(format!(
+                "signature was not produced by {address}"
+            )));
+        }
+
+        self.verify_personal_message(message, signature)
+    }
+
+    /// Verify that the base64 serialized `signature` is a valid signature over `message` produced
+    /// by `address`, returning the decoded signature.
+    pub fn verify_personal_message_base64(
+        &self,
+        message: &PersonalMessage<'_>,
+        signature: &str,
+        address: &Address,
+    ) -> Result<UserSignature, SignatureError> {
+        let signature =
+            UserSignature::from_base64(signature).map_err(SignatureError::from_source)?;
+        self.verify_personal_message_from(message, &signature, address)?;
+        Ok(signature)
+    }
+}
+
+/// A "sign-in with wallet" challenge.
+///
+/// A service issues a challenge for the user's address, the user's wallet signs the text
+/// produced by [`SignInChallenge::to_message`] as a personal message, and the service verifies
+/// the signature using [`SignInChallenge::verify`]. The message has the following format, where
+/// the statement and expiration time are optional and timestamps are in milliseconds since the
+/// unix epoch:
+///
+/// ```text
+/// example.com wants you to sign in with your account:
+/// 0x0000000000000000000000000000000000000000000000000000000000000001
+///
+/// Sign in to Example
+///
+/// Chain: mainnet
+/// Nonce: 8b1f0e6c2d3a4b5c
+/// Issued At: 1700000000000
+/// Expiration Time: 1700000300000
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignInChallenge {
+    domain: String,
+    address: Address,
+    statement: Option<String>,
+    chain: String,
+    nonce: String,
+    issued_at_ms: u64,
+    expiration_time_ms: Option<u64>,
+}
+
+impl SignInChallenge {
+    const HEADER_SUFFIX: &'static str = " wants you to sign in with your account:";
+    const CHAIN: &'static str = "Chain: ";
+    const NONCE: &'static str = "Nonce: ";
+    const ISSUED_AT: &'static str = "Issued At: ";
+    const EXPIRATION_TIME: &'static str = "Expiration Time: ";
+
+    /// Construct a new challenge.
+    ///
+    /// Returns an error if `domain`, `chain` or `nonce` contain a line break, as they would
+    /// change the structure of the signed message.
+    pub fn new<D: Into<String>, C: Into<String>, N: Into<String>>(
+        domain: D,
+        address: Address,
+        chain: C,
+        nonce: N,
+        issued_at_ms: u64,
+    ) -> Result<Self, SignatureError> {
+        Ok(Self {
+            domain: Self::single_line("domain", domain.into())?,
+            address,
+            statement: None,
+            chain: Self::single_line("chain", chain.into())?,
+            nonce: Self::single_line("nonce", nonce.into())?,
+            issued_at_ms,
+            expiration_time_ms: None,
+        })
+    }
+
+    fn single_line(name: &str, value: String) -> Result<String, SignatureError> {
+        if value.contains(['\n', '\r']) {
+            return Err(SignatureError::from_source(format!(
+                "sign-in challenge {name} must not contain line breaks"
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Generate a random 128-bit nonce, hex encoded.
+    pub fn generate_nonce<R>(mut rng: R) -> String
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        let mut buf = [0; 16];
+        rng.fill_bytes(&mut buf);
+        buf.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// A human readable statement shown to the user.
+    ///
+    /// Returns an error if `statement` contains a line break or starts with `Chain: `, as either
+    /// would change the structure of the signed message.
+    pub fn with_statement<S: Into<String>>(mut self, statement: S) -> Result<Self, SignatureError> {
+        let statement = Self::single_line("statement", statement.into())?;
+        if statement.starts_with(Self::CHAIN) {
+            return Err(SignatureError::from_source(format!(
+                "sign-in challenge statement must not start with `{}`",
+                Self::CHAIN.trim_end()
+            )));
+        }
+        self.statement = Some(statement);
+        Ok(self)
+    }
+
+    pub fn with_expiration_time_ms(mut self, expiration_time_ms: u64) -> Self {
+        self.expiration_time_ms = Some(expiration_time_ms);
+        self
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn statement(&self) -> Option<&str> {
+        self.statement.as_deref()
+    }
+
+    pub fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn issued_at_ms(&self) -> u64 {
+        self.issued_at_ms
+    }
+
+    pub fn expiration_time_ms(&self) -> Option<u64> {
+        self.expiration_time_ms
+    }
+
+    /// The text of the challenge which is signed by the user.
+    pub fn to_message(&self) -> String {
+        let mut message = format!(
+            "{}{}\n{}\n\n",
+            self.domain,
+            Self::HEADER_SUFFIX,
+            self.address
+        );
+        if let Some(statement) = &self.statement {
+            message.push_str(statement);
+            message.push_str("\n\n");
+        }
+        message.push_str(&format!("{}{}\n", Self::CHAIN, self.chain));
+        message.push_str(&format!("{}{}\n", Self::NONCE, self.nonce));
+        message.push_str(&format!("{}{}", Self::ISSUED_AT, self.issued_at_ms));
+        if let Some(expiration_time_ms) = self.expiration_time_ms {
+            message.push_str(&format!(
+                "\n{}{}",
+                Self::EXPIRATION_TIME,
+                expiration_time_ms
+            ));
+        }
+        message
+    }
+
+    pub fn personal_message(&self) -> PersonalMessage<'static> {
+        PersonalMessage(self.to_message().into_bytes().into())
+    }
+
+    /// Parse a challenge from the text produced by [`SignInChallenge::to_message`].
+    pub fn parse(message: &str) -> Result<Self, SignatureError> {
+        fn invalid(reason: &str) -> SignatureError {
+            SignatureError::from_source(format!("invalid sign-in challenge: {reason}"))
+        }
+
+        fn field<'a>(line: Option<&'a str>, prefix: &str) -> Result<&'a str, SignatureError> {
+            line.and_then(|line| line.strip_prefix(prefix))
+                .ok_or_else(|| invalid(&format!("missing `{}`", prefix.trim_end())))
+        }
+
+        fn timestamp(value: &str) -> Result<u64, SignatureError> {
+            value.parse().map_err(|_| invalid("invalid timestamp"))
+        }
+
+        let mut lines = message.split('\n');
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(Self::HEADER_SUFFIX))
+            .ok_or_else(|| invalid("missing header"))?;
+        let address = lines
+            .next()
+            .ok_or_else(|| invalid("missing address"))?
+            .parse::<Address>()
+            .map_err(|_| invalid("invalid address"))?;
+        if lines.next() != Some("") {
+            return Err(invalid("missing blank line after address"));
+        }
+
+        let mut line = lines.next();
+        let statement = match line {
+            Some(l) if !l.starts_with(Self::CHAIN) => {
+                if lines.next() != Some("") {
+                    return Err(invalid("missing blank line after statement"));
+                }
+                line = lines.next();
+                Some(l.to_owned())
+            }
+            _ => None,
+        };
+
+        let chain = field(line, Self::CHAIN)?;
+        let nonce = field(lines.next(), Self::NONCE)?;
+        let issued_at_ms = timestamp(field(lines.next(), Self::ISSUED_AT)?)?;
+        let expiration_time_ms = lines
+            .next()
+            .map(|line| timestamp(field(Some(line), Self::EXPIRATION_TIME)?))
+            .transpose()?;
+
+        if lines.next().is_some() {
+            return Err(invalid("unexpected trailing content"));
+        }
+
+        Ok(Self {
+            domain: domain.to_owned(),
+            address,
+            statement,
+            chain: chain.to_owned(),
+            nonce: nonce.to_owned(),
+            issued_at_ms,
+            expiration_time_ms,
+        })
+    }
+
+    /// Sign this challenge, returning the signature in the base64 serialized format used by
+    /// wallets.
+    pub fn sign<S: SuiSigner + ?Sized>(&self, signer: &S) -> Result<String, SignatureError> {
+        sign_personal_message_base64(signer, &self.personal_message())
+    }
+
+    /// Verify that the base64 serialized `signature` was produced by this challenge's address
+    /// over this challenge, and that the challenge hasn't expired as of `now_ms`.
+    pub fn verify(
+        &self,
+        verifier: &UserSignatureVerifier,
+        signature: &str,
+        now_ms: u64,
+    ) -> Result<UserSignature, SignatureError> {
+        if now_ms < self.issued_at_ms {
+            return Err(SignatureError::from_source(
+                "sign-in challenge was issued in the future",
+            ));
+        }
+
+        if self
+            .expiration_time_ms
+            .is_some_and(|expiration_time_ms| now_ms >= expiration_time_ms)
+        {
+            return Err(SignatureError::from_source("sign-in challenge has expired"));
+        }
+
+        verifier.verify_personal_message_base64(&self.personal_message(), signature, &self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::Ed25519PrivateKey;
+    use crate::multisig::MultisigAggregator;
+    use crate::multisig::MultisigCommitteeBuilder;
+    use crate::secp256r1::Secp256r1PrivateKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn verify_base64_signature_against_address() {
+        let verifier = UserSignatureVerifier::new();
+        let key = Ed25519PrivateKey::new([1; Ed25519PrivateKey::LENGTH]);
+        let address = key.public_key().derive_address();
+        let message = PersonalMessage(b"hello world".into());
+
+        let signature = sign_personal_message_base64(&key, &message).unwrap();
+        verifier
+            .verify_personal_message_base64(&message, &signature, &address)
+            .unwrap();
+
+        // Wrong address
+        let other = Secp256r1PrivateKey::new([2; Secp256r1PrivateKey::LENGTH]);
+        verifier
+            .verify_personal_message_base64(
+                &message,
+                &signature,
+                &other.public_key().derive_address(),
+            )
+            .unwrap_err();
+
+        // Wrong message
+        verifier
+            .verify_personal_message_base64(
+                &PersonalMessage(b"goodbye".into()),
+                &signature,
+                &address,
+            )
+            .unwrap_err();
+
+        // Multisig signatures are checked against the committee's address
+        let committee = MultisigCommitteeBuilder::new()
+            .with_member(key.public_key(), 1)
+            .with_member(other.public_key(), 1)
+            .with_threshold(1)
+            .build()
+            .unwrap();
+        let mut aggregator = MultisigAggregator::new_with_message(committee.clone(), &message);
+        aggregator
+            .add_signature(other.sign_personal_message(&message).unwrap())
+            .unwrap();
+        let multisig = UserSignature::Multisig(aggregator.finish().unwrap());
+        assert_eq!(
+            multisig.derive_addresses().unwrap(),
+            [committee.derive_address()]
+        );
+        verifier
+            .verify_personal_message_base64(
+                &message,
+                &multisig.to_base64(),
+                &committee.derive_address(),
+            )
+            .unwrap();
+        verifier
+            .verify_personal_message_from(&message, &multisig, &address)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn sign_in_challenge() {
+        let verifier = UserSignatureVerifier::new();
+        let key = Ed25519PrivateKey::new([1; Ed25519PrivateKey::LENGTH]);
+        let address = key.public_key().derive_address();
+
+        let challenge = SignInChallenge::new("example.com", address, "mainnet", "abc123", 1_000)
+            .unwrap()
+            .with_statement("Sign in to Example")
+            .unwrap()
+            .with_expiration_time_ms(2_000);
+        let message = challenge.to_message();
+        assert_eq!(SignInChallenge::parse(&message).unwrap(), challenge);
+
+        let minimal =
+            SignInChallenge::new("example.com", address, "testnet", "abc123", 1_000).unwrap();
+        assert_eq!(
+            SignInChallenge::parse(&minimal.to_message()).unwrap(),
+            minimal
+        );
+        SignInChallenge::parse(&message.replace("Nonce", "nonce")).unwrap_err();
+
+        let signature = challenge.sign(&key).unwrap();
+        challenge.verify(&verifier, &signature, 1_500).unwrap();
+        challenge.verify(&verifier, &signature, 500).unwrap_err();
+        challenge.verify(&verifier, &signature, 2_000).unwrap_err();
+
+        // A signature over a different challenge
+        let other =
+            SignInChallenge::new("example.com", address, "mainnet", "def456", 1_000).unwrap();
+        other.verify(&verifier, &signature, 1_500).unwrap_err();
+    }
+
+    #[test]
+    fn sign_in_challenge_rejects_line_breaks() {
+        let address = Address::ZERO;
+
+        for value in ["a\nb", "a\rb", "a\r\n", "\n"] {
+            SignInChallenge::new(value, address, "mainnet", "abc123", 1_000).unwrap_err();
+            SignInChallenge::new("example.com", address, value, "abc123", 1_000).unwrap_err();
+            SignInChallenge::new("example.com", address, "mainnet", value, 1_000).unwrap_err();
+        }
+
+        let challenge =
+            SignInChallenge::new("example.com", address, "mainnet", "abc123", 1_000).unwrap();
+        // Otherwise an injected line could pose as a different nonce or expiration time
+        challenge
+            .clone()
+            .with_statement("Hello\nNonce: 000000")
+            .unwrap_err();
+        challenge.clone().with_statement("Hello\r").unwrap_err();
+        challenge
+            .clone()
+            .with_statement("Chain: testnet")
+            .unwrap_err();
+
+        let challenge = challenge.with_statement("Hello: world").unwrap();
+        assert_eq!(
+            SignInChallenge::parse(&challenge.to_message()).unwrap(),
+            challenge
+        );
+    }
+}
@@ -0,0 +1,1069 @@
+//! Authorization policies enforced before signing.
+//!
+//! A [`PolicySigner`] wraps another [`SuiSigner`] and evaluates a [`Policy`] against every
+//! transaction before delegating to it. Transactions which violate the policy are rejected with a
+//! [`PolicyError`] listing each [`PolicyViolation`], and every decision is recorded in an audit
+//! log.
+//!
+//! Policies can be loaded from TOML or JSON:
+//!
+//! ```
+//! use sui_crypto::policy::Policy;
+//!
+//! let policy = Policy::from_toml(
+//!     r#"
+//!     max_gas_budget = 50000000
+//!     require_expiration = true
+//!     allowed_recipients = ["0xb0b"]
+//!
+//!     [[allowed_move_calls]]
+//!     package = "0x2"
+//!     module = "coin"
+//!
+//!     [[spend_limits]]
+//!     coin_type = "0x2::bfc::BFC"
+//!     limit = 1000000000
+//!     window_seconds = 86400
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(policy.max_gas_budget, Some(50_000_000));
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use sui_sdk_types::Address;
+use sui_sdk_types::Argument;
+use sui_sdk_types::BalanceChange;
+use sui_sdk_types::Command;
+use sui_sdk_types::Identifier;
+use sui_sdk_types::Input;
+use sui_sdk_types::MoveCall;
+use sui_sdk_types::ObjectId;
+use sui_sdk_types::PersonalMessage;
+use sui_sdk_types::ProgrammableTransaction;
+use sui_sdk_types::StructTag;
+use sui_sdk_types::Transaction;
+use sui_sdk_types::TransactionDigest;
+use sui_sdk_types::TransactionExpiration;
+use sui_sdk_types::TransactionKind;
+use sui_sdk_types::TypeTag;
+use sui_sdk_types::UserSignature;
+
+use crate::SignatureError;
+use crate::SuiSigner;
+
+/// A set of rules a transaction must satisfy in order to be signed.
+///
+/// Every rule is optional and the default policy allows everything.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize,
+)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// The Move functions which may be called. If set, any call not matching one of these rules
+    /// is rejected.
+    pub allowed_move_calls: Option<Vec<MoveCallRule>>,
+    /// The maximum gas budget a transaction may set.
+    pub max_gas_budget: Option<u64>,
+    /// The addresses objects may be transferred to. If set, transfers to any other address, by a
+    /// `TransferObjects` command or a framework function such as `0x2::transfer::public_transfer`,
+    /// are rejected. Transfers back to the sender are always allowed.
+    ///
+    /// Functions of other packages may transfer objects they are given to any address, and so
+    /// should be restricted with `allowed_move_calls`.
+    pub allowed_recipients: Option<Vec<Address>>,
+    /// Limits on the amount of each coin type which may leave the sender.
+    pub spend_limits: Vec<SpendLimit>,
+    /// Reject transactions which don't expire.
+    pub require_expiration: bool,
+    /// Reject requests to sign personal messages.
+    pub deny_personal_messages: bool,
+}
+
+/// Matches calls to a package, optionally restricted to a module and function.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveCallRule {
+    pub package: ObjectId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<Identifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<Identifier>,
+}
+
+/// A limit on the amount of a coin type which may leave the sender.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpendLimit {
+    /// The type of the coin, e.g. `0x2::bfc::BFC`.
+    pub coin_type: TypeTag,
+    /// The maximum amount which may be spent.
+    pub limit: u64,
+    /// The window of time, ending when a transaction is signed, over which `limit` applies. If
+    /// not set, the limit applies to each transaction individually.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_seconds: Option<u64>,
+}
+
+impl Policy {
+    /// Parse a policy from TOML.
+    pub fn from_toml(s: &str) -> Result<Self, PolicyParseError> {
+        toml::from_str(s).map_err(|e| PolicyParseError(e.to_string()))
+    }
+
+    /// Parse a policy from JSON.
+    pub fn from_json(s: &str) -> Result<Self, PolicyParseError> {
+        serde_json::from_str(s).map_err(|e| PolicyParseError(e.to_string()))
+    }
+}
+
+impl MoveCallRule {
+    /// Returns true if a call to `package::module::function` matches this rule.
+    pub fn matches(&self, package: &ObjectId, module: &Identifier, function: &Identifier) -> bool {
+        &self.package == package
+            && self.module.as_ref().is_none_or(|m| m == module)
+            && self.function.as_ref().is_none_or(|f| f == function)
+    }
+}
+
+/// An error parsing a [`Policy`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyParseError(String);
+
+impl std::fmt::Display for PolicyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid policy: {}", self.0)
+    }
+}
+
+impl std::error::Error for PolicyParseError {}
+
+/// A reason for rejecting a signing request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// Only programmable transactions can be evaluated, and so other kinds are always rejected.
+    NotProgrammable,
+    MoveCallNotAllowed {
+        package: ObjectId,
+        module: Identifier,
+        function: Identifier,
+    },
+    GasBudgetExceeded {
+        budget: u64,
+        max: u64,
+    },
+    RecipientNotAllowed {
+        recipient: Address,
+    },
+    /// The recipient of a transfer is computed by the transaction, and so can't be checked
+    /// against the allowed recipients.
+    UnknownRecipient,
+    SpendLimitExceeded {
+        coin_type: TypeTag,
+        limit: u64,
+        /// The amount already spent within the limit's window.
+        spent: u128,
+        /// The amount this transaction would spend.
+        amount: u128,
+    },
+    /// The amount of `coin_type` spent by the transaction can't be determined without the
+    /// balance changes from a dry run, e.g. because the gas coin is passed to a Move function or
+    /// an object input may be a coin.
+    UnknownSpend {
+        coin_type: TypeTag,
+    },
+    MissingExpiration,
+    PersonalMessageNotAllowed,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyViolation::NotProgrammable => {
+                f.write_str("only programmable transactions can be signed")
+            }
+            PolicyViolation::MoveCallNotAllowed {
+                package,
+                module,
+                function,
+            } => write!(f, "call to {package}::{module}::{function} is not allowed"),
+            PolicyViolation::GasBudgetExceeded { budget, max } => {
+                write!(f, "gas budget {budget} exceeds the maximum of {max}")
+            }
+            PolicyViolation::RecipientNotAllowed { recipient } => {
+                write!(f, "transfer to {recipient} is not allowed")
+            }
+            PolicyViolation::UnknownRecipient => {
+                f.write_str("the recipient of a transfer can't be determined")
+            }
+            PolicyViolation::SpendLimitExceeded {
+                coin_type,
+                limit,
+                spent,
+                amount,
+            } => write!(
+                f,
+                "spending {amount} of {coin_type} exceeds the limit of {limit}, \
+                 {spent} has already been spent"
+            ),
+            PolicyViolation::UnknownSpend { coin_type } => write!(
+                f,
+                "the amount of {coin_type} spent can't be determined without balance changes"
+            ),
+            PolicyViolation::MissingExpiration => {
+                f.write_str("transactions must set an expiration epoch")
+            }
+            PolicyViolation::PersonalMessageNotAllowed => {
+                f.write_str("signing personal messages is not allowed")
+            }
+        }
+    }
+}
+
+/// The error returned when a signing request is rejected by a [`Policy`].
+///
+/// When signing through [`SuiSigner`] this is available as the source of the returned
+/// [`SignatureError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyError {
+    violations: Vec<PolicyViolation>,
+}
+
+impl PolicyError {
+    pub fn violations(&self) -> &[PolicyViolation] {
+        &self.violations
+    }
+}
+
+impl std::fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("rejected by policy: ")?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            violation.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// A record of a signing decision made by a [`PolicySigner`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// The digest of the transaction, or `None` for personal messages.
+    pub digest: Option<TransactionDigest>,
+    /// The time of the decision, in milliseconds since the unix epoch.
+    pub timestamp_ms: u64,
+    /// The violations which caused the request to be rejected, empty if it was approved.
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl AuditEntry {
+    pub fn is_approved(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Additional information used when evaluating a transaction against a [`Policy`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SigningContext<'a> {
+    balance_changes: Option<&'a [BalanceChange]>,
+    timestamp_ms: Option<u64>,
+}
+
+impl<'a> SigningContext<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the balance changes from a dry run of the transaction to determine the coins spent by
+    /// the sender.
+    ///
+    /// Without balance changes only spending of the gas coin, by the gas budget and coins split
+    /// from the gas coin, can be determined, and any spend limits on other coin types cause the
+    /// transaction to be rejected.
+    pub fn with_balance_changes(mut self, balance_changes: &'a [BalanceChange]) -> Self {
+        self.balance_changes = Some(balance_changes);
+        self
+    }
+
+    /// Evaluate the transaction at `timestamp_ms`, in milliseconds since the unix epoch, rather
+    /// than the current time.
+    pub fn with_timestamp_ms(mut self, timestamp_ms: u64) -> Self {
+        self.timestamp_ms = Some(timestamp_ms);
+        self
+    }
+
+    fn timestamp_ms(&self) -> u64 {
+        self.timestamp_ms.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// A [`SuiSigner`] which only signs requests satisfying a [`Policy`].
+#[derive(Debug)]
+pub struct PolicySigner<S> {
+    signer: S,
+    policy: Policy,
+    audit_log_capacity: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Coins spent by approved transactions within the largest spend limit window, used to
+    /// enforce windowed spend limits.
+    spends: Vec<Spend>,
+    audit_log: VecDeque<AuditEntry>,
+}
+
+#[derive(Debug)]
+struct Spend {
+    coin_type: TypeTag,
+    amount: u128,
+    timestamp_ms: u64,
+}
+
+impl State {
+    /// Record a decision, discarding the oldest ones beyond `capacity`.
+    fn record(&mut self, entry: AuditEntry, capacity: usize) {
+        self.audit_log.push_back(entry);
+        while self.audit_log.len() > capacity {
+            self.audit_log.pop_front();
+        }
+    }
+
+    /// Discard spends which are outside the window of every spend limit at `timestamp_ms`.
+    fn prune_spends(&mut self, policy: &Policy, timestamp_ms: u64) {
+        let max_window_seconds = policy
+            .spend_limits
+            .iter()
+            .filter_map(|limit| limit.window_seconds)
+            .max();
+        match max_window_seconds {
+            Some(window_seconds) => {
+                let start = timestamp_ms.saturating_sub(window_seconds.saturating_mul(1000));
+                self.spends.retain(|spend| spend.timestamp_ms > start);
+            }
+            None => self.spends.clear(),
+        }
+    }
+}
+
+impl<S: SuiSigner> PolicySigner<S> {
+    /// The number of decisions kept in the audit log by default.
+    pub const DEFAULT_AUDIT_LOG_CAPACITY: usize = 10_000;
+
+    pub fn new(signer: S, policy: Policy) -> Self {
+        Self {
+            signer,
+            policy,
+            audit_log_capacity: Self::DEFAULT_AUDIT_LOG_CAPACITY,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Keep at most `capacity` decisions in the audit log, discarding the oldest ones.
+    pub fn with_audit_log_capacity(mut self, capacity: usize) -> Self {
+        self.audit_log_capacity = capacity;
+        self
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.signer
+    }
+
+    /// Returns the most recent decisions, in order, up to the audit log's capacity.
+    pub fn audit_log(&self) -> Vec<AuditEntry> {
+        self.state().audit_log.iter().cloned().collect()
+    }
+
+    /// Removes and returns the decisions made so far, e.g. to persist them elsewhere.
+    pub fn take_audit_log(&self) -> Vec<AuditEntry> {
+        std::mem::take(&mut self.state().audit_log).into()
+    }
+
+    /// Evaluate `transaction` against the policy without signing it or recording the decision.
+    pub fn evaluate(
+        &self,
+        transaction: &Transaction,
+        context: SigningContext<'_>,
+    ) -> Result<(), PolicyError> {
+        let state = self.state();
+        self.evaluate_with_state(&state, transaction, &context, context.timestamp_ms())
+            .map(|_| ())
+    }
+
+    /// Sign `transaction` if it satisfies the policy, recording the decision in the audit log.
+    pub fn sign_transaction_with_context(
+        &self,
+        transaction: &Transaction,
+        context: SigningContext<'_>,
+    ) -> Result<UserSignature, SignatureError> {
+        let timestamp_ms = context.timestamp_ms();
+        let mut state = self.state();
+        let digest = Some(transaction.digest());
+
+        match self.evaluate_with_state(&state, transaction, &context, timestamp_ms) {
+            Ok(spends) => {
+                let signature = self.signer.sign_transaction(transaction)?;
+                state
+                    .spends
+                    .extend(spends.into_iter().map(|(coin_type, amount)| Spend {
+                        coin_type,
+                        amount,
+                        timestamp_ms,
+                    }));
+                state.prune_spends(&self.policy, timestamp_ms);
+                state.record(
+                    AuditEntry {
+                        digest,
+                        timestamp_ms,
+                        violations: Vec::new(),
+                    },
+                    self.audit_log_capacity,
+                );
+                Ok(signature)
+            }
+            Err(error) => {
+                state.record(
+                    AuditEntry {
+                        digest,
+                        timestamp_ms,
+                        violations: error.violations.clone(),
+                    },
+                    self.audit_log_capacity,
+                );
+                Err(SignatureError::from_source(error))
+            }
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the amount of each coin type spent by the transaction if it satisfies the policy.
+    fn evaluate_with_state(
+        &self,
+        state: &State,
+        transaction: &Transaction,
+        context: &SigningContext<'_>,
+        timestamp_ms: u64,
+    ) -> Result<Vec<(TypeTag, u128)>, PolicyError> {
+        let mut violations = Vec::new();
+
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            return Err(PolicyError {
+                violations: vec![PolicyViolation::NotProgrammable],
+            });
+        };
+
+        if let Some(max) = self.policy.max_gas_budget {
+            if transaction.gas_payment.budget > max {
+                violations.push(PolicyViolation::GasBudgetExceeded {
+                    budget: transaction.gas_payment.budget,
+                    max,
+                });
+            }
+        }
+
+        if self.policy.require_expiration && transaction.expiration == TransactionExpiration::None {
+            violations.push(PolicyViolation::MissingExpiration);
+        }
+
+        for command in &ptb.commands {
+            let recipient = match command {
+                Command::MoveCall(call) => {
+                    if let Some(rules) = &self.policy.allowed_move_calls {
+                        if !rules
+                            .iter()
+                            .any(|rule| rule.matches(&call.package, &call.module, &call.function))
+                        {
+                            violations.push(PolicyViolation::MoveCallNotAllowed {
+                                package: call.package,
+                                module: call.module.clone(),
+                                function: call.function.clone(),
+                            });
+                        }
+                    }
+
+                    let Some(index) = transfer_recipient_parameter(call) else {
+                        continue;
+                    };
+                    call.arguments
+                        .get(index)
+                        .and_then(|argument| ptb.pure_address(argument))
+                }
+                Command::TransferObjects(transfer) => ptb.pure_address(&transfer.address),
+                _ => continue,
+            };
+
+            let Some(allowed) = &self.policy.allowed_recipients else {
+                continue;
+            };
+            match recipient {
+                Some(recipient) if recipient == transaction.sender => {}
+                Some(recipient) if allowed.contains(&recipient) => {}
+                Some(recipient) => {
+                    violations.push(PolicyViolation::RecipientNotAllowed { recipient })
+                }
+                None => violations.push(PolicyViolation::UnknownRecipient),
+            }
+        }
+
+        let gas_coin_spend = gas_coin_spend(transaction, ptb);
+        let spends = match context.balance_changes {
+            Some(balance_changes) => balance_changes
+                .iter()
+                .filter(|change| change.address == transaction.sender && change.amount < 0)
+                .map(|change| (change.coin_type.clone(), change.amount.unsigned_abs()))
+                .collect(),
+            None => gas_coin_spend
+                .map(|amount| vec![(gas_coin_type(), amount)])
+                .unwrap_or_default(),
+        };
+
+        for limit in &self.policy.spend_limits {
+            let determined = context.balance_changes.is_some()
+                || (limit.coin_type == gas_coin_type() && gas_coin_spend.is_some());
+            if !determined {
+                violations.push(PolicyViolation::UnknownSpend {
+                    coin_type: limit.coin_type.clone(),
+                });
+                continue;
+            }
+
+            let amount = spends
+                .iter()
+                .filter(|(coin_type, _)| coin_type == &limit.coin_type)
+                .map(|(_, amount)| amount)
+                .sum::<u128>();
+            let spent = match limit.window_seconds {
+                Some(window_seconds) => {
+                    let start = timestamp_ms.saturating_sub(window_seconds.saturating_mul(1000));
+                    state
+                        .spends
+                        .iter()
+                        .filter(|spend| {
+                            spend.coin_type == limit.coin_type && spend.timestamp_ms > start
+                        })
+                        .map(|spend| spend.amount)
+                        .sum::<u128>()
+                }
+                None => 0,
+            };
+
+            if spent + amount > limit.limit as u128 {
+                violations.push(PolicyViolation::SpendLimitExceeded {
+                    coin_type: limit.coin_type.clone(),
+                    limit: limit.limit,
+                    spent,
+                    amount,
+                });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(spends)
+        } else {
+            Err(PolicyError { violations })
+        }
+    }
+}
+
+impl<S: SuiSigner> SuiSigner for PolicySigner<S> {
+    fn sign_transaction(&self, transaction: &Transaction) -> Result<UserSignature, SignatureError> {
+        self.sign_transaction_with_context(transaction, SigningContext::new())
+    }
+
+    fn sign_personal_message(
+        &self,
+        message: &PersonalMessage<'_>,
+    ) -> Result<UserSignature, SignatureError> {
+        let violations = if self.policy.deny_personal_messages {
+            vec![PolicyViolation::PersonalMessageNotAllowed]
+        } else {
+            Vec::new()
+        };
+
+        self.state().record(
+            AuditEntry {
+                digest: None,
+                timestamp_ms: SigningContext::new().timestamp_ms(),
+                violations: violations.clone(),
+            },
+            self.audit_log_capacity,
+        );
+
+        if violations.is_empty() {
+            self.signer.sign_personal_message(message)
+        } else {
+            Err(SignatureError::from_source(PolicyError { violations }))
+        }
+    }
+}
+
+fn gas_coin_type() -> TypeTag {
+    StructTag::gas_coin()
+        .is_coin()
+        .cloned()
+        .expect("gas coin is a coin")
+}
+
+/// Functions of the `0x2` framework package which transfer an object to an address, along with
+/// the index of their recipient parameter.
+const TRANSFER_FUNCTIONS: &[(&str, &str, usize)] = &[
+    ("transfer", "public_transfer", 1),
+    ("transfer", "transfer", 1),
+    ("pay", "split_and_transfer", 2),
+    ("bfc", "transfer", 1),
+];
+
+/// The index of the recipient parameter of `call`, if it calls a framework transfer function.
+fn transfer_recipient_parameter(call: &MoveCall) -> Option<usize> {
+    if Address::from(call.package) != Address::TWO {
+        return None;
+    }
+    TRANSFER_FUNCTIONS
+        .iter()
+        .find(|(module, function, _)| {
+            call.module.as_str() == *module && call.function.as_str() == *function
+        })
+        .map(|(_, _, index)| *index)
+}
+
+/// The amount of the gas coin spent by the sender, from the gas budget and coins split from the
+/// gas coin, or `None` if it can't be determined.
+///
+/// Only splitting pure amounts from the gas coin is understood. Any other use of the gas coin,
+/// e.g. passing it to a Move function, which may take any amount from it, merging it or
+/// transferring it, makes the amount unknown. So does any object input, as it may be a
+/// `Coin<BFC>` owned by the sender.
+fn gas_coin_spend(transaction: &Transaction, ptb: &ProgrammableTransaction) -> Option<u128> {
+    if ptb
+        .inputs
+        .iter()
+        .any(|input| matches!(input, Input::ImmutableOrOwned(_) | Input::Receiving(_)))
+    {
+        return None;
+    }
+
+    let mut amount = 0u128;
+    if transaction.gas_payment.owner == transaction.sender {
+        amount += transaction.gas_payment.budget as u128;
+    }
+
+    for command in &ptb.commands {
+        let uses_gas = match command {
+            Command::SplitCoins(split) if split.coin == Argument::Gas => {
+                for split_amount in ptb.split_amounts(split)? {
+                    amount += split_amount as u128;
+                }
+                split.amounts.contains(&Argument::Gas)
+            }
+            Command::SplitCoins(split) => split.amounts.contains(&Argument::Gas),
+            Command::MoveCall(call) => call.arguments.contains(&Argument::Gas),
+            Command::MergeCoins(merge) => {
+                merge.coin == Argument::Gas || merge.coins_to_merge.contains(&Argument::Gas)
+            }
+            Command::MakeMoveVector(make) => make.elements.contains(&Argument::Gas),
+            Command::TransferObjects(transfer) => {
+                transfer.objects.contains(&Argument::Gas) || transfer.address == Argument::Gas
+            }
+            Command::Upgrade(upgrade) => upgrade.ticket == Argument::Gas,
+            Command::Publish(_) => false,
+        };
+        if uses_gas {
+            return None;
+        }
+    }
+
+    Some(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::Ed25519PrivateKey;
+    use sui_sdk_types::GasPayment;
+    use sui_sdk_types::ObjectDigest;
+    use sui_sdk_types::ObjectReference;
+    use sui_sdk_types::SplitCoins;
+    use sui_sdk_types::TransferObjects;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const POLICY: &str = r#"
+        max_gas_budget = 1000
+        allowed_recipients = ["0xb0b"]
+
+        [[allowed_move_calls]]
+        package = "0x2"
+        module = "coin"
+
+        [[spend_limits]]
+        coin_type = "0x2::bfc::BFC"
+        limit = 10000
+        window_seconds = 60
+    "#;
+
+    fn transaction(sender: Address, recipient: Address, amount: u64) -> Transaction {
+        Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    Input::Pure {
+                        value: amount.to_le_bytes().to_vec(),
+                    },
+                    Input::Pure {
+                        value: recipient.as_bytes().to_vec(),
+                    },
+                ],
+                commands: vec![
+                    Command::SplitCoins(SplitCoins {
+                        coin: Argument::Gas,
+                        amounts: vec![Argument::Input(0)],
+                    }),
+                    Command::TransferObjects(TransferObjects {
+                        objects: vec![Argument::Result(0)],
+                        address: Argument::Input(1),
+                    }),
+                ],
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![],
+                owner: sender,
+                price: 1,
+                budget: 1000,
+            },
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let policy = Policy::from_toml(POLICY).unwrap();
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(Policy::from_json(&json).unwrap(), policy);
+        assert_eq!(Policy::from_json("{}").unwrap(), Policy::default());
+        assert!(Policy::from_toml("max_gas = 1").is_err());
+    }
+
+    #[test]
+    fn spend_limit_window() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let signer = PolicySigner::new(key, Policy::from_toml(POLICY).unwrap());
+        let recipient: Address = "0xb0b".parse().unwrap();
+
+        let at = |timestamp_ms| SigningContext::new().with_timestamp_ms(timestamp_ms);
+
+        // 1000 gas + 5000 transferred
+        let transaction = transaction(sender, recipient, 5000);
+        signer
+            .sign_transaction_with_context(&transaction, at(1_000))
+            .unwrap();
+
+        let error = signer
+            .sign_transaction_with_context(&transaction, at(2_000))
+            .unwrap_err();
+        let error = std::error::Error::source(&error)
+            .unwrap()
+            .downcast_ref::<PolicyError>()
+            .unwrap();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::SpendLimitExceeded {
+                coin_type: gas_coin_type(),
+                limit: 10000,
+                spent: 6000,
+                amount: 6000,
+            }]
+        );
+
+        // Once the window has passed the earlier spend no longer counts
+        signer
+            .sign_transaction_with_context(&transaction, at(62_000))
+            .unwrap();
+
+        // Spends outside of the window are discarded
+        assert_eq!(signer.state().spends.len(), 1);
+
+        let log = signer.audit_log();
+        assert_eq!(
+            log.iter().map(AuditEntry::is_approved).collect::<Vec<_>>(),
+            [true, false, true]
+        );
+        assert_eq!(log[0].digest, Some(transaction.digest()));
+    }
+
+    #[test]
+    fn audit_log_capacity() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let signer = PolicySigner::new(key, Policy::default()).with_audit_log_capacity(2);
+
+        let transactions = (1..=3)
+            .map(|amount| transaction(sender, Address::ZERO, amount))
+            .collect::<Vec<_>>();
+        for transaction in &transactions {
+            signer.sign_transaction(transaction).unwrap();
+        }
+
+        let digests = signer
+            .take_audit_log()
+            .into_iter()
+            .map(|entry| entry.digest)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            digests,
+            [
+                Some(transactions[1].digest()),
+                Some(transactions[2].digest())
+            ]
+        );
+        assert!(signer.audit_log().is_empty());
+    }
+
+    #[test]
+    fn gas_coin_passed_to_move_call() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let signer = PolicySigner::new(key, Policy::from_toml(POLICY).unwrap());
+        let recipient: Address = "0xb0b".parse().unwrap();
+
+        // `0x2::coin::split(Gas, amount)` instead of `SplitCoins(Gas, [amount])`
+        let mut transaction = transaction(sender, recipient, 1_000_000);
+        let TransactionKind::ProgrammableTransaction(ptb) = &mut transaction.kind else {
+            unreachable!()
+        };
+        ptb.commands[0] = Command::MoveCall(MoveCall {
+            package: Address::TWO.into(),
+            module: Identifier::new("coin").unwrap(),
+            function: Identifier::new("split").unwrap(),
+            type_arguments: vec![gas_coin_type()],
+            arguments: vec![Argument::Gas, Argument::Input(0)],
+        });
+
+        let error = signer
+            .evaluate(&transaction, SigningContext::new())
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::UnknownSpend {
+                coin_type: gas_coin_type(),
+            }]
+        );
+
+        // The balance changes from a dry run determine the amount spent
+        let balance_changes = [BalanceChange {
+            address: sender,
+            coin_type: gas_coin_type(),
+            amount: -1_001_000,
+        }];
+        let error = signer
+            .evaluate(
+                &transaction,
+                SigningContext::new().with_balance_changes(&balance_changes),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::SpendLimitExceeded {
+                coin_type: gas_coin_type(),
+                limit: 10000,
+                spent: 0,
+                amount: 1_001_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn input_coin_transfer() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let signer = PolicySigner::new(key, Policy::from_toml(POLICY).unwrap());
+        let recipient: Address = "0xb0b".parse().unwrap();
+
+        // Transfers a `Coin<BFC>` owned by the sender without touching the gas coin
+        let mut transaction = transaction(sender, recipient, 0);
+        let TransactionKind::ProgrammableTransaction(ptb) = &mut transaction.kind else {
+            unreachable!()
+        };
+        ptb.inputs[0] = Input::ImmutableOrOwned(ObjectReference::new(
+            "0xc014".parse().unwrap(),
+            1,
+            ObjectDigest::ZERO,
+        ));
+        ptb.commands = vec![Command::TransferObjects(TransferObjects {
+            objects: vec![Argument::Input(0)],
+            address: Argument::Input(1),
+        })];
+
+        let error = signer
+            .evaluate(&transaction, SigningContext::new())
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::UnknownSpend {
+                coin_type: gas_coin_type(),
+            }]
+        );
+
+        let balance_changes = [BalanceChange {
+            address: sender,
+            coin_type: gas_coin_type(),
+            amount: -50_000,
+        }];
+        let error = signer
+            .evaluate(
+                &transaction,
+                SigningContext::new().with_balance_changes(&balance_changes),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::SpendLimitExceeded {
+                coin_type: gas_coin_type(),
+                limit: 10000,
+                spent: 0,
+                amount: 50_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn framework_transfer_recipients() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let policy = Policy {
+            allowed_recipients: Some(vec!["0xb0b".parse().unwrap()]),
+            ..Default::default()
+        };
+        let signer = PolicySigner::new(key, policy);
+        let attacker: Address = "0xbad".parse().unwrap();
+
+        let public_transfer = |recipient: Address| Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    Input::ImmutableOrOwned(ObjectReference::new(
+                        "0x1234".parse().unwrap(),
+                        1,
+                        ObjectDigest::ZERO,
+                    )),
+                    Input::Pure {
+                        value: recipient.as_bytes().to_vec(),
+                    },
+                ],
+                commands: vec![Command::MoveCall(MoveCall {
+                    package: Address::TWO.into(),
+                    module: Identifier::new("transfer").unwrap(),
+                    function: Identifier::new("public_transfer").unwrap(),
+                    type_arguments: vec!["0x1234::nft::Nft".parse().unwrap()],
+                    arguments: vec![Argument::Input(0), Argument::Input(1)],
+                })],
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![],
+                owner: sender,
+                price: 1,
+                budget: 1000,
+            },
+            expiration: TransactionExpiration::None,
+        };
+
+        let error = signer
+            .evaluate(&public_transfer(attacker), SigningContext::new())
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [PolicyViolation::RecipientNotAllowed {
+                recipient: attacker
+            }]
+        );
+        signer
+            .evaluate(
+                &public_transfer("0xb0b".parse().unwrap()),
+                SigningContext::new(),
+            )
+            .unwrap();
+        signer
+            .evaluate(&public_transfer(sender), SigningContext::new())
+            .unwrap();
+    }
+
+    #[test]
+    fn violations() {
+        let key = Ed25519PrivateKey::new([7; Ed25519PrivateKey::LENGTH]);
+        let sender = key.public_key().derive_address();
+        let mut policy = Policy::from_toml(POLICY).unwrap();
+        policy.require_expiration = true;
+        policy.spend_limits.push(SpendLimit {
+            coin_type: "0x1234::token::TOKEN".parse().unwrap(),
+            limit: 1,
+            window_seconds: None,
+        });
+        let signer = PolicySigner::new(key, policy);
+
+        let mut transaction = transaction(sender, Address::ZERO, 1);
+        transaction.gas_payment.budget = 2000;
+        let TransactionKind::ProgrammableTransaction(ptb) = &mut transaction.kind else {
+            unreachable!()
+        };
+        ptb.commands.push(Command::MoveCall(MoveCall {
+            package: "0xdee9".parse().unwrap(),
+            module: Identifier::new("clob").unwrap(),
+            function: Identifier::new("swap").unwrap(),
+            type_arguments: vec![],
+            arguments: vec![],
+        }));
+
+        let error = signer
+            .evaluate(&transaction, SigningContext::new().with_timestamp_ms(0))
+            .unwrap_err();
+        assert_eq!(
+            error.violations(),
+            [
+                PolicyViolation::GasBudgetExceeded {
+                    budget: 2000,
+                    max: 1000
+                },
+                PolicyViolation::MissingExpiration,
+                PolicyViolation::RecipientNotAllowed {
+                    recipient: Address::ZERO
+                },
+                PolicyViolation::MoveCallNotAllowed {
+                    package: "0xdee9".parse().unwrap(),
+                    module: Identifier::new("clob").unwrap(),
+                    function: Identifier::new("swap").unwrap(),
+                },
+                PolicyViolation::UnknownSpend {
+                    coin_type: "0x1234::token::TOKEN".parse().unwrap()
+                },
+            ]
+        );
+        assert!(error
+            .to_string()
+            .starts_with("rejected by policy: gas budget 2000"));
+
+        // Evaluating doesn't record a decision
+        assert!(signer.audit_log().is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::SignatureError;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The reason a zklogin signature failed to verify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ZkLoginErrorKind {
+    /// The proof points are not valid points on the bn254 curve.
+    InvalidProofPoints,
+    /// The groth16 proof did not verify against the provided inputs.
+    InvalidProof,
+    /// The JWT header could not be decoded.
+    InvalidHeader,
+    /// The JWT header specifies an algorithm other than `RS256`.
+    UnsupportedAlgorithm,
+    /// The `iss` claim could not be decoded.
+    InvalidIss,
+    /// The JWT header, `iss` or another claim exceeds the maximum length supported by the
+    /// circuit.
+    OversizedInput,
+    /// The inputs could not be hashed into a public input of the circuit.
+    InvalidInput,
+    /// The modulus of the JWK could not be decoded or is larger than 2048 bits.
+    InvalidJwk,
+    /// No JWK is known for the `iss` and `kid` the proof was generated for.
+    JwkNotFound,
+    /// The signature's `max_epoch` has already passed.
+    Expired,
+    /// The signature's `max_epoch` is further in the future than is allowed.
+    MaxEpochTooLarge,
+    /// The signature by the ephemeral key is invalid.
+    InvalidSignature,
+}
+
+impl ZkLoginErrorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidProofPoints => "invalid proof points",
+            Self::InvalidProof => "groth16 proof verification failed",
+            Self::InvalidHeader => "invalid jwt header",
+            Self::UnsupportedAlgorithm => "unsupported jwt algorithm",
+            Self::InvalidIss => "invalid iss",
+            Self::OversizedInput => "input too long",
+            Self::InvalidInput => "invalid input",
+            Self::InvalidJwk => "invalid jwk",
+            Self::JwkNotFound => "jwk not found",
+            Self::Expired => "max_epoch expired",
+            Self::MaxEpochTooLarge => "max_epoch too large",
+            Self::InvalidSignature => "invalid ephemeral signature",
+        }
+    }
+}
+
+impl std::fmt::Display for ZkLoginErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error encountered while verifying a zklogin signature.
+///
+/// When returned via the [`Verifier`](signature::Verifier) impls of
+/// [`ZkloginVerifier`](super::ZkloginVerifier) this error is wrapped in a [`SignatureError`] and
+/// can be recovered using [`ZkLoginError::from_signature_error`].
+#[derive(Debug)]
+pub struct ZkLoginError {
+    kind: ZkLoginErrorKind,
+    source: Option<BoxError>,
+}
+
+impl ZkLoginError {
+    pub fn new(kind: ZkLoginErrorKind) -> Self {
+        Self { kind, source: None }
+    }
+
+    pub fn from_source<E: Into<BoxError>>(kind: ZkLoginErrorKind, source: E) -> Self {
+        Self {
+            kind,
+            source: Some(source.into()),
+        }
+    }
+
+    pub fn kind(&self) -> ZkLoginErrorKind {
+        self.kind
+    }
+
+    /// Recover the `ZkLoginError` wrapped by a [`SignatureError`], if any.
+    pub fn from_signature_error(error: &SignatureError) -> Option<&Self> {
+        std::error::Error::source(error).and_then(|source| source.downcast_ref())
+    }
+}
+
+impl std::fmt::Display for ZkLoginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "zklogin verification failed: {}", self.kind)?;
+        if let Some(source) = &self.source {
+            write!(f, ": {source}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ZkLoginError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+impl From<ZkLoginErrorKind> for ZkLoginError {
+    fn from(kind: ZkLoginErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl From<ZkLoginError> for SignatureError {
+    fn from(error: ZkLoginError) -> Self {
+        SignatureError::from_source(error)
+    }
+}
@@ -0,0 +1,468 @@
+//! Fetching and refreshing the set of JWKs used to verify zklogin signatures.
+//!
+//! JWKs can come from two places: directly from the OpenID providers' published JWK sets, or from
+//! the set of JWKs the validators have agreed upon which is stored on-chain in the
+//! `0x2::authenticator_state::AuthenticatorState` object. Both are modeled as a [`JwkProvider`],
+//! and a [`JwkRefresher`] is used to periodically load them into a [`ZkloginVerifier`], expiring
+//! JWKs which are no longer being observed.
+//!
+//! Fetching is best-effort: a provider which fails doesn't prevent the JWKs of the remaining
+//! providers from being loaded, and the errors encountered are reported alongside the result.
+
+use std::future::Future;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use sui_sdk_types::ActiveJwk;
+use sui_sdk_types::Jwk;
+use sui_sdk_types::JwkId;
+
+use super::ZkloginVerifier;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// An error encountered while fetching or parsing a set of JWKs.
+#[derive(Debug)]
+pub struct JwkError {
+    iss: Option<String>,
+    source: BoxError,
+}
+
+impl JwkError {
+    pub fn from_source<E: Into<BoxError>>(source: E) -> Self {
+        Self {
+            iss: None,
+            source: source.into(),
+        }
+    }
+
+    /// Attribute this error to the OpenID provider identified by `iss`.
+    pub fn with_iss<I: Into<String>>(mut self, iss: I) -> Self {
+        self.iss = Some(iss.into());
+        self
+    }
+
+    /// The `iss` of the OpenID provider whose JWKs couldn't be fetched, if known.
+    pub fn iss(&self) -> Option<&str> {
+        self.iss.as_deref()
+    }
+}
+
+impl std::fmt::Display for JwkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.iss {
+            Some(iss) => write!(f, "unable to fetch jwks for {iss}: {}", self.source),
+            None => write!(f, "unable to fetch jwks: {}", self.source),
+        }
+    }
+}
+
+impl std::error::Error for JwkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// An OpenID provider, identified by its `iss` claim, along with the url its JWK set is
+/// published at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OAuthProvider {
+    pub iss: String,
+    pub jwks_url: String,
+}
+
+impl OAuthProvider {
+    pub fn new<I: Into<String>, U: Into<String>>(iss: I, jwks_url: U) -> Self {
+        Self {
+            iss: iss.into(),
+            jwks_url: jwks_url.into(),
+        }
+    }
+
+    pub fn google() -> Self {
+        Self::new(
+            "https://accounts.google.com",
+            "https://www.googleapis.com/oauth2/v3/certs",
+        )
+    }
+
+    pub fn facebook() -> Self {
+        Self::new(
+            "https://www.facebook.com",
+            "https://www.facebook.com/.well-known/oauth/openid/jwks/",
+        )
+    }
+
+    pub fn twitch() -> Self {
+        Self::new(
+            "https://id.twitch.tv/oauth2",
+            "https://id.twitch.tv/oauth2/keys",
+        )
+    }
+
+    pub fn apple() -> Self {
+        Self::new(
+            "https://appleid.apple.com",
+            "https://appleid.apple.com/auth/keys",
+        )
+    }
+}
+
+/// Parse a JWK set, as published by an OpenID provider, into the set of JWKs usable for zklogin.
+///
+/// Only RSA keys used with `RS256` are supported by zklogin; any other keys in the set are
+/// skipped. Each returned JWK is stamped with the provided `epoch`.
+pub fn parse_jwks(iss: &str, body: &[u8], epoch: u64) -> Result<Vec<ActiveJwk>, JwkError> {
+    #[derive(serde_derive::Deserialize)]
+    struct JwkSet {
+        keys: Vec<RawJwk>,
+    }
+
+    #[derive(serde_derive::Deserialize)]
+    struct RawJwk {
+        kid: Option<String>,
+        kty: String,
+        e: Option<String>,
+        n: Option<String>,
+        alg: Option<String>,
+    }
+
+    let JwkSet { keys } = serde_json::from_slice(body).map_err(JwkError::from_source)?;
+
+    let jwks = keys
+        .into_iter()
+        .filter_map(|key| {
+            // Providers are inconsistent about including `alg`, so default to RS256 when missing
+            let alg = key.alg.unwrap_or_else(|| "RS256".to_owned());
+            if key.kty != "RSA" || alg != "RS256" {
+                return None;
+            }
+
+            Some(ActiveJwk {
+                jwk_id: JwkId {
+                    iss: iss.to_owned(),
+                    kid: key.kid?,
+                },
+                jwk: Jwk {
+                    kty: key.kty,
+                    e: key.e?.trim_end_matches('=').to_owned(),
+                    n: key.n?.trim_end_matches('=').to_owned(),
+                    alg,
+                },
+                epoch,
+            })
+        })
+        .collect();
+
+    Ok(jwks)
+}
+
+/// A source of JWKs.
+///
+/// This is implemented for closures of the form `Fn(u64) -> impl Future`, which makes it easy to
+/// load the JWKs stored on-chain using one of the available clients:
+///
+/// ```rust,ignore
+/// let provider = |_epoch| async {
+///     client.active_jwks().await.map_err(JwkError::from_source)
+/// };
+/// ```
+pub trait JwkProvider {
+    /// Fetch the current set of JWKs, stamping any JWKs which don't otherwise have an epoch
+    /// associated with them with the provided `epoch`.
+    fn fetch_jwks(&self, epoch: u64) -> impl Future<Output = Result<Vec<ActiveJwk>, JwkError>>;
+
+    /// Fetch the current set of JWKs on a best-effort basis, returning every JWK which could be
+    /// fetched along with the errors encountered.
+    ///
+    /// Providers which aggregate several sources override this so that one failing source
+    /// doesn't prevent the JWKs of the others from being returned.
+    fn fetch_jwks_best_effort(
+        &self,
+        epoch: u64,
+    ) -> impl Future<Output = (Vec<ActiveJwk>, Vec<JwkError>)> {
+        async move {
+            match self.fetch_jwks(epoch).await {
+                Ok(jwks) => (jwks, Vec::new()),
+                Err(e) => (Vec::new(), vec![e]),
+            }
+        }
+    }
+}
+
+impl<F, Fut> JwkProvider for F
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<Vec<ActiveJwk>, JwkError>>,
+{
+    fn fetch_jwks(&self, epoch: u64) -> impl Future<Output = Result<Vec<ActiveJwk>, JwkError>> {
+        self(epoch)
+    }
+}
+
+/// Fetches from each provider in turn. [`JwkProvider::fetch_jwks`] fails if any provider fails,
+/// while [`JwkProvider::fetch_jwks_best_effort`] collects the errors of each failing provider.
+impl<P: JwkProvider> JwkProvider for Vec<P> {
+    async fn fetch_jwks(&self, epoch: u64) -> Result<Vec<ActiveJwk>, JwkError> {
+        let mut jwks = Vec::new();
+        for provider in self {
+            jwks.extend(provider.fetch_jwks(epoch).await?);
+        }
+        Ok(jwks)
+    }
+
+    async fn fetch_jwks_best_effort(&self, epoch: u64) -> (Vec<ActiveJwk>, Vec<JwkError>) {
+        let mut jwks = Vec::new();
+        let mut errors = Vec::new();
+        for provider in self {
+            let (fetched, errs) = provider.fetch_jwks_best_effort(epoch).await;
+            jwks.extend(fetched);
+            errors.extend(errs);
+        }
+        (jwks, errors)
+    }
+}
+
+/// A [`JwkProvider`] which fetches JWK sets directly from a set of OpenID providers over HTTP.
+#[cfg(feature = "jwks")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "jwks")))]
+#[derive(Clone, Debug)]
+pub struct HttpJwkProvider {
+    client: reqwest::Client,
+    providers: Vec<OAuthProvider>,
+}
+
+#[cfg(feature = "jwks")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "jwks")))]
+impl HttpJwkProvider {
+    /// Construct a provider which fetches the JWK sets of the provided OpenID providers.
+    pub fn new<I: IntoIterator<Item = OAuthProvider>>(providers: I) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            providers: providers.into_iter().collect(),
+        }
+    }
+
+    /// Construct a provider for Google, Facebook, Twitch and Apple.
+    pub fn with_default_providers() -> Self {
+        Self::new([
+            OAuthProvider::google(),
+            OAuthProvider::facebook(),
+            OAuthProvider::twitch(),
+            OAuthProvider::apple(),
+        ])
+    }
+
+    /// Use the provided `reqwest::Client` for making requests.
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn providers(&self) -> &[OAuthProvider] {
+        &self.providers
+    }
+
+    async fn fetch_provider(
+        &self,
+        provider: &OAuthProvider,
+        epoch: u64,
+    ) -> Result<Vec<ActiveJwk>, JwkError> {
+        let body = self
+            .client
+            .get(&provider.jwks_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(JwkError::from_source)?
+            .bytes()
+            .await
+            .map_err(JwkError::from_source)?;
+
+        parse_jwks(&provider.iss, &body, epoch)
+    }
+
+    async fn fetch_provider_with_iss(
+        &self,
+        provider: &OAuthProvider,
+        epoch: u64,
+    ) -> Result<Vec<ActiveJwk>, JwkError> {
+        self.fetch_provider(provider, epoch)
+            .await
+            .map_err(|e| e.with_iss(&provider.iss))
+    }
+}
+
+#[cfg(feature = "jwks")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "jwks")))]
+impl JwkProvider for HttpJwkProvider {
+    async fn fetch_jwks(&self, epoch: u64) -> Result<Vec<ActiveJwk>, JwkError> {
+        let mut jwks = Vec::new();
+        for provider in &self.providers {
+            jwks.extend(self.fetch_provider_with_iss(provider, epoch).await?);
+        }
+        Ok(jwks)
+    }
+
+    async fn fetch_jwks_best_effort(&self, epoch: u64) -> (Vec<ActiveJwk>, Vec<JwkError>) {
+        let mut jwks = Vec::new();
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            match self.fetch_provider_with_iss(provider, epoch).await {
+                Ok(fetched) => jwks.extend(fetched),
+                Err(e) => errors.push(e),
+            }
+        }
+        (jwks, errors)
+    }
+}
+
+/// The outcome of a single [`JwkRefresher::refresh`].
+#[derive(Debug, Default)]
+pub struct JwkRefresh {
+    /// The number of JWKs loaded into the verifier.
+    pub updated: usize,
+    /// The number of JWKs expired from the verifier.
+    pub expired: usize,
+    /// The errors encountered fetching JWKs. JWKs of the providers which failed are not refreshed
+    /// and will eventually expire if the failures persist.
+    pub errors: Vec<JwkError>,
+}
+
+/// Keeps the JWKs of a [`ZkloginVerifier`] up to date using a [`JwkProvider`].
+///
+/// [`JwkRefresher::run`] refreshes a shared verifier every [`JwkRefresher::refresh_interval`].
+/// This crate doesn't depend on an async runtime, so the caller supplies the function used to
+/// sleep between refreshes along with the function used to look up the current epoch:
+///
+/// ```rust,ignore
+/// let verifier = RwLock::new(ZkloginVerifier::new_mainnet());
+/// let refresher = JwkRefresher::new(HttpJwkProvider::with_default_providers())
+///     .with_max_age_epochs(2);
+///
+/// refresher
+///     .run(
+///         &verifier,
+///         || async { Ok(client.epoch(None).await?.unwrap().epoch_id) },
+///         tokio::time::sleep,
+///         |outcome| println!("refreshed jwks: {outcome:?}"),
+///     )
+///     .await;
+/// ```
+///
+/// Alternatively [`JwkRefresher::refresh`] performs a single refresh, or
+/// [`JwkRefresher::fetch`] and [`JwkRefresher::apply`] can be used separately.
+#[derive(Clone, Debug)]
+pub struct JwkRefresher<P> {
+    provider: P,
+    refresh_interval: Duration,
+    max_age_epochs: Option<u64>,
+}
+
+impl<P: JwkProvider> JwkRefresher<P> {
+    const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            refresh_interval: Self::DEFAULT_REFRESH_INTERVAL,
+            max_age_epochs: None,
+        }
+    }
+
+    /// Set how often JWKs should be refreshed. Defaults to one hour.
+    pub fn with_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Expire JWKs which haven't been validated within the last `max_age_epochs` epochs. By
+    /// default JWKs are never expired.
+    pub fn with_max_age_epochs(mut self, max_age_epochs: u64) -> Self {
+        self.max_age_epochs = Some(max_age_epochs);
+        self
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    /// Fetch the current set of JWKs from the underlying provider on a best-effort basis,
+    /// returning the JWKs which could be fetched along with the errors encountered.
+    pub async fn fetch(&self, current_epoch: u64) -> (Vec<ActiveJwk>, Vec<JwkError>) {
+        self.provider.fetch_jwks_best_effort(current_epoch).await
+    }
+
+    /// Load `jwks` into `verifier`, expiring any JWKs which are too old, returning the number of
+    /// JWKs expired.
+    pub fn apply(
+        &self,
+        verifier: &mut ZkloginVerifier,
+        jwks: Vec<ActiveJwk>,
+        current_epoch: u64,
+    ) -> usize {
+        verifier.update_jwks(jwks);
+
+        self.max_age_epochs
+            .and_then(|max_age| current_epoch.checked_sub(max_age))
+            .map(|min_epoch| verifier.expire_jwks(min_epoch))
+            .unwrap_or(0)
+    }
+
+    /// Fetch the current set of JWKs and load them into `verifier`, expiring any JWKs which are
+    /// too old.
+    pub async fn refresh(&self, verifier: &mut ZkloginVerifier, current_epoch: u64) -> JwkRefresh {
+        let (jwks, errors) = self.fetch(current_epoch).await;
+        let updated = jwks.len();
+        let expired = self.apply(verifier, jwks, current_epoch);
+        JwkRefresh {
+            updated,
+            expired,
+            errors,
+        }
+    }
+
+    /// Refresh `verifier` every [`JwkRefresher::refresh_interval`], forever.
+    ///
+    /// Before each refresh `current_epoch` is used to look up the current epoch, and `sleep` is
+    /// used to wait between refreshes. `on_refresh` is called with the outcome of each refresh,
+    /// or the error returned by `current_epoch`, in which case that refresh is skipped. The lock
+    /// on `verifier` is only held while loading fetched JWKs, never while fetching them.
+    pub async fn run<E, EFut, S, SFut, R>(
+        &self,
+        verifier: &RwLock<ZkloginVerifier>,
+        mut current_epoch: E,
+        sleep: S,
+        mut on_refresh: R,
+    ) where
+        E: FnMut() -> EFut,
+        EFut: Future<Output = Result<u64, JwkError>>,
+        S: Fn(Duration) -> SFut,
+        SFut: Future<Output = ()>,
+        R: FnMut(Result<JwkRefresh, JwkError>),
+    {
+        loop {
+            let outcome = match current_epoch().await {
+                Ok(epoch) => {
+                    let (jwks, errors) = self.fetch(epoch).await;
+                    let updated = jwks.len();
+                    let mut verifier = verifier.write().unwrap_or_else(PoisonError::into_inner);
+                    let expired = self.apply(&mut verifier, jwks, epoch);
+                    Ok(JwkRefresh {
+                        updated,
+                        expired,
+                        errors,
+                    })
+                }
+                Err(e) => Err(e),
+            };
+            on_refresh(outcome);
+            sleep(self.refresh_interval).await;
+        }
+    }
+}
@@ -3,21 +3,91 @@ use std::collections::HashMap;
 use crate::SignatureError;
 use poseidon::POSEIDON;
 use signature::Verifier;
+use sui_sdk_types::ActiveJwk;
 use sui_sdk_types::Jwk;
 use sui_sdk_types::JwkId;
 use sui_sdk_types::UserSignature;
 use sui_sdk_types::ZkLoginAuthenticator;
 use sui_sdk_types::ZkLoginInputs;
 
+mod error;
+mod jwks;
 mod poseidon;
 mod verify;
 
+pub use error::ZkLoginError;
+pub use error::ZkLoginErrorKind;
+
+pub use jwks::parse_jwks;
+#[cfg(feature = "jwks")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "jwks")))]
+pub use jwks::HttpJwkProvider;
+pub use jwks::JwkError;
+pub use jwks::JwkProvider;
+pub use jwks::JwkRefresh;
+pub use jwks::JwkRefresher;
+pub use jwks::OAuthProvider;
+
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+mod sign;
+
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::compute_address_seed;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::compute_nonce;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::generate_randomness;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::public_identifier_from_jwt;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::ZkLoginProverResponse;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::ZkLoginSession;
+#[cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1"))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(feature = "ed25519", feature = "secp256r1", feature = "secp256k1")))
+)]
+pub use sign::ZkLoginSigner;
+
 #[cfg(test)]
 mod tests;
 
 pub struct ZkloginVerifier {
     proof_verifying_key: verify::VerifyingKey,
     jwks: HashMap<JwkId, Jwk>,
+    /// The epoch in which each JWK added via [`ZkloginVerifier::update_jwks`] was most recently
+    /// validated.
+    jwk_epochs: HashMap<JwkId, u64>,
+    context: Option<ZkLoginVerificationContext>,
 }
 
 impl ZkloginVerifier {
@@ -25,6 +95,8 @@ impl ZkloginVerifier {
         Self {
             proof_verifying_key,
             jwks: Default::default(),
+            jwk_epochs: Default::default(),
+            context: None,
         }
     }
 
@@ -36,32 +108,138 @@ impl ZkloginVerifier {
         Self::new(verify::VerifyingKey::new_dev())
     }
 
+    /// Check the `max_epoch` of signatures against the provided context when verifying.
+    ///
+    /// Without a context the `max_epoch` of a signature is not checked.
+    pub fn with_context(mut self, context: ZkLoginVerificationContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn context(&self) -> Option<&ZkLoginVerificationContext> {
+        self.context.as_ref()
+    }
+
+    pub fn set_context(&mut self, context: Option<ZkLoginVerificationContext>) {
+        self.context = context;
+    }
+
     pub fn jwks(&self) -> &HashMap<JwkId, Jwk> {
         &self.jwks
     }
 
+    /// Direct access to the set of JWKs.
+    ///
+    /// JWKs inserted here have no associated epoch and are never expired; prefer
+    /// [`ZkloginVerifier::update_jwks`], which records the epoch each JWK was validated in.
     pub fn jwks_mut(&mut self) -> &mut HashMap<JwkId, Jwk> {
         &mut self.jwks
     }
+
+    /// Insert or update a set of JWKs, recording the epoch in which each was validated.
+    ///
+    /// If a JWK with the same id is already known with a more recent epoch the existing entry is
+    /// kept.
+    pub fn update_jwks<I: IntoIterator<Item = ActiveJwk>>(&mut self, jwks: I) {
+        for ActiveJwk { jwk_id, jwk, epoch } in jwks {
+            if self.jwks.contains_key(&jwk_id)
+                && self
+                    .jwk_epochs
+                    .get(&jwk_id)
+                    .is_some_and(|known| *known > epoch)
+            {
+                continue;
+            }
+
+            self.jwk_epochs.insert(jwk_id.clone(), epoch);
+            self.jwks.insert(jwk_id, jwk);
+        }
+    }
+
+    /// Returns the epoch in which the JWK with the provided id was most recently validated, if it
+    /// was added via [`ZkloginVerifier::update_jwks`].
+    pub fn jwk_epoch(&self, id: &JwkId) -> Option<u64> {
+        if !self.jwks.contains_key(id) {
+            return None;
+        }
+        self.jwk_epochs.get(id).copied()
+    }
+
+    /// Remove all JWKs which were last validated before `min_epoch`, returning the number of
+    /// JWKs removed.
+    ///
+    /// JWKs inserted directly via [`ZkloginVerifier::jwks_mut`] have no associated epoch and are
+    /// never expired.
+    pub fn expire_jwks(&mut self, min_epoch: u64) -> usize {
+        // Forget the epochs of any JWKs removed via `jwks_mut`
+        let jwks = &self.jwks;
+        self.jwk_epochs.retain(|id, _| jwks.contains_key(id));
+
+        let expired = self
+            .jwk_epochs
+            .iter()
+            .filter(|(_, epoch)| **epoch < min_epoch)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
+        for id in &expired {
+            self.jwk_epochs.remove(id);
+            self.jwks.remove(id);
+        }
+
+        expired.len()
+    }
 }
 
-impl Verifier<ZkLoginAuthenticator> for ZkloginVerifier {
-    fn verify(
+impl ZkloginVerifier {
+    /// Verify a zklogin signature, checking its `max_epoch` against the configured
+    /// [`ZkLoginVerificationContext`], if any.
+    pub fn verify_zklogin(
         &self,
         message: &[u8],
         signature: &ZkLoginAuthenticator,
-    ) -> Result<(), SignatureError> {
+    ) -> Result<(), ZkLoginError> {
+        self.verify_zklogin_inner(message, signature, self.context.as_ref())
+    }
+
+    /// Verify a zklogin signature, checking its `max_epoch` against the provided `context`
+    /// instead of the configured one.
+    pub fn verify_zklogin_with_context(
+        &self,
+        message: &[u8],
+        signature: &ZkLoginAuthenticator,
+        context: &ZkLoginVerificationContext,
+    ) -> Result<(), ZkLoginError> {
+        self.verify_zklogin_inner(message, signature, Some(context))
+    }
+
+    fn verify_zklogin_inner(
+        &self,
+        message: &[u8],
+        signature: &ZkLoginAuthenticator,
+        context: Option<&ZkLoginVerificationContext>,
+    ) -> Result<(), ZkLoginError> {
+        // 0. check that the signature is valid in the current epoch
+        if let Some(context) = context {
+            context.check_max_epoch(signature.max_epoch)?;
+        }
+
         // 1. check that we have a valid corrisponding Jwk
         let jwt_details = JwtDetails::from_zklogin_inputs(&signature.inputs)?;
         let jwk = self.jwks.get(&jwt_details.id).ok_or_else(|| {
-            SignatureError::from_source(format!(
-                "unable to find corrisponding jwk with id '{:?}' for provided authenticator",
-                jwt_details.id
-            ))
+            ZkLoginError::from_source(
+                ZkLoginErrorKind::JwkNotFound,
+                format!(
+                    "unable to find corrisponding jwk with id '{:?}' for provided authenticator",
+                    jwt_details.id
+                ),
+            )
         })?;
 
         // 2. verify that the provided SimpleSignature is valid
-        crate::simple::SimpleVerifier.verify(message, &signature.signature)?;
+        crate::simple::SimpleVerifier
+            .verify(message, &signature.signature)
+            .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidSignature, e))?;
 
         // 3. verify groth16 proof
         self.proof_verifying_key.verify_zklogin(
@@ -73,6 +251,16 @@ impl Verifier<ZkLoginAuthenticator> for ZkloginVerifier {
     }
 }
 
+impl Verifier<ZkLoginAuthenticator> for ZkloginVerifier {
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &ZkLoginAuthenticator,
+    ) -> Result<(), SignatureError> {
+        self.verify_zklogin(message, signature).map_err(Into::into)
+    }
+}
+
 impl Verifier<UserSignature> for ZkloginVerifier {
     fn verify(&self, message: &[u8], signature: &UserSignature) -> Result<(), SignatureError> {
         let UserSignature::ZkLogin(zklogin_authenticator) = signature else {
@@ -83,6 +271,71 @@ impl Verifier<UserSignature> for ZkloginVerifier {
     }
 }
 
+/// The epoch information used to check that a zklogin signature is currently valid.
+///
+/// A signature is only valid up to and including its `max_epoch`, and a signature's `max_epoch`
+/// may be at most `max_epoch_upper_bound_delta` epochs after the current epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZkLoginVerificationContext {
+    current_epoch: u64,
+    max_epoch_upper_bound_delta: Option<u64>,
+}
+
+impl ZkLoginVerificationContext {
+    /// The maximum span between the current epoch and a signature's `max_epoch` allowed by
+    /// default, matching the protocol's `zklogin_max_epoch_upper_bound_delta`.
+    pub const DEFAULT_MAX_EPOCH_UPPER_BOUND_DELTA: u64 = 30;
+
+    pub fn new(current_epoch: u64) -> Self {
+        Self {
+            current_epoch,
+            max_epoch_upper_bound_delta: Some(Self::DEFAULT_MAX_EPOCH_UPPER_BOUND_DELTA),
+        }
+    }
+
+    /// Set the maximum span between the current epoch and a signature's `max_epoch`, or `None`
+    /// to not bound it.
+    pub fn with_max_epoch_upper_bound_delta(mut self, delta: Option<u64>) -> Self {
+        self.max_epoch_upper_bound_delta = delta;
+        self
+    }
+
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    pub fn max_epoch_upper_bound_delta(&self) -> Option<u64> {
+        self.max_epoch_upper_bound_delta
+    }
+
+    /// Check that a signature with the provided `max_epoch` is valid in the current epoch.
+    pub fn check_max_epoch(&self, max_epoch: u64) -> Result<(), ZkLoginError> {
+        if max_epoch < self.current_epoch {
+            return Err(ZkLoginError::from_source(
+                ZkLoginErrorKind::Expired,
+                format!(
+                    "max_epoch {max_epoch} is before the current epoch {}",
+                    self.current_epoch
+                ),
+            ));
+        }
+
+        if let Some(delta) = self.max_epoch_upper_bound_delta {
+            if max_epoch - self.current_epoch > delta {
+                return Err(ZkLoginError::from_source(
+                    ZkLoginErrorKind::MaxEpochTooLarge,
+                    format!(
+                        "max_epoch {max_epoch} is more than {delta} epochs after the current epoch {}",
+                        self.current_epoch
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// A structed of parsed JWT details, consists of kid, header, iss.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct JwtDetails {
@@ -91,10 +344,25 @@ struct JwtDetails {
 }
 
 impl JwtDetails {
-    fn from_zklogin_inputs(inputs: &ZkLoginInputs) -> Result<Self, SignatureError> {
+    fn from_zklogin_inputs(inputs: &ZkLoginInputs) -> Result<Self, ZkLoginError> {
+        if inputs.header_base64.len() > verify::MAX_HEADER_LEN as usize {
+            return Err(ZkLoginError::from_source(
+                ZkLoginErrorKind::OversizedInput,
+                "header too long",
+            ));
+        }
+        if inputs.iss_base64_details.value.len() > verify::MAX_ISS_LEN_B64 as usize {
+            return Err(ZkLoginError::from_source(
+                ZkLoginErrorKind::OversizedInput,
+                "iss too long",
+            ));
+        }
+
         let header = JwtHeader::from_base64(&inputs.header_base64)?;
         let id = JwkId {
-            iss: inputs.iss().map_err(SignatureError::from_source)?,
+            iss: inputs
+                .iss()
+                .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidIss, e))?,
             kid: header.kid.clone(),
         };
         Ok(JwtDetails { header, id })
@@ -111,7 +379,7 @@ struct JwtHeader {
 }
 
 impl JwtHeader {
-    fn from_base64(s: &str) -> Result<Self, SignatureError> {
+    fn from_base64(s: &str) -> Result<Self, ZkLoginError> {
         use base64ct::Base64UrlUnpadded;
         use base64ct::Encoding;
 
@@ -123,12 +391,16 @@ impl JwtHeader {
             typ: Option<String>,
         }
 
-        let header_bytes = Base64UrlUnpadded::decode_vec(s)
-            .map_err(|e| SignatureError::from_source(e.to_string()))?;
-        let Header { alg, kid, typ } =
-            serde_json::from_slice(&header_bytes).map_err(SignatureError::from_source)?;
+        let header_bytes = Base64UrlUnpadded::decode_vec(s).map_err(|e| {
+            ZkLoginError::from_source(ZkLoginErrorKind::InvalidHeader, e.to_string())
+        })?;
+        let Header { alg, kid, typ } = serde_json::from_slice(&header_bytes)
+            .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidHeader, e))?;
         if alg != "RS256" {
-            return Err(SignatureError::from_source("jwt alg must be RS256"));
+            return Err(ZkLoginError::from_source(
+                ZkLoginErrorKind::UnsupportedAlgorithm,
+                format!("jwt alg must be RS256, found {alg}"),
+            ));
         }
         Ok(Self { alg, kid, typ })
     }
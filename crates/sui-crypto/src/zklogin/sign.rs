@@ -0,0 +1,353 @@
+use ark_bn254::Fr;
+use ark_ff::BigInteger;
+use ark_ff::PrimeField;
+use signature::Signer;
+use sui_sdk_types::Bn254FieldElement;
+use sui_sdk_types::EpochId;
+use sui_sdk_types::MultisigMemberPublicKey;
+use sui_sdk_types::SimpleSignature;
+use sui_sdk_types::UserSignature;
+use sui_sdk_types::ZkLoginAuthenticator;
+use sui_sdk_types::ZkLoginClaim;
+use sui_sdk_types::ZkLoginInputs;
+use sui_sdk_types::ZkLoginProof;
+use sui_sdk_types::ZkLoginPublicIdentifier;
+
+use super::verify::bn254_to_fr;
+use super::verify::gen_address_seed;
+use super::verify::public_key_bytes_to_frs;
+use super::POSEIDON;
+use crate::simple::SimpleKeypair;
+use crate::SignatureError;
+
+/// The number of bytes of the poseidon hash used as the OAuth nonce.
+const NONCE_LENGTH: usize = 20;
+
+/// The number of random bytes used for the randomness committed to in the OAuth nonce.
+const RANDOMNESS_LENGTH: usize = 16;
+
+/// Generate the randomness committed to in the OAuth nonce of a zklogin login.
+pub fn generate_randomness<R>(mut rng: R) -> Bn254FieldElement
+where
+    R: rand_core::RngCore + rand_core::CryptoRng,
+{
+    let mut buf = [0; 32];
+    rng.fill_bytes(&mut buf[32 - RANDOMNESS_LENGTH..]);
+    Bn254FieldElement::new(buf)
+}
+
+/// Compute the nonce to include in the OAuth request of a zklogin login.
+///
+/// The nonce commits to the ephemeral public key which will sign transactions, the last epoch
+/// in which the resulting proof is valid, and some randomness to hide the ephemeral public key.
+/// It is the base64url encoding of the last 20 bytes of the poseidon hash of these values.
+pub fn compute_nonce(
+    ephemeral_public_key: &MultisigMemberPublicKey,
+    max_epoch: EpochId,
+    randomness: &Bn254FieldElement,
+) -> Result<String, SignatureError> {
+    use base64ct::Base64UrlUnpadded;
+    use base64ct::Encoding;
+
+    let (first, second) = public_key_bytes_to_frs(&flagged_public_key(ephemeral_public_key)?);
+    let hash = POSEIDON
+        .hash(&[first, second, Fr::from(max_epoch), bn254_to_fr(randomness)])
+        .map_err(SignatureError::from_source)?;
+
+    let bytes = hash.into_bigint().to_bytes_be();
+    Ok(Base64UrlUnpadded::encode_string(
+        &bytes[bytes.len() - NONCE_LENGTH..],
+    ))
+}
+
+/// Returns the public key prefixed by its signature scheme flag.
+fn flagged_public_key(public_key: &MultisigMemberPublicKey) -> Result<Vec<u8>, SignatureError> {
+    let (flag, bytes): (_, &[u8]) = match public_key {
+        MultisigMemberPublicKey::Ed25519(public_key) => {
+            (sui_sdk_types::SignatureScheme::Ed25519, public_key.inner())
+        }
+        MultisigMemberPublicKey::Secp256k1(public_key) => (
+            sui_sdk_types::SignatureScheme::Secp256k1,
+            public_key.inner(),
+        ),
+        MultisigMemberPublicKey::Secp256r1(public_key) => (
+            sui_sdk_types::SignatureScheme::Secp256r1,
+            public_key.inner(),
+        ),
+        MultisigMemberPublicKey::ZkLogin(_) | MultisigMemberPublicKey::Passkey(_) => {
+            return Err(SignatureError::from_source(
+                "ephemeral key must be an ed25519, secp256k1 or secp256r1 key",
+            ))
+        }
+    };
+
+    let mut buf = Vec::with_capacity(bytes.len() + 1);
+    buf.push(flag.to_u8());
+    buf.extend_from_slice(bytes);
+    Ok(buf)
+}
+
+/// Compute the address seed of a zklogin account from the user's salt and the values of the
+/// claims identifying them.
+///
+/// `claim_name` is generally `sub`, and `aud` is the OAuth client id.
+pub fn compute_address_seed(
+    salt: &Bn254FieldElement,
+    claim_name: &str,
+    claim_value: &str,
+    aud: &str,
+) -> Result<Bn254FieldElement, SignatureError> {
+    gen_address_seed(&salt.to_string(), claim_name, claim_value, aud)?
+        .parse()
+        .map_err(SignatureError::from_source)
+}
+
+/// Derive the public identifier of a zklogin account from a JWT returned by an OAuth provider
+/// and the user's salt.
+///
+/// The JWT's signature isn't checked. The account is identified by the `sub` claim for the
+/// `aud` client of the `iss` provider. The address of the account can be derived from the
+/// identifier with [`ZkLoginPublicIdentifier::derive_address`].
+pub fn public_identifier_from_jwt(
+    jwt: &str,
+    salt: &Bn254FieldElement,
+) -> Result<ZkLoginPublicIdentifier, SignatureError> {
+    use base64ct::Base64UrlUnpadded;
+    use base64ct::Encoding;
+
+    #[derive(serde_derive::Deserialize)]
+    #[serde(untagged)]
+    enum Aud {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    #[derive(serde_derive::Deserialize)]
+    struct Claims {
+        iss: String,
+        sub: String,
+        aud: Aud,
+    }
+
+    let mut parts = jwt.split('.');
+    let (Some(_header), Some(payload), Some(_signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(SignatureError::from_source("invalid jwt"));
+    };
+
+    let payload = Base64UrlUnpadded::decode_vec(payload)
+        .map_err(|e| SignatureError::from_source(e.to_string()))?;
+    let Claims { iss, sub, aud } =
+        serde_json::from_slice(&payload).map_err(SignatureError::from_source)?;
+    let aud = match aud {
+        Aud::One(aud) => aud,
+        Aud::Many(mut auds) if auds.len() == 1 => auds.remove(0),
+        Aud::Many(_) => {
+            return Err(SignatureError::from_source(
+                "jwt must have a single aud claim",
+            ))
+        }
+    };
+
+    // Google issues tokens with an iss missing its scheme, which is normalized when deriving
+    // addresses.
+    let iss = if iss == "accounts.google.com" {
+        "https://accounts.google.com".to_owned()
+    } else {
+        iss
+    };
+
+    let address_seed = compute_address_seed(salt, "sub", &sub, &aud)?;
+    ZkLoginPublicIdentifier::new(iss, address_seed)
+        .ok_or_else(|| SignatureError::from_source("invalid iss: too long"))
+}
+
+/// The proof returned by a zklogin prover service.
+///
+/// This deserializes from the JSON response of the prover, which has `proofPoints`,
+/// `issBase64Details` and `headerBase64` fields.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkLoginProverResponse {
+    pub proof_points: ZkLoginProof,
+    #[serde(with = "camel_case_claim")]
+    pub iss_base64_details: ZkLoginClaim,
+    pub header_base64: String,
+}
+
+impl ZkLoginProverResponse {
+    /// Combine the proof with the address seed of the account it was generated for.
+    pub fn into_inputs(self, address_seed: Bn254FieldElement) -> ZkLoginInputs {
+        ZkLoginInputs {
+            proof_points: self.proof_points,
+            iss_base64_details: self.iss_base64_details,
+            header_base64: self.header_base64,
+            address_seed,
+        }
+    }
+}
+
+mod camel_case_claim {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+    use sui_sdk_types::ZkLoginClaim;
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Claim {
+        value: String,
+        index_mod4: u8,
+    }
+
+    pub fn serialize<S: Serializer>(
+        claim: &ZkLoginClaim,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Claim {
+            value: claim.value.clone(),
+            index_mod4: claim.index_mod_4,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ZkLoginClaim, D::Error> {
+        let Claim { value, index_mod4 } = Claim::deserialize(deserializer)?;
+        Ok(ZkLoginClaim {
+            value,
+            index_mod_4: index_mod4,
+        })
+    }
+}
+
+/// The client side state of a zklogin login, from the OAuth request until a proof is received.
+///
+/// A login starts by generating an ephemeral key and committing to it, along with the last epoch
+/// it may be used in, via the [`nonce`](ZkLoginSession::nonce) included in the OAuth request.
+/// Once the OAuth provider returns a JWT, a proof for it is requested from a prover service and
+/// the session is [`finish`](ZkLoginSession::finish)ed into a [`ZkLoginSigner`].
+pub struct ZkLoginSession {
+    ephemeral_key: SimpleKeypair,
+    max_epoch: EpochId,
+    randomness: Bn254FieldElement,
+}
+
+impl ZkLoginSession {
+    pub fn new(
+        ephemeral_key: SimpleKeypair,
+        max_epoch: EpochId,
+        randomness: Bn254FieldElement,
+    ) -> Self {
+        Self {
+            ephemeral_key,
+            max_epoch,
+            randomness,
+        }
+    }
+
+    /// Start a session with a newly generated ed25519 ephemeral key and randomness.
+    #[cfg(feature = "ed25519")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "ed25519")))]
+    pub fn generate<R>(mut rng: R, max_epoch: EpochId) -> Self
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        let ephemeral_key = crate::ed25519::Ed25519PrivateKey::generate(&mut rng).into();
+        let randomness = generate_randomness(&mut rng);
+        Self::new(ephemeral_key, max_epoch, randomness)
+    }
+
+    pub fn ephemeral_key(&self) -> &SimpleKeypair {
+        &self.ephemeral_key
+    }
+
+    pub fn max_epoch(&self) -> EpochId {
+        self.max_epoch
+    }
+
+    pub fn randomness(&self) -> &Bn254FieldElement {
+        &self.randomness
+    }
+
+    /// The nonce to include in the OAuth request, see [`compute_nonce`].
+    pub fn nonce(&self) -> Result<String, SignatureError> {
+        compute_nonce(
+            &self.ephemeral_key.public_key(),
+            self.max_epoch,
+            &self.randomness,
+        )
+    }
+
+    /// Finish the login with the proof returned by the prover and the address seed of the
+    /// account, see [`compute_address_seed`].
+    pub fn finish(
+        self,
+        proof: ZkLoginProverResponse,
+        address_seed: Bn254FieldElement,
+    ) -> ZkLoginSigner {
+        ZkLoginSigner::new(
+            self.ephemeral_key,
+            proof.into_inputs(address_seed),
+            self.max_epoch,
+        )
+    }
+}
+
+/// Signs on behalf of a zklogin account using an ephemeral key attested to by a zklogin proof.
+///
+/// Signatures are valid up to and including `max_epoch`.
+pub struct ZkLoginSigner {
+    ephemeral_key: SimpleKeypair,
+    inputs: ZkLoginInputs,
+    max_epoch: EpochId,
+}
+
+impl ZkLoginSigner {
+    pub fn new(ephemeral_key: SimpleKeypair, inputs: ZkLoginInputs, max_epoch: EpochId) -> Self {
+        Self {
+            ephemeral_key,
+            inputs,
+            max_epoch,
+        }
+    }
+
+    pub fn ephemeral_key(&self) -> &SimpleKeypair {
+        &self.ephemeral_key
+    }
+
+    pub fn inputs(&self) -> &ZkLoginInputs {
+        &self.inputs
+    }
+
+    pub fn max_epoch(&self) -> EpochId {
+        self.max_epoch
+    }
+
+    pub fn public_identifier(&self) -> Result<ZkLoginPublicIdentifier, SignatureError> {
+        self.inputs
+            .public_identifier()
+            .map_err(SignatureError::from_source)
+    }
+}
+
+impl Signer<ZkLoginAuthenticator> for ZkLoginSigner {
+    fn try_sign(&self, msg: &[u8]) -> Result<ZkLoginAuthenticator, SignatureError> {
+        let signature: SimpleSignature = self.ephemeral_key.try_sign(msg)?;
+        Ok(ZkLoginAuthenticator {
+            inputs: self.inputs.clone(),
+            max_epoch: self.max_epoch,
+            signature,
+        })
+    }
+}
+
+impl Signer<UserSignature> for ZkLoginSigner {
+    fn try_sign(&self, msg: &[u8]) -> Result<UserSignature, SignatureError> {
+        <Self as Signer<ZkLoginAuthenticator>>::try_sign(self, msg)
+            .map(|authenticator| UserSignature::ZkLogin(Box::new(authenticator)))
+    }
+}
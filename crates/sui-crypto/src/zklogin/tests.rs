@@ -1,4 +1,5 @@
 use signature::Signer;
+use sui_sdk_types::Bn254FieldElement;
 use sui_sdk_types::PersonalMessage;
 
 use crate::ed25519::Ed25519PrivateKey;
@@ -79,3 +80,575 @@ fn zklogin_sign_personal_message() {
         .verify_personal_message(&message, &user_signature)
         .unwrap();
 }
+
+#[test]
+fn zklogin_signer() {
+    let message = PersonalMessage(b"hello world".into());
+
+    let (jwk, jwk_id, inputs, key, max_epoch) = test_zklogin_material();
+    let signer = ZkLoginSigner::new(key.into(), inputs, max_epoch);
+    assert_eq!(
+        signer.public_identifier().unwrap().iss(),
+        "https://id.twitch.tv/oauth2"
+    );
+
+    let mut verifier = ZkloginVerifier::new_dev();
+    verifier.update_jwks([ActiveJwk {
+        jwk_id,
+        jwk,
+        epoch: 0,
+    }]);
+
+    let signature = crate::SuiSigner::sign_personal_message(&signer, &message).unwrap();
+    verifier
+        .verify_personal_message(&message, &signature)
+        .unwrap();
+}
+
+#[test]
+fn zklogin_session() {
+    let (_, _, inputs, key, max_epoch) = test_zklogin_material();
+    let randomness: Bn254FieldElement = "100681567828351849884072155819400689117".parse().unwrap();
+    let session = ZkLoginSession::new(key.into(), max_epoch, randomness.clone());
+
+    // The nonce of the test JWT used by the Sui TypeScript SDK and fastcrypto, which was issued
+    // for this ephemeral key, max epoch and randomness
+    let nonce = session.nonce().unwrap();
+    assert_eq!(nonce, "hTPpgF7XAKbW37rEUS6pEVZqmoI");
+    assert_ne!(
+        nonce,
+        compute_nonce(
+            &session.ephemeral_key().public_key(),
+            max_epoch + 1,
+            &randomness
+        )
+        .unwrap()
+    );
+
+    let response: ZkLoginProverResponse = serde_json::from_value(serde_json::json!({
+        "proofPoints": serde_json::to_value(&inputs.proof_points).unwrap(),
+        "issBase64Details": {
+            "value": inputs.iss_base64_details.value,
+            "indexMod4": inputs.iss_base64_details.index_mod_4,
+        },
+        "headerBase64": inputs.header_base64,
+    }))
+    .unwrap();
+    let signer = session.finish(response, inputs.address_seed.clone());
+    assert_eq!(signer.inputs(), &inputs);
+    assert_eq!(signer.max_epoch(), max_epoch);
+}
+
+#[test]
+fn poseidon_known_answers() {
+    use ark_bn254::Fr;
+
+    // Reference values from circomlibjs
+    assert_eq!(
+        POSEIDON.hash(&[Fr::from(1u64)]).unwrap().to_string(),
+        "18586133768512220936620570745912940619677854269274689475585506675881198879027"
+    );
+    assert_eq!(
+        POSEIDON
+            .hash(&[Fr::from(1u64), Fr::from(2u64)])
+            .unwrap()
+            .to_string(),
+        "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+    );
+}
+
+#[test]
+fn zklogin_address_from_jwt() {
+    use base64ct::Base64UrlUnpadded;
+    use base64ct::Encoding;
+
+    let salt: Bn254FieldElement = "206703048842351542647799591018316385612".parse().unwrap();
+    let sub = "106294049240999307923";
+    let aud = "25769832374-famecqrhe2gkebt5fvqms2263046lj96.apps.googleusercontent.com";
+
+    let payload = serde_json::json!({
+        "iss": "accounts.google.com",
+        "sub": sub,
+        "aud": [aud],
+    });
+    let jwt = format!(
+        "eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl",
+        Base64UrlUnpadded::encode_string(payload.to_string().as_bytes())
+    );
+
+    let address_seed: Bn254FieldElement =
+        "13319968244245342702944364608316777772547259798425697923099390355538529931211"
+            .parse()
+            .unwrap();
+    assert_eq!(
+        compute_address_seed(&salt, "sub", sub, aud).unwrap(),
+        address_seed
+    );
+
+    let identifier = public_identifier_from_jwt(&jwt, &salt).unwrap();
+    assert_eq!(identifier.iss(), "https://accounts.google.com");
+    assert_eq!(identifier.address_seed(), &address_seed);
+
+    assert!(public_identifier_from_jwt("not.a-jwt", &salt).is_err());
+}
+
+#[test]
+fn zklogin_jwk_expiry() {
+    let message = PersonalMessage(b"hello world".into());
+
+    let (jwk, jwk_id, inputs, key, max_epoch) = test_zklogin_material();
+    let signer = ZkLoginSigner::new(key.into(), inputs, max_epoch);
+    let signature = crate::SuiSigner::sign_personal_message(&signer, &message).unwrap();
+
+    let manual_id = JwkId {
+        iss: "https://accounts.google.com".to_string(),
+        kid: "manual".to_string(),
+    };
+    let mut verifier = ZkloginVerifier::new_dev();
+    verifier.jwks_mut().insert(manual_id.clone(), jwk.clone());
+    verifier.update_jwks([ActiveJwk {
+        jwk_id: jwk_id.clone(),
+        jwk: jwk.clone(),
+        epoch: 5,
+    }]);
+    verifier
+        .verify_personal_message(&message, &signature)
+        .unwrap();
+
+    // Older observations don't regress the recorded epoch
+    verifier.update_jwks([ActiveJwk {
+        jwk_id: jwk_id.clone(),
+        jwk,
+        epoch: 3,
+    }]);
+    assert_eq!(verifier.jwk_epoch(&jwk_id), Some(5));
+
+    assert_eq!(verifier.expire_jwks(5), 0);
+    assert_eq!(verifier.expire_jwks(6), 1);
+    assert!(!verifier.jwks().contains_key(&jwk_id));
+    assert!(verifier.jwks().contains_key(&manual_id));
+    verifier
+        .verify_personal_message(&message, &signature)
+        .unwrap_err();
+
+    // Removing a jwk directly also forgets its epoch, so re-adding it with an older epoch works
+    verifier.update_jwks([ActiveJwk {
+        jwk_id: jwk_id.clone(),
+        jwk: verifier.jwks()[&manual_id].clone(),
+        epoch: 9,
+    }]);
+    verifier.jwks_mut().remove(&jwk_id);
+    assert_eq!(verifier.jwk_epoch(&jwk_id), None);
+    assert_eq!(verifier.expire_jwks(10), 0);
+    verifier.update_jwks([ActiveJwk {
+        jwk_id: jwk_id.clone(),
+        jwk: verifier.jwks()[&manual_id].clone(),
+        epoch: 2,
+    }]);
+    assert_eq!(verifier.jwk_epoch(&jwk_id), Some(2));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::test]
+async fn zklogin_jwk_refresher_best_effort() {
+    let (jwk, jwk_id, _, _, _) = test_zklogin_material();
+
+    let provider = |fail: bool| {
+        let jwk = ActiveJwk {
+            jwk_id: jwk_id.clone(),
+            jwk: jwk.clone(),
+            epoch: 0,
+        };
+        move |epoch: u64| {
+            let jwk = ActiveJwk {
+                epoch,
+                ..jwk.clone()
+            };
+            async move {
+                if fail {
+                    Err(JwkError::from_source("unavailable").with_iss("https://example.com"))
+                } else {
+                    Ok(vec![jwk])
+                }
+            }
+        }
+    };
+
+    // A failing provider doesn't prevent the others from being refreshed
+    let refresher = JwkRefresher::new(vec![provider(true), provider(false), provider(true)]);
+    let mut verifier = ZkloginVerifier::new_dev();
+    let outcome = refresher.refresh(&mut verifier, 3).await;
+    assert_eq!(outcome.updated, 1);
+    assert_eq!(outcome.expired, 0);
+    assert_eq!(outcome.errors.len(), 2);
+    assert_eq!(outcome.errors[0].iss(), Some("https://example.com"));
+    assert_eq!(verifier.jwk_epoch(&jwk_id), Some(3));
+
+    // Strict fetching still fails if any provider fails
+    assert!(refresher.provider().fetch_jwks(3).await.is_err());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::test]
+async fn zklogin_jwk_refresher_run() {
+    use std::future::Future;
+    use std::sync::RwLock;
+
+    let (jwk, jwk_id, _, _, _) = test_zklogin_material();
+    let jwk = ActiveJwk {
+        jwk_id: jwk_id.clone(),
+        jwk,
+        epoch: 0,
+    };
+    let refresher = JwkRefresher::new(move |epoch| {
+        let jwk = ActiveJwk {
+            epoch,
+            ..jwk.clone()
+        };
+        async move { Ok(vec![jwk]) }
+    })
+    .with_refresh_interval(std::time::Duration::from_millis(1));
+
+    let verifier = RwLock::new(ZkloginVerifier::new_dev());
+    let epochs = std::cell::Cell::new(0);
+    let outcomes = std::cell::RefCell::new(Vec::new());
+    {
+        let run = refresher.run(
+            &verifier,
+            || {
+                epochs.set(epochs.get() + 1);
+                let epoch = epochs.get();
+                async move {
+                    if epoch == 2 {
+                        Err(JwkError::from_source("epoch unavailable"))
+                    } else {
+                        Ok(epoch)
+                    }
+                }
+            },
+            // Yield once in place of sleeping so the loop can be driven by hand
+            |_| {
+                let mut slept = false;
+                std::future::poll_fn(move |_| {
+                    if std::mem::replace(&mut slept, true) {
+                        std::task::Poll::Ready(())
+                    } else {
+                        std::task::Poll::Pending
+                    }
+                })
+            },
+            |outcome| outcomes.borrow_mut().push(outcome),
+        );
+
+        // Drive the loop until three refreshes have been attempted
+        let mut run = std::pin::pin!(run);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        while outcomes.borrow().len() < 3 {
+            let _ = run.as_mut().poll(&mut cx);
+        }
+    }
+
+    let outcomes = outcomes.into_inner();
+    assert_eq!(outcomes[0].as_ref().unwrap().updated, 1);
+    assert!(outcomes[1].is_err());
+    assert!(outcomes[2].as_ref().unwrap().errors.is_empty());
+    assert_eq!(verifier.read().unwrap().jwk_epoch(&jwk_id), Some(3));
+}
+
+#[test]
+fn zklogin_parse_jwks() {
+    let body = serde_json::json!({
+        "keys": [
+            { "kty": "RSA", "kid": "a", "e": "AQAB", "n": "abc==", "alg": "RS256", "use": "sig" },
+            { "kty": "RSA", "kid": "b", "e": "AQAB", "n": "def" },
+            { "kty": "EC", "kid": "c", "crv": "P-256", "x": "x", "y": "y" },
+            { "kty": "RSA", "kid": "d", "e": "AQAB", "n": "ghi", "alg": "RS512" },
+        ]
+    });
+
+    let jwks = parse_jwks("https://appleid.apple.com", body.to_string().as_bytes(), 7).unwrap();
+    assert_eq!(jwks.len(), 2);
+    assert_eq!(jwks[0].jwk_id.iss, "https://appleid.apple.com");
+    assert_eq!(jwks[0].jwk_id.kid, "a");
+    assert_eq!(jwks[0].jwk.n, "abc");
+    assert_eq!(jwks[0].epoch, 7);
+    assert_eq!(jwks[1].jwk.alg, "RS256");
+
+    assert!(parse_jwks("https://appleid.apple.com", b"not json", 7).is_err());
+}
+
+#[cfg(all(feature = "jwks", not(target_arch = "wasm32")))]
+#[tokio::test]
+async fn zklogin_jwk_refresher_http() {
+    use std::io::Read;
+    use std::io::Write;
+
+    let message = PersonalMessage(b"hello world".into());
+    let (jwk, jwk_id, inputs, key, max_epoch) = test_zklogin_material();
+    let signer = ZkLoginSigner::new(key.into(), inputs, max_epoch);
+    let signature = crate::SuiSigner::sign_personal_message(&signer, &message).unwrap();
+
+    // A local stub serving a JWKS containing the test jwk
+    let mut key = serde_json::to_value(&jwk).unwrap();
+    key["kid"] = jwk_id.kid.clone().into();
+    let body = serde_json::json!({ "keys": [key] }).to_string();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+
+    // Along with a provider which can't be reached
+    let provider = HttpJwkProvider::new([
+        OAuthProvider::new("https://unreachable.example.com", "http://127.0.0.1:1/keys"),
+        OAuthProvider::new(jwk_id.iss.clone(), format!("http://{addr}/keys")),
+    ]);
+    let refresher = JwkRefresher::new(provider).with_max_age_epochs(1);
+
+    let mut verifier = ZkloginVerifier::new_dev();
+    let outcome = refresher.refresh(&mut verifier, 4).await;
+    assert_eq!(outcome.updated, 1);
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(
+        outcome.errors[0].iss(),
+        Some("https://unreachable.example.com")
+    );
+    assert_eq!(outcome.expired, 0);
+    assert_eq!(verifier.jwk_epoch(&jwk_id), Some(4));
+    verifier
+        .verify_personal_message(&message, &signature)
+        .unwrap();
+
+    // Once the provider stops serving the jwk it expires
+    let empty = JwkRefresher::new(|_epoch| async { Ok(Vec::new()) }).with_max_age_epochs(1);
+    assert_eq!(empty.refresh(&mut verifier, 5).await.expired, 0);
+    assert_eq!(empty.refresh(&mut verifier, 6).await.expired, 1);
+    verifier
+        .verify_personal_message(&message, &signature)
+        .unwrap_err();
+}
+
+fn test_zklogin_authenticator(
+    message: &PersonalMessage,
+) -> (ZkloginVerifier, ZkLoginAuthenticator) {
+    let (jwk, jwk_id, inputs, key, max_epoch) = test_zklogin_material();
+    let signature = key.sign(&message.signing_digest());
+    let authenticator = ZkLoginAuthenticator {
+        inputs,
+        max_epoch,
+        signature,
+    };
+    let mut verifier = ZkloginVerifier::new_dev();
+    verifier.update_jwks([ActiveJwk {
+        jwk_id,
+        jwk,
+        epoch: 0,
+    }]);
+    (verifier, authenticator)
+}
+
+fn assert_kind(result: Result<(), ZkLoginError>, kind: ZkLoginErrorKind) {
+    assert_eq!(result.unwrap_err().kind(), kind);
+}
+
+#[test]
+fn zklogin_epoch_context() {
+    use base64ct::Base64UrlUnpadded;
+    use base64ct::Encoding;
+
+    let message = PersonalMessage(b"hello world".into());
+    let digest = message.signing_digest();
+    let (verifier, authenticator) = test_zklogin_authenticator(&message);
+
+    // max_epoch is 10
+    for epoch in [0, 5, 10] {
+        verifier
+            .verify_zklogin_with_context(
+                &digest,
+                &authenticator,
+                &ZkLoginVerificationContext::new(epoch),
+            )
+            .unwrap();
+    }
+    assert_kind(
+        verifier.verify_zklogin_with_context(
+            &digest,
+            &authenticator,
+            &ZkLoginVerificationContext::new(11),
+        ),
+        ZkLoginErrorKind::Expired,
+    );
+    let context = ZkLoginVerificationContext::new(5).with_max_epoch_upper_bound_delta(Some(4));
+    assert_kind(
+        verifier.verify_zklogin_with_context(&digest, &authenticator, &context),
+        ZkLoginErrorKind::MaxEpochTooLarge,
+    );
+
+    // The configured context is used by the Verifier impls and the error kind can be recovered
+    let verifier = verifier.with_context(ZkLoginVerificationContext::new(11));
+    let error = verifier.verify(&digest, &authenticator).unwrap_err();
+    assert_eq!(
+        ZkLoginError::from_signature_error(&error).map(ZkLoginError::kind),
+        Some(ZkLoginErrorKind::Expired)
+    );
+
+    let (verifier, authenticator) = test_zklogin_authenticator(&message);
+
+    let mut unsupported = authenticator.clone();
+    unsupported.inputs.header_base64 =
+        Base64UrlUnpadded::encode_string(br#"{"alg":"RS512","typ":"JWT","kid":"1"}"#);
+    assert_kind(
+        verifier.verify_zklogin(&digest, &unsupported),
+        ZkLoginErrorKind::UnsupportedAlgorithm,
+    );
+
+    let mut oversized = authenticator.clone();
+    oversized.inputs.header_base64 = "a".repeat(249);
+    assert_kind(
+        verifier.verify_zklogin(&digest, &oversized),
+        ZkLoginErrorKind::OversizedInput,
+    );
+
+    let mut bad_points = authenticator.clone();
+    bad_points.inputs.proof_points.a.0[0] = Bn254FieldElement::new([1; 32]);
+    assert_kind(
+        verifier.verify_zklogin(&digest, &bad_points),
+        ZkLoginErrorKind::InvalidProofPoints,
+    );
+
+    let mut wrong_epoch = authenticator.clone();
+    wrong_epoch.max_epoch = 9;
+    assert_kind(
+        verifier.verify_zklogin(&digest, &wrong_epoch),
+        ZkLoginErrorKind::InvalidProof,
+    );
+
+    assert_kind(
+        verifier.verify_zklogin(b"wrong message", &authenticator),
+        ZkLoginErrorKind::InvalidSignature,
+    );
+
+    assert_kind(
+        ZkloginVerifier::new_dev().verify_zklogin(&digest, &authenticator),
+        ZkLoginErrorKind::JwkNotFound,
+    );
+}
+
+mod proptests {
+    use proptest::prelude::*;
+    use sui_sdk_types::CircomG1;
+    use sui_sdk_types::CircomG2;
+    use sui_sdk_types::ZkLoginClaim;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[proptest]
+    fn arbitrary_authenticator_never_panics(
+        authenticator: ZkLoginAuthenticator,
+        message: Vec<u8>,
+        current_epoch: u64,
+    ) {
+        let message = PersonalMessage(message.into());
+        let (verifier, _) = test_zklogin_authenticator(&message);
+        let _ = verifier.verify_zklogin(&message.signing_digest(), &authenticator);
+        let _ = verifier.verify_zklogin_with_context(
+            &message.signing_digest(),
+            &authenticator,
+            &ZkLoginVerificationContext::new(current_epoch),
+        );
+    }
+
+    #[proptest]
+    fn malformed_proof_points(a: CircomG1, b: CircomG2, c: CircomG1) {
+        let message = PersonalMessage(b"hello world".into());
+        let (verifier, mut authenticator) = test_zklogin_authenticator(&message);
+        authenticator.inputs.proof_points.a = a;
+        authenticator.inputs.proof_points.b = b;
+        authenticator.inputs.proof_points.c = c;
+
+        let kind = verifier
+            .verify_zklogin(&message.signing_digest(), &authenticator)
+            .unwrap_err()
+            .kind();
+        prop_assert!(matches!(
+            kind,
+            ZkLoginErrorKind::InvalidProofPoints | ZkLoginErrorKind::InvalidProof
+        ));
+    }
+
+    #[proptest]
+    fn malformed_claims(
+        #[strategy("[A-Za-z0-9_=.-]{0,300}")] header_base64: String,
+        #[strategy("[A-Za-z0-9_-]{0,300}")] iss: String,
+        #[strategy(0u8..5)] index_mod_4: u8,
+    ) {
+        let message = PersonalMessage(b"hello world".into());
+        let (verifier, authenticator) = test_zklogin_authenticator(&message);
+
+        let mut header = authenticator.clone();
+        header.inputs.header_base64 = header_base64;
+        let kind = verifier
+            .verify_zklogin(&message.signing_digest(), &header)
+            .unwrap_err()
+            .kind();
+        prop_assert!(matches!(
+            kind,
+            ZkLoginErrorKind::InvalidHeader
+                | ZkLoginErrorKind::UnsupportedAlgorithm
+                | ZkLoginErrorKind::OversizedInput
+                | ZkLoginErrorKind::JwkNotFound
+                | ZkLoginErrorKind::InvalidProof
+        ));
+
+        let mut claim = authenticator;
+        claim.inputs.iss_base64_details = ZkLoginClaim {
+            value: iss,
+            index_mod_4,
+        };
+        let kind = verifier
+            .verify_zklogin(&message.signing_digest(), &claim)
+            .unwrap_err()
+            .kind();
+        prop_assert!(matches!(
+            kind,
+            ZkLoginErrorKind::InvalidIss
+                | ZkLoginErrorKind::OversizedInput
+                | ZkLoginErrorKind::JwkNotFound
+                | ZkLoginErrorKind::InvalidProof
+        ));
+    }
+
+    // Most moduli decode successfully and run a full groth16 verification, so use fewer cases
+    #[proptest(cases = 32)]
+    fn malformed_jwk_modulus(#[strategy("[A-Za-z0-9_-]{0,400}")] n: String) {
+        let message = PersonalMessage(b"hello world".into());
+        let (mut verifier, authenticator) = test_zklogin_authenticator(&message);
+        for jwk in verifier.jwks_mut().values_mut() {
+            jwk.n = n.clone();
+        }
+
+        let kind = verifier
+            .verify_zklogin(&message.signing_digest(), &authenticator)
+            .unwrap_err()
+            .kind();
+        prop_assert!(matches!(
+            kind,
+            ZkLoginErrorKind::InvalidJwk | ZkLoginErrorKind::InvalidProof
+        ));
+    }
+}
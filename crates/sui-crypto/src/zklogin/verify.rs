@@ -22,6 +22,8 @@ use sui_sdk_types::SimpleSignature;
 use sui_sdk_types::ZkLoginInputs;
 use sui_sdk_types::ZkLoginProof;
 
+use super::ZkLoginError;
+use super::ZkLoginErrorKind;
 use super::POSEIDON;
 
 #[derive(Clone, Debug)]
@@ -48,20 +50,23 @@ const fn build_circom_g2([[e00, e01], [e10, e11], [e20, e21]]: [[&str; 2]; 3]) -
     ])
 }
 
-fn circom_to_arkworks_g1(g1: &CircomG1) -> Result<G1Affine, SignatureError> {
+fn circom_to_arkworks_g1(g1: &CircomG1) -> Result<G1Affine, ZkLoginError> {
     let CircomG1([f0, f1, f2]) = g1;
 
     let g1: G1Affine =
         G1Projective::new_unchecked(bn254_to_fq(f0), bn254_to_fq(f1), bn254_to_fq(f2)).into();
 
     if !g1.is_on_curve() || !g1.is_in_correct_subgroup_assuming_on_curve() {
-        return Err(SignatureError::from_source("invalid G1 input"));
+        return Err(ZkLoginError::from_source(
+            ZkLoginErrorKind::InvalidProofPoints,
+            "invalid G1 input",
+        ));
     }
 
     Ok(g1)
 }
 
-fn circom_to_arkworks_g2(g2: &CircomG2) -> Result<G2Affine, SignatureError> {
+fn circom_to_arkworks_g2(g2: &CircomG2) -> Result<G2Affine, ZkLoginError> {
     let CircomG2([[f00, f01], [f10, f11], [f20, f21]]) = g2;
 
     let g2: G2Affine = G2Projective::new_unchecked(
@@ -72,7 +77,10 @@ fn circom_to_arkworks_g2(g2: &CircomG2) -> Result<G2Affine, SignatureError> {
     .into();
 
     if !g2.is_on_curve() || !g2.is_in_correct_subgroup_assuming_on_curve() {
-        return Err(SignatureError::from_source("invalid G2 input"));
+        return Err(ZkLoginError::from_source(
+            ZkLoginErrorKind::InvalidProofPoints,
+            "invalid G2 input",
+        ));
     }
 
     Ok(g2)
@@ -82,7 +90,7 @@ fn bn254_to_fq(f: &Bn254FieldElement) -> Fq {
     Fq::from_be_bytes_mod_order(f.padded())
 }
 
-fn bn254_to_fr(f: &Bn254FieldElement) -> Fr {
+pub(crate) fn bn254_to_fr(f: &Bn254FieldElement) -> Fr {
     Fr::from_be_bytes_mod_order(f.padded())
 }
 
@@ -250,15 +258,15 @@ impl VerifyingKey {
         inputs: &ZkLoginInputs,
         signature: &SimpleSignature,
         max_epoch: u64,
-    ) -> Result<(), SignatureError> {
+    ) -> Result<(), ZkLoginError> {
         use base64ct::Base64UrlUnpadded;
         use base64ct::Encoding;
         // Decode modulus to bytes.
         let modulus = Base64UrlUnpadded::decode_vec(&jwk.n)
-            .map_err(|e| SignatureError::from_source(e.to_string()))?;
+            .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidJwk, e.to_string()))?;
 
-        let proof = zklogin_proof_to_arkworks(&inputs.proof_points).unwrap();
-        let input_hash = calculate_all_inputs_hash(inputs, signature, &modulus, max_epoch).unwrap();
+        let proof = zklogin_proof_to_arkworks(&inputs.proof_points)?;
+        let input_hash = calculate_all_inputs_hash(inputs, signature, &modulus, max_epoch)?;
 
         self.verify_proof(&proof, &[input_hash])
     }
@@ -267,7 +275,7 @@ impl VerifyingKey {
         &self,
         proof: &Proof<ark_bn254::Bn254>,
         public_inputs: &[ark_bn254::Fr],
-    ) -> Result<(), SignatureError> {
+    ) -> Result<(), ZkLoginError> {
         use ark_snark::SNARK;
 
         if ark_groth16::Groth16::<ark_bn254::Bn254>::verify_with_processed_vk(
@@ -275,18 +283,18 @@ impl VerifyingKey {
             public_inputs,
             proof,
         )
-        .map_err(|e| SignatureError::from_source(e.to_string()))?
+        .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidProof, e.to_string()))?
         {
             Ok(())
         } else {
-            Err(SignatureError::from_source("Groth16 proof verify failed"))
+            Err(ZkLoginErrorKind::InvalidProof.into())
         }
     }
 }
 
 fn zklogin_proof_to_arkworks(
     proof: &ZkLoginProof,
-) -> Result<Proof<ark_bn254::Bn254>, SignatureError> {
+) -> Result<Proof<ark_bn254::Bn254>, ZkLoginError> {
     Ok(Proof {
         a: circom_to_arkworks_g1(&proof.a)?,
         b: circom_to_arkworks_g2(&proof.b)?,
@@ -318,6 +326,11 @@ pub fn public_key_to_frs(signature: &SimpleSignature) -> (Fr, Fr) {
         }
     };
 
+    public_key_bytes_to_frs(buf)
+}
+
+/// Split a public key, prefixed with its signature scheme flag, into two Bn254Frs
+pub(crate) fn public_key_bytes_to_frs(buf: &[u8]) -> (Fr, Fr) {
     //TODO this comment is wrong...
     // Split the bytes deterministically such that the first element contains the first 128
     // bits of the hash, and the second element contains the latter ones.
@@ -331,25 +344,28 @@ pub fn public_key_to_frs(signature: &SimpleSignature) -> (Fr, Fr) {
 pub(crate) type U256 = bnum::BUintD8<32>;
 pub(crate) type U2048 = bnum::BUintD8<256>;
 
-const MAX_HEADER_LEN: u8 = 248;
+pub(super) const MAX_HEADER_LEN: u8 = 248;
 const PACK_WIDTH: u8 = 248;
 const MAX_EXT_ISS_LEN: u8 = 165;
-const MAX_ISS_LEN_B64: u8 = 4 * (1 + MAX_EXT_ISS_LEN / 3);
+pub(super) const MAX_ISS_LEN_B64: u8 = 4 * (1 + MAX_EXT_ISS_LEN / 3);
 
 /// Pads a stream of bytes and maps it to a field element
-pub fn hash_ascii_str_to_field(s: &str, max_size: u8) -> Result<Fr, SignatureError> {
+pub fn hash_ascii_str_to_field(s: &str, max_size: u8) -> Result<Fr, ZkLoginError> {
     let str_padded = str_to_padded_char_codes(s, max_size)?;
     hash_to_field(&str_padded, 8, PACK_WIDTH)
 }
 
-fn str_to_padded_char_codes(s: &str, max_len: u8) -> Result<Vec<U256>, SignatureError> {
+fn str_to_padded_char_codes(s: &str, max_len: u8) -> Result<Vec<U256>, ZkLoginError> {
     let arr: Vec<U256> = s.bytes().map(U256::from).collect();
     pad_with_zeroes(arr, max_len)
 }
 
-fn pad_with_zeroes(in_arr: Vec<U256>, out_count: u8) -> Result<Vec<U256>, SignatureError> {
+fn pad_with_zeroes(in_arr: Vec<U256>, out_count: u8) -> Result<Vec<U256>, ZkLoginError> {
     if in_arr.len() > out_count as usize {
-        return Err(SignatureError::from_source("in_arr too long"));
+        return Err(ZkLoginError::from_source(
+            ZkLoginErrorKind::OversizedInput,
+            format!("length {} exceeds maximum of {out_count}", in_arr.len()),
+        ));
     }
     let mut padded = in_arr;
     padded.resize(out_count as usize, U256::ZERO);
@@ -364,10 +380,12 @@ fn hash_to_field<T: ToBits>(
     input: &[T],
     in_width: u16,
     pack_width: u8,
-) -> Result<Fr, SignatureError> {
+) -> Result<Fr, ZkLoginError> {
     let packed = convert_base(input, in_width, pack_width)?;
 
-    POSEIDON.hash(&packed).map_err(SignatureError::from_source)
+    POSEIDON
+        .hash(&packed)
+        .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidInput, e))
 }
 
 /// Helper function to pack field elements from big ints.
@@ -375,9 +393,9 @@ fn convert_base<T: ToBits>(
     in_arr: &[T],
     in_width: u16,
     out_width: u8,
-) -> Result<Vec<Fr>, SignatureError> {
+) -> Result<Vec<Fr>, ZkLoginError> {
     if out_width == 0 {
-        return Err(SignatureError::from_source("invalid input"));
+        return Err(ZkLoginErrorKind::InvalidInput.into());
     }
     let bits = big_int_array_to_bits(in_arr, in_width as usize)?;
     let mut packed: Vec<Fr> = bits
@@ -388,7 +406,7 @@ fn convert_base<T: ToBits>(
         .collect();
     packed.reverse();
     match packed.len() != (in_arr.len() * in_width as usize).div_ceil(out_width as usize) {
-        true => Err(SignatureError::from_source("invalid input")),
+        true => Err(ZkLoginErrorKind::InvalidInput.into()),
         false => Ok(packed),
     }
 }
@@ -397,7 +415,7 @@ fn convert_base<T: ToBits>(
 fn big_int_array_to_bits<T: ToBits>(
     integers: &[T],
     intended_size: usize,
-) -> Result<Vec<u8>, SignatureError> {
+) -> Result<Vec<u8>, ZkLoginError> {
     use itertools::Itertools;
     use std::cmp::Ordering::Equal;
     use std::cmp::Ordering::Greater;
@@ -415,7 +433,7 @@ fn big_int_array_to_bits<T: ToBits>(
                     Ok(padded)
                 }
                 Equal => Ok(bits),
-                Greater => Err(SignatureError::from_source("invalid input")),
+                Greater => Err(ZkLoginError::from(ZkLoginErrorKind::InvalidInput)),
             }
         })
         .flatten_ok()
@@ -444,9 +462,12 @@ pub fn calculate_all_inputs_hash(
     signature: &SimpleSignature,
     modulus: &[u8],
     max_epoch: u64,
-) -> Result<Fr, SignatureError> {
+) -> Result<Fr, ZkLoginError> {
     if inputs.header_base64.len() > MAX_HEADER_LEN as usize {
-        return Err(SignatureError::from_source("header too long"));
+        return Err(ZkLoginError::from_source(
+            ZkLoginErrorKind::OversizedInput,
+            "header too long",
+        ));
     }
 
     let (first, second) = public_key_to_frs(signature);
@@ -461,7 +482,13 @@ pub fn calculate_all_inputs_hash(
 
     let iss_base64_f = hash_ascii_str_to_field(&inputs.iss_base64_details.value, MAX_ISS_LEN_B64)?;
     let header_f = hash_ascii_str_to_field(&inputs.header_base64, MAX_HEADER_LEN)?;
-    let modulus_f = hash_to_field(&[U2048::from_be_slice(modulus).unwrap()], 2048, PACK_WIDTH)?;
+    let modulus = U2048::from_be_slice(modulus).ok_or_else(|| {
+        ZkLoginError::from_source(
+            ZkLoginErrorKind::InvalidJwk,
+            "modulus larger than 2048 bits",
+        )
+    })?;
+    let modulus_f = hash_to_field(&[modulus], 2048, PACK_WIDTH)?;
 
     POSEIDON
         .hash(&[
@@ -474,12 +501,12 @@ pub fn calculate_all_inputs_hash(
             header_f,
             modulus_f,
         ])
-        .map_err(SignatureError::from_source)
+        .map_err(|e| ZkLoginError::from_source(ZkLoginErrorKind::InvalidInput, e))
 }
 
 /// Calculate the Sui address based on address seed and address params.
 #[allow(unused)]
-fn gen_address_seed(
+pub(crate) fn gen_address_seed(
     salt: &str,
     name: &str,  // i.e. "sub"
     value: &str, // i.e. the sub value
@@ -0,0 +1,252 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64ct::Encoding;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use reqwest::header::AUTHORIZATION;
+use reqwest::Url;
+
+use crate::error::Error;
+use crate::error::Kind;
+use crate::error::Result;
+use crate::Client;
+use crate::GraphQlTransport;
+use crate::ReqwestTransport;
+use crate::USER_AGENT;
+
+/// Builder for a [`Client`] with custom headers, authentication, or HTTP transport.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use sui_graphql_client::Client;
+///
+/// # fn main() -> sui_graphql_client::error::Result<()> {
+/// let client = Client::builder("https://graphql.example.com/graphql")
+///     .with_bearer_auth("my-api-token")
+///     .with_header("x-request-source", "indexer")
+///     .with_timeout(Duration::from_secs(10))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClientBuilder {
+    server: String,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<reqwest::Proxy>,
+    transport: Option<Arc<dyn GraphQlTransport>>,
+    /// The first error encountered while configuring the builder, returned from `build`.
+    error: Option<Error>,
+}
+
+impl ClientBuilder {
+    /// Create a builder for a client of the GraphQL server at `server`.
+    pub fn new(server: &str) -> Self {
+        Self {
+            server: server.to_owned(),
+            headers: HeaderMap::new(),
+            timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: None,
+            transport: None,
+            error: None,
+        }
+    }
+
+    /// Add a header sent with every request, including when establishing subscriptions.
+    pub fn with_header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: std::error::Error + Send + Sync + 'static,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let header = HeaderName::try_from(name)
+            .map_err(|e| Error::from_error(Kind::Parse, e))
+            .and_then(|name| {
+                HeaderValue::try_from(value)
+                    .map(|value| (name, value))
+                    .map_err(|e| Error::from_error(Kind::Parse, e))
+            });
+
+        match header {
+            Ok((name, value)) => {
+                self.headers.append(name, value);
+            }
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
+    /// Add headers sent with every request, including when establishing subscriptions.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Enable HTTP basic authentication with a username and optional password.
+    pub fn with_basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: std::fmt::Display,
+        P: std::fmt::Display,
+    {
+        let credentials = match password {
+            Some(password) => format!("{username}:{password}"),
+            None => format!("{username}:"),
+        };
+        let encoded = base64ct::Base64::encode_string(credentials.as_bytes());
+        self.with_authorization(format!("Basic {encoded}"))
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn with_bearer_auth<T>(self, token: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.with_authorization(format!("Bearer {token}"))
+    }
+
+    fn with_authorization(mut self, value: String) -> Self {
+        match HeaderValue::try_from(value) {
+            Ok(mut value) => {
+                value.set_sensitive(true);
+                self.headers.insert(AUTHORIZATION, value);
+            }
+            Err(e) => {
+                self.error.get_or_insert(Error::from_error(Kind::Parse, e));
+            }
+        }
+        self
+    }
+
+    /// Set the timeout applied to every request.
+    ///
+    /// This only applies to the default transport and is ignored if a custom transport is
+    /// provided with [`ClientBuilder::with_transport`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send requests through `proxy`.
+    ///
+    /// This only applies to the default transport and is ignored if a custom transport is
+    /// provided with [`ClientBuilder::with_transport`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Send requests using `transport` instead of the default `reqwest` based transport.
+    pub fn with_transport<T: GraphQlTransport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn build(self) -> Result<Client> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let rpc = Url::parse(&self.server)?;
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                #[allow(unused_mut)]
+                let mut builder = reqwest::Client::builder().user_agent(USER_AGENT);
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+
+                let mut transport = ReqwestTransport::new(builder.build()?);
+                if let Some(timeout) = self.timeout {
+                    transport = transport.with_timeout(timeout);
+                }
+                Arc::new(transport)
+            }
+        };
+
+        Ok(Client {
+            rpc,
+            transport,
+            headers: self.headers,
+            service_config: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            subscription: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            reconnect_policy: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// A transport which records the headers of each request and responds with a fixed body.
+    #[derive(Default)]
+    struct MockTransport {
+        requests: Arc<Mutex<Vec<(HeaderMap, serde_json::Value)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl GraphQlTransport for MockTransport {
+        async fn post(&self, url: &Url, headers: &HeaderMap, body: Vec<u8>) -> Result<Vec<u8>> {
+            assert_eq!(url.as_str(), "http://localhost:9125/graphql");
+            let body = serde_json::from_slice(&body).unwrap();
+            self.requests.lock().unwrap().push((headers.clone(), body));
+            Ok(br#"{"data":{"chainIdentifier":"4c78adac"}}"#.to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_transport_and_headers() {
+        let transport = MockTransport::default();
+        let requests = transport.requests.clone();
+
+        let client = Client::builder("http://localhost:9125/graphql")
+            .with_bearer_auth("token")
+            .with_header("x-api-key", "key")
+            .with_transport(transport)
+            .build()
+            .unwrap();
+        assert_eq!(client.chain_id().await.unwrap(), "4c78adac");
+
+        let requests = requests.lock().unwrap();
+        let (headers, body) = &requests[0];
+        assert_eq!(headers[AUTHORIZATION], "Bearer token");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+        assert_eq!(headers["x-api-key"], "key");
+        assert!(body["query"].as_str().unwrap().contains("chainIdentifier"));
+    }
+
+    #[test]
+    fn basic_auth() {
+        let builder = ClientBuilder::new("http://localhost").with_basic_auth("user", Some("pass"));
+        assert_eq!(builder.headers[AUTHORIZATION], "Basic dXNlcjpwYXNz");
+
+        let builder = ClientBuilder::new("http://localhost").with_basic_auth("user", None::<&str>);
+        assert_eq!(builder.headers[AUTHORIZATION], "Basic dXNlcjo=");
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        assert!(ClientBuilder::new("http://localhost")
+            .with_header("invalid header", "value")
+            .build()
+            .is_err());
+        assert!(ClientBuilder::new("not a url").build().is_err());
+    }
+}
@@ -8,6 +8,7 @@ use cynic::GraphQlError;
 
 use sui_types::AddressParseError;
 use sui_types::DigestParseError;
+use sui_types::EventDecodeError;
 use sui_types::TypeParseError;
 
 type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -124,6 +125,12 @@ impl From<bcs::Error> for Error {
     }
 }
 
+impl From<EventDecodeError> for Error {
+    fn from(error: EventDecodeError) -> Self {
+        Self::from_error(Kind::Deserialization, error)
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
         Self::from_error(Kind::Other, error)
@@ -3,10 +3,20 @@
 
 #![doc = include_str!("../README.md")]
 
+mod builder;
 pub mod error;
 pub mod faucet;
 pub mod query_types;
 pub mod streams;
+#[cfg(not(target_arch = "wasm32"))]
+mod subscription;
+mod transport;
+
+pub use builder::ClientBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use subscription::ReconnectPolicy;
+pub use transport::GraphQlTransport;
+pub use transport::ReqwestTransport;
 
 use error::Error;
 use query_types::ActiveValidatorsArgs;
@@ -81,20 +91,31 @@ use query_types::TransactionsFilter;
 use query_types::Validator;
 use streams::stream_paginated_query;
 
+use sui_types::framework::derive_dynamic_field_id;
+use sui_types::framework::derive_dynamic_object_field_id;
+use sui_types::framework::AuthenticatorState;
+use sui_types::framework::AuthenticatorStateInner;
 use sui_types::framework::Coin;
+use sui_types::framework::Field;
+use sui_types::framework::Wrapper;
+use sui_types::ActiveJwk;
 use sui_types::Address;
 use sui_types::CheckpointDigest;
 use sui_types::CheckpointSequenceNumber;
 use sui_types::CheckpointSummary;
 use sui_types::Event;
+use sui_types::EventRegistry;
 use sui_types::MovePackage;
+use sui_types::MoveType;
 use sui_types::Object;
+use sui_types::ObjectId;
 use sui_types::SignedTransaction;
 use sui_types::Transaction;
 use sui_types::TransactionDigest;
 use sui_types::TransactionEffects;
 use sui_types::TransactionKind;
 use sui_types::TypeTag;
+use sui_types::TypedEvent;
 use sui_types::UserSignature;
 
 use base64ct::Encoding;
@@ -104,9 +125,12 @@ use cynic::MutationBuilder;
 use cynic::Operation;
 use cynic::QueryBuilder;
 use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
 use reqwest::Url;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::error::Kind;
@@ -118,6 +142,7 @@ use query_types::TransactionBlockWithEffectsQuery;
 use query_types::TransactionBlocksWithEffectsQuery;
 
 const DEFAULT_ITEMS_PER_PAGE: i32 = 10;
+const MAX_CONCURRENT_BATCH_REQUESTS: usize = 4;
 const MAINNET_HOST: &str = "https://sui-mainnet.mystenlabs.com/graphql";
 const TESTNET_HOST: &str = "https://sui-testnet.mystenlabs.com/graphql";
 const DEVNET_HOST: &str = "https://sui-devnet.mystenlabs.com/graphql";
@@ -275,14 +300,24 @@ impl DynamicFieldOutput {
 }
 
 /// The GraphQL client for interacting with the Sui blockchain.
-/// By default, it uses the `reqwest` crate as the HTTP client.
+/// By default, it uses the `reqwest` crate as the HTTP client, see [`ClientBuilder`] for how to
+/// customize it.
 pub struct Client {
     /// The URL of the GraphQL server.
     rpc: Url,
-    /// The reqwest client.
-    inner: reqwest::Client,
+    /// The transport used to send requests to the server.
+    transport: std::sync::Arc<dyn GraphQlTransport>,
+    /// Headers sent with every request.
+    headers: reqwest::header::HeaderMap,
 
     service_config: std::sync::OnceLock<ServiceConfig>,
+
+    /// The URL of the GraphQL server's WebSocket endpoint, if it differs from `rpc`.
+    #[cfg(not(target_arch = "wasm32"))]
+    subscription: Option<Url>,
+    /// How subscriptions reconnect after losing their connection to the server.
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnect_policy: ReconnectPolicy,
 }
 
 impl Client {
@@ -292,14 +327,13 @@ impl Client {
 
     /// Create a new GraphQL client with the provided server address.
     pub fn new(server: &str) -> Result<Self> {
-        let rpc = reqwest::Url::parse(server)?;
+        Self::builder(server).build()
+    }
 
-        let client = Client {
-            rpc,
-            inner: reqwest::Client::builder().user_agent(USER_AGENT).build()?,
-            service_config: Default::default(),
-        };
-        Ok(client)
+    /// Create a builder for a client of the provided server address, in order to set custom
+    /// headers, authentication, timeouts or HTTP transport.
+    pub fn builder(server: &str) -> ClientBuilder {
+        ClientBuilder::new(server)
     }
 
     /// Create a new GraphQL client connected to the `mainnet` GraphQL server: {MAINNET_HOST}.
@@ -332,6 +366,7 @@ impl Client {
     }
 
     /// Return the URL for the GraphQL server.
+    #[cfg(test)]
     fn rpc_server(&self) -> &str {
         self.rpc.as_str()
     }
@@ -366,15 +401,9 @@ impl Client {
         T: serde::de::DeserializeOwned,
         V: serde::Serialize,
     {
-        let res = self
-            .inner
-            .post(self.rpc_server())
-            .json(&operation)
-            .send()
-            .await?
-            .json::<GraphQlResponse<T>>()
-            .await?;
-        Ok(res)
+        let body = serde_json::to_vec(operation).map_err(|e| Error::from_error(Kind::Other, e))?;
+        let response = self.transport.post(&self.rpc, &self.headers, body).await?;
+        serde_json::from_slice(&response).map_err(|e| Error::from_error(Kind::Deserialization, e))
     }
 
     // ===========================================================================
@@ -828,6 +857,89 @@ impl Client {
         Ok(result)
     }
 
+    /// Read the value of the dynamic field of `parent` whose name is `key`.
+    ///
+    /// The id of the field is derived locally from the name of the field and its Move type, as
+    /// given by [`MoveType`], and the field object is then fetched directly by its id and decoded
+    /// as a `0x2::dynamic_field::Field<K, V>`.
+    ///
+    /// The `parent` can also be the id of a [`Table`](sui_types::framework::Table) or
+    /// [`Bag`](sui_types::framework::Bag) in order to read one of its entries.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let client = sui_graphql_client::Client::new_devnet();
+    /// let value: Option<u64> = client
+    ///     .get_dynamic_field(parent, &2u64)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn get_dynamic_field<K, V>(&self, parent: Address, key: &K) -> Result<Option<V>>
+    where
+        K: MoveType + Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let field_id = derive_dynamic_field_id(parent.into(), &K::type_tag(), key)?;
+        let Some(field) = self
+            .dynamic_field_object::<K, V>(*field_id.as_address())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(field.value))
+    }
+
+    /// Read the child object stored in the dynamic object field of `parent` whose name is `key`.
+    ///
+    /// The id of the field is derived locally from the name of the field, the field is fetched to
+    /// determine the id of the child object, and then the child object itself is fetched.
+    ///
+    /// The `parent` can also be the id of an
+    /// [`ObjectTable`](sui_types::framework::ObjectTable) in order to read one of its entries.
+    pub async fn get_dynamic_object_field<K>(
+        &self,
+        parent: Address,
+        key: &K,
+    ) -> Result<Option<Object>>
+    where
+        K: MoveType + Serialize + DeserializeOwned,
+    {
+        let field_id = derive_dynamic_object_field_id(parent.into(), &K::type_tag(), key)?;
+        let Some(field) = self
+            .dynamic_field_object::<Wrapper<K>, ObjectId>(*field_id.as_address())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.object(*field.value.as_address(), None).await
+    }
+
+    /// Fetch and decode the `Field` object with the provided id.
+    async fn dynamic_field_object<K, V>(&self, field_id: Address) -> Result<Option<Field<K, V>>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let Some(object) = self.object(field_id, None).await? else {
+            return Ok(None);
+        };
+
+        let contents = object
+            .as_struct()
+            .filter(|s| s.object_type().is_dynamic_field().is_some())
+            .map(|s| s.contents())
+            .ok_or_else(|| {
+                Error::from_error(
+                    Kind::Deserialization,
+                    format!("object {field_id} is not a dynamic field"),
+                )
+            })?;
+
+        Field::from_contents(contents).map(Some).map_err(Into::into)
+    }
+
     /// Get a page of dynamic fields for the provided address. Note that this will also fetch
     /// dynamic fields on wrapped objects.
     ///
@@ -958,6 +1070,36 @@ impl Client {
             .and_then(|e| e.total_transactions))
     }
 
+    /// Return the set of JWKs currently active for zklogin, as recorded in the on-chain
+    /// [`AuthenticatorState`] object.
+    ///
+    /// Each [`ActiveJwk`] carries the epoch in which it was most recently observed by the
+    /// validators, which can be used to expire stale keys.
+    pub async fn active_jwks(&self) -> Result<Vec<ActiveJwk>> {
+        let state = self
+            .object(*AuthenticatorState::ID.as_address(), None)
+            .await?
+            .ok_or_else(|| Error::from_error(Kind::Other, "AuthenticatorState object not found"))?;
+        let state = AuthenticatorState::try_from_object(&state).ok_or_else(|| {
+            Error::from_error(Kind::Deserialization, "invalid AuthenticatorState object")
+        })?;
+
+        let inner = self
+            .object(*state.inner_id().as_address(), None)
+            .await?
+            .ok_or_else(|| {
+                Error::from_error(Kind::Other, "AuthenticatorStateInner object not found")
+            })?;
+        let inner = AuthenticatorStateInner::try_from_object(&inner).ok_or_else(|| {
+            Error::from_error(
+                Kind::Deserialization,
+                "invalid AuthenticatorStateInner object",
+            )
+        })?;
+
+        Ok(inner.active_jwks)
+    }
+
     /// Internal method for getting the epoch summary that is called in a few other APIs for
     /// convenience.
     async fn epoch_summary(
@@ -1040,6 +1182,43 @@ impl Client {
         )
     }
 
+    /// Return a stream of the events registered for `T` in `registry`, decoded as `T`.
+    ///
+    /// Events which aren't registered for `T` are skipped. If no filter is provided and `T` is
+    /// registered for a single event type, only events of that type are queried.
+    pub async fn events_typed<'a, T>(
+        &'a self,
+        registry: &'a EventRegistry,
+        filter: Option<EventFilter>,
+        streaming_direction: Direction,
+    ) -> impl Stream<Item = Result<TypedEvent<T>>> + 'a
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let filter = filter.or_else(|| {
+            let mut types = registry.types_for::<T>();
+            match (types.next(), types.next()) {
+                (Some(type_), None) => Some(EventFilter {
+                    emitting_module: None,
+                    event_type: Some(type_.to_string()),
+                    sender: None,
+                    transaction_digest: None,
+                }),
+                _ => None,
+            }
+        });
+
+        self.events_stream(filter, streaming_direction)
+            .await
+            .try_filter_map(move |(event, digest)| {
+                let event = registry
+                    .decode_typed::<T>(&event, digest, None)
+                    .transpose()
+                    .map_err(Error::from);
+                futures::future::ready(event)
+            })
+    }
+
     // ===========================================================================
     // Objects API
     // ===========================================================================
@@ -1144,6 +1323,50 @@ impl Client {
         )
     }
 
+    /// Return the latest version of every object in `object_ids`.
+    ///
+    /// The ids are split into chunks based on the service's limits, which are then queried
+    /// concurrently. The returned `Vec` is in the same order as `object_ids`, with `None` for
+    /// objects which could not be found.
+    pub async fn get_objects(&self, object_ids: &[Address]) -> Result<Vec<Option<Object>>> {
+        let config = self.service_config().await?;
+        let chunk_size = config
+            .max_multi_get_objects_keys
+            .min(config.max_page_size)
+            .max(1) as usize;
+
+        let chunks = object_ids.chunks(chunk_size).map(|chunk| async move {
+            let filter = ObjectFilter {
+                object_ids: Some(chunk.to_vec()),
+                ..Default::default()
+            };
+            let pagination_filter = PaginationFilter {
+                limit: Some(chunk.len() as i32),
+                ..Default::default()
+            };
+            let objects = self
+                .objects(Some(filter), pagination_filter)
+                .await?
+                .into_parts()
+                .1
+                .into_iter()
+                .map(|object| (*object.object_id().as_address(), object))
+                .collect::<HashMap<_, _>>();
+
+            Ok::<_, Error>(
+                chunk
+                    .iter()
+                    .map(|id| objects.get(id).cloned())
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        futures::stream::iter(chunks)
+            .buffered(MAX_CONCURRENT_BATCH_REQUESTS)
+            .try_concat()
+            .await
+    }
+
     /// Return the object's bcs content [`Vec<u8>`] based on the provided [`Address`].
     pub async fn object_bcs(&self, object_id: Address) -> Result<Option<Vec<u8>>> {
         let operation = ObjectQuery::build(ObjectQueryArgs {
@@ -1561,6 +1784,59 @@ impl Client {
         }
     }
 
+    /// Get every transaction in `digests`.
+    ///
+    /// The digests are split into chunks based on the service's limits, which are then queried
+    /// concurrently. The returned `Vec` is in the same order as `digests`, with `None` for
+    /// transactions which could not be found.
+    pub async fn get_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> Result<Vec<Option<SignedTransaction>>> {
+        let config = self.service_config().await?;
+        let chunk_size = config.max_transaction_ids.min(config.max_page_size).max(1) as usize;
+
+        let chunks = digests.chunks(chunk_size).map(|chunk| async move {
+            let digest_strings = chunk.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let filter = TransactionsFilter {
+                function: None,
+                kind: None,
+                after_checkpoint: None,
+                at_checkpoint: None,
+                before_checkpoint: None,
+                affected_address: None,
+                sent_address: None,
+                input_object: None,
+                changed_object: None,
+                transaction_ids: Some(digest_strings.iter().map(String::as_str).collect()),
+            };
+            let pagination_filter = PaginationFilter {
+                limit: Some(chunk.len() as i32),
+                ..Default::default()
+            };
+            let transactions = self
+                .transactions(Some(filter), pagination_filter)
+                .await?
+                .into_parts()
+                .1
+                .into_iter()
+                .map(|tx| (tx.transaction.digest(), tx))
+                .collect::<HashMap<_, _>>();
+
+            Ok::<_, Error>(
+                chunk
+                    .iter()
+                    .map(|digest| transactions.get(digest).cloned())
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        futures::stream::iter(chunks)
+            .buffered(MAX_CONCURRENT_BATCH_REQUESTS)
+            .try_concat()
+            .await
+    }
+
     /// Get a page of transactions based on the provided filters.
     pub async fn transactions(
         &self,
@@ -2137,6 +2413,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_objects() {
+        let client = test_client();
+        let ids = ["0x5", "0x12345", "0x6"].map(|id| id.parse().unwrap());
+        let objects = client.get_objects(&ids).await;
+        assert!(
+            objects.is_ok(),
+            "Get objects query failed for {} network. Error: {}",
+            client.rpc_server(),
+            objects.unwrap_err()
+        );
+
+        let objects = objects.unwrap();
+        assert_eq!(objects.len(), ids.len());
+        assert!(objects[1].is_none());
+        for (id, object) in ids.iter().zip(&objects) {
+            if let Some(object) = object {
+                assert_eq!(object.object_id().as_address(), id);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_object_bcs_query() {
         let client = test_client();
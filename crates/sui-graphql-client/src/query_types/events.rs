@@ -31,6 +31,26 @@ pub struct EventsQueryArgs<'a> {
     pub last: Option<i32>,
 }
 
+// ===========================================================================
+// Events Subscription
+// ===========================================================================
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema = "rpc",
+    graphql_type = "Subscription",
+    variables = "EventsSubscriptionArgs"
+)]
+pub struct EventsSubscription {
+    #[arguments(filter: $filter)]
+    pub events: Event,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct EventsSubscriptionArgs {
+    pub filter: Option<EventFilter>,
+}
+
 // ===========================================================================
 // Events Types
 // ===========================================================================
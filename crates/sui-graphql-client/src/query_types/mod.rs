@@ -60,6 +60,8 @@ pub use events::EventConnection;
 pub use events::EventFilter;
 pub use events::EventsQuery;
 pub use events::EventsQueryArgs;
+pub use events::EventsSubscription;
+pub use events::EventsSubscriptionArgs;
 pub use execute_tx::ExecuteTransactionArgs;
 pub use execute_tx::ExecuteTransactionQuery;
 pub use execute_tx::ExecutionResult;
@@ -113,6 +115,8 @@ pub use transaction::TransactionBlocksQuery;
 pub use transaction::TransactionBlocksQueryArgs;
 pub use transaction::TransactionBlocksWithEffectsQuery;
 pub use transaction::TransactionsFilter;
+pub use transaction::TransactionsSubscription;
+pub use transaction::TransactionsSubscriptionArgs;
 
 use sui_types::Address;
 
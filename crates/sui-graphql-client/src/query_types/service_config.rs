@@ -63,6 +63,10 @@ pub struct ServiceConfig {
     pub mutation_timeout_ms: i32,
     /// Maximum time in milliseconds that will be spent to serve one query request.
     pub request_timeout_ms: i32,
+    /// Maximum number of transaction ids that can be passed to a `TransactionBlockFilter`.
+    pub max_transaction_ids: i32,
+    /// Maximum number of keys that can be passed to a `multiGetObjects` query.
+    pub max_multi_get_objects_keys: i32,
 }
 
 #[derive(cynic::Enum, Clone, Copy, Debug)]
@@ -102,6 +102,26 @@ pub struct TransactionBlocksQueryArgs<'a> {
     pub filter: Option<TransactionsFilter<'a>>,
 }
 
+// ===========================================================================
+// Transactions Subscription
+// ===========================================================================
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema = "rpc",
+    graphql_type = "Subscription",
+    variables = "TransactionsSubscriptionArgs"
+)]
+pub struct TransactionsSubscription {
+    #[arguments(filter: $filter)]
+    pub transactions: TransactionBlock,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct TransactionsSubscriptionArgs<'a> {
+    pub filter: Option<TransactionsFilter<'a>>,
+}
+
 // ===========================================================================
 // Transaction Block(s) Types
 // ===========================================================================
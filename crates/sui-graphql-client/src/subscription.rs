@@ -0,0 +1,584 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! GraphQL subscriptions over WebSocket, using the [graphql-transport-ws] protocol.
+//!
+//! [graphql-transport-ws]: https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md
+
+use std::time::Duration;
+
+use base64ct::Encoding;
+use cynic::GraphQlError;
+use cynic::SubscriptionBuilder;
+use futures::stream::BoxStream;
+use futures::SinkExt;
+use futures::Stream;
+use futures::StreamExt;
+use reqwest::header::HeaderMap;
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use sui_types::Event;
+use sui_types::SignedTransaction;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::MaybeTlsStream;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::error::Error;
+use crate::error::Kind;
+use crate::error::Result;
+use crate::query_types::EventFilter;
+use crate::query_types::EventsSubscription;
+use crate::query_types::EventsSubscriptionArgs;
+use crate::query_types::TransactionsFilter;
+use crate::query_types::TransactionsSubscription;
+use crate::query_types::TransactionsSubscriptionArgs;
+use crate::Client;
+
+/// The WebSocket sub-protocol spoken by GraphQL servers supporting subscriptions.
+const PROTOCOL: &str = "graphql-transport-ws";
+
+/// Identifier of the single subscription made over each connection.
+const SUBSCRIPTION_ID: &str = "1";
+
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How a subscription reconnects after its connection to the server is lost.
+///
+/// Reconnection attempts are delayed using exponential backoff, starting at `initial_delay` and
+/// doubling after every consecutive failure up to `max_delay`. A connection only counts as
+/// healthy, resetting the failure count, once the server has delivered a result over it.
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// The maximum number of consecutive failed connections before the subscription gives up and
+    /// yields the last error, or `None` to retry indefinitely.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    fn delay(&self, failures: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(1 << failures.saturating_sub(1).min(16))
+            .min(self.max_delay)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage<'a> {
+    ConnectionInit {
+        payload: serde_json::Value,
+    },
+    Subscribe {
+        id: &'a str,
+        payload: &'a serde_json::Value,
+    },
+    Pong {},
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    ConnectionAck {},
+    Ping {},
+    Pong {},
+    Next {
+        payload: cynic::GraphQlResponse<serde_json::Value>,
+    },
+    Error {
+        payload: Vec<GraphQlError>,
+    },
+    Complete {},
+}
+
+impl Client {
+    /// Set the address of the WebSocket endpoint used for subscriptions.
+    ///
+    /// By default this is the address of the GraphQL server with its scheme replaced by `ws` or
+    /// `wss`.
+    pub fn set_subscription_server(&mut self, server: &str) -> Result<()> {
+        let url = Url::parse(server)?;
+        self.subscription = Some(websocket_url(&url)?);
+        Ok(())
+    }
+
+    /// Set how subscriptions reconnect after losing their connection to the server.
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
+
+    /// Return the URL of the WebSocket endpoint used for subscriptions.
+    fn subscription_server(&self) -> Result<Url> {
+        match &self.subscription {
+            Some(url) => Ok(url.clone()),
+            None => websocket_url(&self.rpc),
+        }
+    }
+
+    /// Subscribe to the events emitted by newly executed transactions, based on the (optional)
+    /// event filter.
+    ///
+    /// Subscriptions are made over a WebSocket connection using the graphql-transport-ws protocol,
+    /// which is established when the stream is first polled and re-established according to the
+    /// client's [`ReconnectPolicy`] if it is lost. Events emitted while reconnecting are missed.
+    pub fn subscribe_events(
+        &self,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>> + Send + Unpin + 'static> {
+        let operation = EventsSubscription::build(EventsSubscriptionArgs { filter });
+        let payload =
+            serde_json::to_value(operation).map_err(|e| Error::from_error(Kind::Other, e))?;
+
+        let stream = subscribe(
+            self.subscription_server()?,
+            self.headers.clone(),
+            payload,
+            self.reconnect_policy.clone(),
+        );
+        Ok(stream.map(|data: Result<EventsSubscription>| {
+            let bcs = base64ct::Base64::decode_vec(&data?.events.bcs.0)?;
+            Ok(bcs::from_bytes::<Event>(&bcs)?)
+        }))
+    }
+
+    /// Subscribe to newly executed transactions, based on the (optional) transaction filter.
+    ///
+    /// See [`Client::subscribe_events`] for how the subscription is managed.
+    pub fn subscribe_transactions(
+        &self,
+        filter: Option<TransactionsFilter<'_>>,
+    ) -> Result<impl Stream<Item = Result<SignedTransaction>> + Send + Unpin + 'static> {
+        let operation = TransactionsSubscription::build(TransactionsSubscriptionArgs { filter });
+        let payload =
+            serde_json::to_value(operation).map_err(|e| Error::from_error(Kind::Other, e))?;
+
+        let stream = subscribe(
+            self.subscription_server()?,
+            self.headers.clone(),
+            payload,
+            self.reconnect_policy.clone(),
+        );
+        Ok(stream.map(|data: Result<TransactionsSubscription>| data?.transactions.try_into()))
+    }
+}
+
+/// Convert a GraphQL server URL into the URL of its WebSocket endpoint.
+fn websocket_url(url: &Url) -> Result<Url> {
+    let mut url = url.clone();
+    let scheme = match url.scheme() {
+        "http" | "ws" => "ws",
+        "https" | "wss" => "wss",
+        scheme => {
+            return Err(Error::from_error(
+                Kind::Parse,
+                format!("unsupported URL scheme for subscriptions: {scheme}"),
+            ))
+        }
+    };
+    url.set_scheme(scheme)
+        .map_err(|_| Error::from_error(Kind::Parse, "invalid subscription URL"))?;
+    Ok(url)
+}
+
+/// Run a subscription, yielding each result decoded as `T`.
+///
+/// The stream ends when the server completes the subscription or rejects it with an error. If
+/// the connection is lost, the subscription is re-established according to `policy`.
+fn subscribe<T>(
+    url: Url,
+    headers: HeaderMap,
+    payload: serde_json::Value,
+    policy: ReconnectPolicy,
+) -> BoxStream<'static, Result<T>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let stream = async_stream::stream! {
+        let mut failures = 0;
+
+        loop {
+            let error = match connect(&url, &headers, &payload).await {
+                Ok(mut connection) => {
+                    loop {
+                        let message = match connection.next().await {
+                            Some(Ok(Message::Text(text))) => text,
+                            Some(Ok(Message::Close(_))) | None => {
+                                let message = "subscription connection closed";
+                                break Error::from_error(Kind::Other, message);
+                            }
+                            // Control frames are handled by the WebSocket implementation
+                            Some(Ok(_)) => continue,
+                            Some(Err(e)) => break Error::from_error(Kind::Other, e),
+                        };
+
+                        match serde_json::from_str::<ServerMessage>(&message) {
+                            Ok(ServerMessage::Next { payload }) => {
+                                // Only a connection that delivers results is healthy, otherwise
+                                // a server that accepts and immediately drops connections would
+                                // be retried forever.
+                                failures = 0;
+                                yield decode(payload);
+                            }
+                            Ok(ServerMessage::Error { payload }) => {
+                                yield Err(Error::graphql_error(payload));
+                                return;
+                            }
+                            Ok(ServerMessage::Complete {}) => return,
+                            Ok(ServerMessage::Ping {}) => {
+                                let pong = send(&mut connection, &ClientMessage::Pong {}).await;
+                                if let Err(e) = pong {
+                                    break e;
+                                }
+                            }
+                            Ok(ServerMessage::ConnectionAck {} | ServerMessage::Pong {}) => {}
+                            Err(e) => break Error::from_error(Kind::Deserialization, e),
+                        }
+                    }
+                }
+                Err(e) => e,
+            };
+
+            failures += 1;
+            if policy.max_attempts.is_some_and(|max| failures > max) {
+                yield Err(error);
+                return;
+            }
+
+            let delay = policy.delay(failures);
+            tracing::warn!("subscription interrupted, reconnecting in {delay:?}: {error}");
+            tokio::time::sleep(delay).await;
+        }
+    };
+
+    stream.boxed()
+}
+
+/// Open a connection, complete the protocol handshake and start the subscription.
+async fn connect(
+    url: &Url,
+    headers: &HeaderMap,
+    payload: &serde_json::Value,
+) -> Result<Connection> {
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| Error::from_error(Kind::Parse, e))?;
+    request.headers_mut().extend(headers.clone());
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", HeaderValue::from_static(PROTOCOL));
+
+    let (mut connection, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| Error::from_error(Kind::Other, e))?;
+
+    send(
+        &mut connection,
+        &ClientMessage::ConnectionInit {
+            payload: serde_json::Value::Object(Default::default()),
+        },
+    )
+    .await?;
+
+    loop {
+        let message = match connection.next().await {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(Error::from_error(
+                    Kind::Other,
+                    "connection closed before it was acknowledged",
+                ))
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(Error::from_error(Kind::Other, e)),
+        };
+
+        match serde_json::from_str::<ServerMessage>(&message)
+            .map_err(|e| Error::from_error(Kind::Deserialization, e))?
+        {
+            ServerMessage::ConnectionAck {} => break,
+            ServerMessage::Ping {} => send(&mut connection, &ClientMessage::Pong {}).await?,
+            _ => {
+                return Err(Error::from_error(
+                    Kind::Other,
+                    "unexpected message before connection was acknowledged",
+                ))
+            }
+        }
+    }
+
+    send(
+        &mut connection,
+        &ClientMessage::Subscribe {
+            id: SUBSCRIPTION_ID,
+            payload,
+        },
+    )
+    .await?;
+
+    Ok(connection)
+}
+
+async fn send(connection: &mut Connection, message: &ClientMessage<'_>) -> Result<()> {
+    let message = serde_json::to_string(message).map_err(|e| Error::from_error(Kind::Other, e))?;
+    connection
+        .send(Message::text(message))
+        .await
+        .map_err(|e| Error::from_error(Kind::Other, e))
+}
+
+fn decode<T: DeserializeOwned>(response: cynic::GraphQlResponse<serde_json::Value>) -> Result<T> {
+    if let Some(errors) = response.errors {
+        return Err(Error::graphql_error(errors));
+    }
+
+    let data = response.data.ok_or_else(Error::empty_response_error)?;
+    serde_json::from_value(data).map_err(|e| Error::from_error(Kind::Deserialization, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sui_types::Address;
+    use sui_types::Identifier;
+    use sui_types::ObjectId;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+    use tokio_tungstenite::tungstenite::handshake::server::Request;
+    use tokio_tungstenite::tungstenite::handshake::server::Response;
+
+    fn event(amount: u64) -> Event {
+        Event {
+            package_id: ObjectId::from(Address::TWO),
+            module: Identifier::new("coin").unwrap(),
+            sender: Address::ZERO,
+            type_: "0x2::coin::Minted".parse().unwrap(),
+            contents: bcs::to_bytes(&amount).unwrap(),
+        }
+    }
+
+    fn next(event: &Event) -> serde_json::Value {
+        let bcs = base64ct::Base64::encode_string(&bcs::to_bytes(event).unwrap());
+        json!({
+            "id": SUBSCRIPTION_ID,
+            "type": "next",
+            "payload": { "data": { "events": { "bcs": bcs, "transactionBlock": null } } },
+        })
+    }
+
+    async fn receive(connection: &mut WebSocketStream<TcpStream>) -> serde_json::Value {
+        loop {
+            match connection.next().await.unwrap().unwrap() {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    /// Check that the client requested the graphql-transport-ws protocol and accept it.
+    #[allow(clippy::result_large_err)]
+    fn accept_protocol(
+        request: &Request,
+        mut response: Response,
+    ) -> std::result::Result<Response, ErrorResponse> {
+        let protocol = request.headers().get("Sec-WebSocket-Protocol").unwrap();
+        assert_eq!(protocol, PROTOCOL);
+        response
+            .headers_mut()
+            .insert("Sec-WebSocket-Protocol", protocol.clone());
+        Ok(response)
+    }
+
+    /// Serve one WebSocket connection per script, sending each of the script's messages after
+    /// the subscription is started and then dropping the connection. Returns the address of the
+    /// server and a handle resolving to the subscribe messages it received.
+    async fn mock_server(
+        scripts: Vec<Vec<serde_json::Value>>,
+    ) -> (String, tokio::task::JoinHandle<Vec<serde_json::Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}/graphql", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let mut subscriptions = Vec::new();
+
+            for script in scripts {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut connection = tokio_tungstenite::accept_hdr_async(stream, accept_protocol)
+                    .await
+                    .unwrap();
+
+                assert_eq!(receive(&mut connection).await["type"], "connection_init");
+                let ack = json!({ "type": "connection_ack" }).to_string();
+                connection.send(Message::text(ack)).await.unwrap();
+
+                let subscribe = receive(&mut connection).await;
+                assert_eq!(subscribe["type"], "subscribe");
+                subscriptions.push(subscribe);
+
+                for message in script {
+                    let is_ping = message["type"] == "ping";
+                    connection
+                        .send(Message::text(message.to_string()))
+                        .await
+                        .unwrap();
+                    if is_ping {
+                        assert_eq!(receive(&mut connection).await["type"], "pong");
+                    }
+                }
+            }
+
+            subscriptions
+        });
+
+        (address, handle)
+    }
+
+    fn client(address: &str) -> Client {
+        let mut client = Client::new(address).unwrap();
+        client.set_reconnect_policy(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+            max_attempts: Some(3),
+        });
+        client
+    }
+
+    #[test]
+    fn websocket_urls() {
+        let url = |s: &str| websocket_url(&s.parse().unwrap()).map(|url| url.to_string());
+        assert_eq!(
+            url("http://localhost:9125/graphql").unwrap(),
+            "ws://localhost:9125/graphql"
+        );
+        assert_eq!(
+            url("https://example.com/graphql").unwrap(),
+            "wss://example.com/graphql"
+        );
+        assert!(url("ftp://example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn subscribe_events() {
+        let (address, server) = mock_server(vec![vec![
+            next(&event(1)),
+            json!({ "type": "ping" }),
+            next(&event(2)),
+            json!({ "id": SUBSCRIPTION_ID, "type": "complete" }),
+        ]])
+        .await;
+
+        let filter = crate::query_types::EventFilter {
+            emitting_module: None,
+            event_type: Some("0x2::coin::Minted".to_owned()),
+            sender: None,
+            transaction_digest: None,
+        };
+        let events = client(&address)
+            .subscribe_events(Some(filter))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, [event(1), event(2)]);
+
+        let subscriptions = server.await.unwrap();
+        let payload = &subscriptions[0]["payload"];
+        assert!(payload["query"]
+            .as_str()
+            .unwrap()
+            .starts_with("subscription EventsSubscription"));
+        assert_eq!(
+            payload["variables"]["filter"]["eventType"],
+            "0x2::coin::Minted"
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_connection_is_lost() {
+        let (address, server) = mock_server(vec![
+            vec![next(&event(1))],
+            vec![
+                next(&event(2)),
+                json!({ "id": SUBSCRIPTION_ID, "type": "complete" }),
+            ],
+        ])
+        .await;
+
+        let events = client(&address)
+            .subscribe_events(None)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, [event(1), event(2)]);
+        assert_eq!(server.await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn subscription_errors_end_the_stream() {
+        let (address, _server) = mock_server(vec![vec![json!({
+            "id": SUBSCRIPTION_ID,
+            "type": "error",
+            "payload": [{ "message": "unknown field" }],
+        })]])
+        .await;
+
+        let results = client(&address)
+            .subscribe_events(None)
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results.len(), 1);
+        let error = results[0].as_ref().unwrap_err();
+        assert_eq!(error.graphql_errors().unwrap()[0].message, "unknown field");
+    }
+
+    #[tokio::test]
+    async fn gives_up_on_connections_that_deliver_nothing() {
+        // Every connection is accepted and then dropped without delivering a result, so each one
+        // counts as a failure: the initial connection plus `max_attempts` reconnections.
+        let (address, server) = mock_server(vec![vec![]; 4]).await;
+
+        let results = client(&address)
+            .subscribe_events(None)
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+        assert_eq!(server.await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        // Nothing is listening on this port once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}/graphql", listener.local_addr().unwrap());
+        drop(listener);
+
+        let results = client(&address)
+            .subscribe_events(None)
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::Url;
+
+use crate::error::Result;
+
+/// The HTTP transport used by a [`Client`](crate::Client) to send GraphQL requests.
+///
+/// The default transport, [`ReqwestTransport`], is backed by `reqwest`, which uses the browser's
+/// `fetch` API when targeting wasm. A custom transport can be provided with
+/// [`ClientBuilder::with_transport`](crate::ClientBuilder::with_transport), e.g. to add retries,
+/// record metrics, or to run on a platform `reqwest` doesn't support.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait GraphQlTransport: Send + Sync {
+    /// POST `body`, a JSON encoded GraphQL request, to `url` along with `headers` and return the
+    /// body of the response.
+    async fn post(&self, url: &Url, headers: &HeaderMap, body: Vec<u8>) -> Result<Vec<u8>>;
+}
+
+/// A [`GraphQlTransport`] backed by a [`reqwest::Client`].
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    timeout: Option<Duration>,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            timeout: None,
+        }
+    }
+
+    /// Set the timeout applied to every request, from when it is sent until the response body
+    /// has been received.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl GraphQlTransport for ReqwestTransport {
+    async fn post(&self, url: &Url, headers: &HeaderMap, body: Vec<u8>) -> Result<Vec<u8>> {
+        let mut request = self
+            .client
+            .post(url.clone())
+            .headers(headers.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+
+        let response = request.send().await?;
+        let body = response.bytes().await?;
+        Ok(body.to_vec())
+    }
+}
@@ -0,0 +1,372 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use futures::TryStreamExt;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+use tokio::sync::mpsc;
+
+use crate::CheckpointSource;
+use crate::Error;
+use crate::ProgressStore;
+use crate::Worker;
+
+/// Default number of checkpoints buffered for each worker before the executor stops reading from
+/// its source to wait for the worker to catch up.
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 100;
+
+struct RegisteredWorker {
+    worker: Arc<dyn Worker>,
+    concurrency: usize,
+}
+
+/// Drives a set of [`Worker`]s with the checkpoints read from a [`CheckpointSource`].
+///
+/// Every worker runs independently of the others: each resumes from its own progress, processes
+/// up to its configured number of checkpoints concurrently, and has its progress saved to the
+/// [`ProgressStore`] as soon as a contiguous run of checkpoints has been processed. The source is
+/// only read once, starting from the checkpoint needed by the worker which is furthest behind.
+pub struct IngestionExecutor<P> {
+    workers: Vec<RegisteredWorker>,
+    progress: P,
+    channel_capacity: usize,
+}
+
+impl<P: ProgressStore> IngestionExecutor<P> {
+    pub fn new(progress: P) -> Self {
+        Self {
+            workers: Vec::new(),
+            progress,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    /// Register a worker which will process up to `concurrency` checkpoints at the same time.
+    pub fn with_worker<W: Worker + 'static>(mut self, worker: W, concurrency: usize) -> Self {
+        self.workers.push(RegisteredWorker {
+            worker: Arc::new(worker),
+            concurrency: concurrency.max(1),
+        });
+        self
+    }
+
+    /// Set the number of checkpoints buffered for each worker.
+    pub fn with_channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn progress_store(&self) -> &P {
+        &self.progress
+    }
+
+    /// Run every worker from where it last left off until checkpoint `end` (exclusive), or
+    /// indefinitely if `end` is `None`.
+    ///
+    /// Workers which have never made progress start from `initial_checkpoint`. Progress is saved
+    /// after every checkpoint, so it is safe to stop ingestion by dropping the returned future.
+    pub async fn run<S: CheckpointSource + ?Sized>(
+        &self,
+        source: &S,
+        initial_checkpoint: CheckpointSequenceNumber,
+        end: Option<CheckpointSequenceNumber>,
+    ) -> Result<(), Error> {
+        self.check_worker_names()?;
+
+        let mut starts = Vec::with_capacity(self.workers.len());
+        for registered in &self.workers {
+            let start = self
+                .progress
+                .load(registered.worker.name())
+                .await
+                .map_err(Error::Progress)?
+                .unwrap_or(initial_checkpoint);
+            starts.push(start);
+        }
+
+        self.process(source, starts, end, true).await
+    }
+
+    /// Process the checkpoints in `range` with every worker, regardless of their progress.
+    ///
+    /// This is intended for backfilling or repairing data and neither reads nor updates the
+    /// progress of the workers.
+    pub async fn reprocess<S: CheckpointSource + ?Sized>(
+        &self,
+        source: &S,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<(), Error> {
+        self.check_worker_names()?;
+
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        let starts = vec![range.start; self.workers.len()];
+        self.process(source, starts, Some(range.end), false).await
+    }
+
+    fn check_worker_names(&self) -> Result<(), Error> {
+        let mut names = HashSet::new();
+        for registered in &self.workers {
+            let name = registered.worker.name();
+            if !names.insert(name) {
+                return Err(Error::DuplicateWorker(name.to_owned()));
+            }
+        }
+        Ok(())
+    }
+
+    async fn process<S: CheckpointSource + ?Sized>(
+        &self,
+        source: &S,
+        starts: Vec<CheckpointSequenceNumber>,
+        end: Option<CheckpointSequenceNumber>,
+        save_progress: bool,
+    ) -> Result<(), Error> {
+        let Some(&start) = starts.iter().min() else {
+            return Ok(());
+        };
+        if end.is_some_and(|end| start >= end) {
+            return Ok(());
+        }
+
+        let mut senders = Vec::with_capacity(self.workers.len());
+        let mut workers = Vec::with_capacity(self.workers.len());
+        for (registered, worker_start) in self.workers.iter().zip(starts) {
+            let (sender, receiver) = mpsc::channel(self.channel_capacity);
+            senders.push((sender, worker_start));
+            workers.push(self.drive_worker(registered, receiver, save_progress));
+        }
+
+        let read = async move {
+            let mut stream = source.stream(start);
+            let mut expected = start;
+
+            while end.is_none_or(|end| expected < end) {
+                let Some(checkpoint) = stream.try_next().await? else {
+                    break;
+                };
+                super::source::check_sequence_number(expected, &checkpoint)?;
+
+                let checkpoint = Arc::new(checkpoint);
+                for (sender, worker_start) in &senders {
+                    if expected >= *worker_start {
+                        // The worker has failed if its receiver was dropped, in which case its
+                        // error will be returned by `try_join` below.
+                        let _ = sender.send(checkpoint.clone()).await;
+                    }
+                }
+                expected += 1;
+            }
+
+            // Dropping the senders lets the workers finish once they've drained their channels
+            drop(senders);
+            Ok(())
+        };
+
+        futures::future::try_join(read, futures::future::try_join_all(workers))
+            .await
+            .map(|_| ())
+    }
+
+    async fn drive_worker(
+        &self,
+        registered: &RegisteredWorker,
+        receiver: mpsc::Receiver<Arc<CheckpointData>>,
+        save_progress: bool,
+    ) -> Result<(), Error> {
+        let worker = &registered.worker;
+        let name = worker.name();
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver
+                .recv()
+                .await
+                .map(|checkpoint| (checkpoint, receiver))
+        })
+        .map(|checkpoint| async move {
+            let sequence_number = checkpoint.checkpoint_summary.checkpoint.sequence_number;
+            worker
+                .process_checkpoint(&checkpoint)
+                .await
+                .map(|()| sequence_number)
+                .map_err(|source| Error::Worker {
+                    worker: name.to_owned(),
+                    checkpoint: sequence_number,
+                    source,
+                })
+        })
+        // `buffered` yields results in order, so progress only advances over checkpoints whose
+        // predecessors have all been processed
+        .buffered(registered.concurrency)
+        .try_for_each(|sequence_number| async move {
+            tracing::trace!(worker = name, sequence_number, "processed checkpoint");
+            if save_progress {
+                self.progress
+                    .save(name, sequence_number + 1)
+                    .await
+                    .map_err(Error::Progress)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures::stream::BoxStream;
+
+    use super::*;
+    use crate::test_util::checkpoint;
+    use crate::BoxError;
+    use crate::MemoryProgressStore;
+
+    /// A source which serves checkpoints `0..count` from memory.
+    struct MemorySource {
+        count: u64,
+    }
+
+    impl CheckpointSource for MemorySource {
+        fn stream(
+            &self,
+            start: CheckpointSequenceNumber,
+        ) -> BoxStream<'static, Result<CheckpointData, Error>> {
+            futures::stream::iter((start..self.count).map(|i| Ok(checkpoint(i)))).boxed()
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingWorker {
+        name: &'static str,
+        seen: Arc<Mutex<Vec<u64>>>,
+        fail_at: Option<u64>,
+    }
+
+    #[async_trait::async_trait]
+    impl Worker for RecordingWorker {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<(), BoxError> {
+            let sequence_number = checkpoint.checkpoint_summary.checkpoint.sequence_number;
+            if self.fail_at == Some(sequence_number) {
+                return Err("boom".into());
+            }
+            // Finish later checkpoints first to exercise out of order completion
+            tokio::time::sleep(std::time::Duration::from_millis(10 - sequence_number % 10)).await;
+            self.seen.lock().unwrap().push(sequence_number);
+            Ok(())
+        }
+    }
+
+    fn sorted(seen: &Mutex<Vec<u64>>) -> Vec<u64> {
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        seen
+    }
+
+    #[tokio::test]
+    async fn workers_resume_from_their_own_progress() {
+        let progress = MemoryProgressStore::new();
+        progress.save("b", 5).await.unwrap();
+
+        let a = RecordingWorker {
+            name: "a",
+            ..Default::default()
+        };
+        let b = RecordingWorker {
+            name: "b",
+            ..Default::default()
+        };
+
+        let executor = IngestionExecutor::new(progress)
+            .with_worker(a.clone(), 4)
+            .with_worker(b.clone(), 1)
+            .with_channel_capacity(2);
+        executor
+            .run(&MemorySource { count: 20 }, 0, Some(8))
+            .await
+            .unwrap();
+
+        assert_eq!(sorted(&a.seen), (0..8).collect::<Vec<_>>());
+        assert_eq!(*b.seen.lock().unwrap(), [5, 6, 7]);
+        assert_eq!(executor.progress_store().get("a"), Some(8));
+        assert_eq!(executor.progress_store().get("b"), Some(8));
+
+        // Running again picks up where the workers left off
+        executor
+            .run(&MemorySource { count: 10 }, 0, None)
+            .await
+            .unwrap();
+        assert_eq!(sorted(&b.seen), [5, 6, 7, 8, 9]);
+        assert_eq!(executor.progress_store().get("a"), Some(10));
+    }
+
+    #[tokio::test]
+    async fn worker_failure_stops_progress() {
+        let worker = RecordingWorker {
+            name: "failing",
+            fail_at: Some(3),
+            ..Default::default()
+        };
+
+        let executor = IngestionExecutor::new(MemoryProgressStore::new()).with_worker(worker, 2);
+        let error = executor
+            .run(&MemorySource { count: 10 }, 0, None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::Worker { checkpoint: 3, .. }));
+        assert_eq!(executor.progress_store().get("failing"), Some(3));
+    }
+
+    #[tokio::test]
+    async fn reprocess_leaves_progress_untouched() {
+        let progress = MemoryProgressStore::new();
+        progress.save("a", 100).await.unwrap();
+
+        let worker = RecordingWorker {
+            name: "a",
+            ..Default::default()
+        };
+        let executor = IngestionExecutor::new(progress).with_worker(worker.clone(), 3);
+        executor
+            .reprocess(&MemorySource { count: 20 }, 2..6)
+            .await
+            .unwrap();
+
+        assert_eq!(sorted(&worker.seen), [2, 3, 4, 5]);
+        assert_eq!(executor.progress_store().get("a"), Some(100));
+    }
+
+    #[tokio::test]
+    async fn duplicate_worker_names() {
+        let executor = IngestionExecutor::new(MemoryProgressStore::new())
+            .with_worker(
+                RecordingWorker {
+                    name: "a",
+                    ..Default::default()
+                },
+                1,
+            )
+            .with_worker(
+                RecordingWorker {
+                    name: "a",
+                    ..Default::default()
+                },
+                1,
+            );
+
+        assert!(matches!(
+            executor.run(&MemorySource { count: 1 }, 0, None).await,
+            Err(Error::DuplicateWorker(_))
+        ));
+    }
+}
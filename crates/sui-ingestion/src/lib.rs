@@ -0,0 +1,212 @@
+//! A framework for building indexers which process every checkpoint of the Sui blockchain.
+//!
+//! Checkpoints are read, in order, from a [`CheckpointSource`] and fanned out to any number of
+//! user defined [`Worker`]s by an [`IngestionExecutor`]. Each worker processes checkpoints with
+//! bounded concurrency and has its progress, the next checkpoint it needs to process, persisted
+//! in a [`ProgressStore`] after every checkpoint so that ingestion resumes where it left off
+//! after a restart.
+//!
+//! The following sources are provided:
+//! - [`LocalDirectorySource`]: a local directory of BCS encoded `<sequence_number>.chk` files.
+//! - [`HttpBucketSource`]: an HTTP checkpoint bucket laid out in the same way.
+//! - [`GrpcSource`]: a gRPC checkpoint subscription to a fullnode.
+//!
+//! A [`LiveObjectSet`] can be used to reconstruct the set of live objects matching an
+//! [`ObjectFilter`] by applying checkpoints to it in order.
+//!
+//! ```no_run
+//! use sui_ingestion::BoxError;
+//! use sui_ingestion::FileProgressStore;
+//! use sui_ingestion::HttpBucketSource;
+//! use sui_ingestion::IngestionExecutor;
+//! use sui_ingestion::Worker;
+//! use sui_sdk_types::CheckpointData;
+//!
+//! struct TransactionCounter;
+//!
+//! #[async_trait::async_trait]
+//! impl Worker for TransactionCounter {
+//!     fn name(&self) -> &str {
+//!         "transaction_counter"
+//!     }
+//!
+//!     async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<(), BoxError> {
+//!         println!(
+//!             "checkpoint {} has {} transactions",
+//!             checkpoint.checkpoint_summary.checkpoint.sequence_number,
+//!             checkpoint.transactions.len()
+//!         );
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn run() -> Result<(), sui_ingestion::Error> {
+//! let source = HttpBucketSource::new("https://checkpoints.example.com".parse().unwrap());
+//! let progress = FileProgressStore::open("progress.json").await?;
+//!
+//! IngestionExecutor::new(progress)
+//!     .with_worker(TransactionCounter, 10)
+//!     .run(&source, 0, None)
+//!     .await
+//! # }
+//! ```
+
+mod executor;
+mod live_objects;
+mod progress;
+mod source;
+mod worker;
+
+pub use executor::IngestionExecutor;
+pub use executor::DEFAULT_CHANNEL_CAPACITY;
+pub use live_objects::LiveObjectSet;
+pub use live_objects::ObjectFilter;
+pub use progress::FileProgressStore;
+pub use progress::MemoryProgressStore;
+pub use progress::ProgressStore;
+pub use source::Backoff;
+pub use source::CheckpointSource;
+pub use source::GrpcSource;
+pub use source::HttpBucketSource;
+pub use source::LocalDirectorySource;
+pub use worker::Worker;
+
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Leading byte of a `.chk` file indicating that its contents are BCS encoded.
+const BLOB_ENCODING_BCS: u8 = 1;
+
+/// Error returned by the ingestion framework.
+#[derive(Debug)]
+pub enum Error {
+    /// A checkpoint could not be read from a [`CheckpointSource`].
+    Source(BoxError),
+
+    /// A [`Worker`] failed to process a checkpoint.
+    Worker {
+        worker: String,
+        checkpoint: CheckpointSequenceNumber,
+        source: BoxError,
+    },
+
+    /// Progress could not be loaded from or saved to a [`ProgressStore`].
+    Progress(BoxError),
+
+    /// More than one worker was registered with the same name.
+    DuplicateWorker(String),
+
+    /// A checkpoint was applied to a [`LiveObjectSet`] out of order.
+    OutOfOrderCheckpoint {
+        expected: CheckpointSequenceNumber,
+        actual: CheckpointSequenceNumber,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "failed to read checkpoint: {e}"),
+            Self::Worker {
+                worker,
+                checkpoint,
+                source,
+            } => write!(
+                f,
+                "worker {worker} failed to process checkpoint {checkpoint}: {source}"
+            ),
+            Self::Progress(e) => write!(f, "failed to access progress store: {e}"),
+            Self::DuplicateWorker(name) => write!(f, "duplicate worker {name}"),
+            Self::OutOfOrderCheckpoint { expected, actual } => {
+                write!(f, "expected checkpoint {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Source(e) | Self::Worker { source: e, .. } | Self::Progress(e) => {
+                Some(e.as_ref())
+            }
+            Self::DuplicateWorker(_) | Self::OutOfOrderCheckpoint { .. } => None,
+        }
+    }
+}
+
+/// Decode the contents of a `.chk` checkpoint file.
+///
+/// Checkpoint files consist of a single byte indicating the encoding of the rest of the file,
+/// which is always BCS, followed by the BCS encoded [`CheckpointData`].
+pub fn decode_checkpoint(bytes: &[u8]) -> Result<CheckpointData, BoxError> {
+    match bytes.split_first() {
+        Some((&BLOB_ENCODING_BCS, contents)) => Ok(bcs::from_bytes(contents)?),
+        Some((encoding, _)) => Err(format!("unknown checkpoint encoding {encoding}").into()),
+        None => Err("empty checkpoint file".into()),
+    }
+}
+
+/// Encode `checkpoint` in the format of a `.chk` checkpoint file.
+pub fn encode_checkpoint(checkpoint: &CheckpointData) -> Result<Vec<u8>, bcs::Error> {
+    let mut bytes = vec![BLOB_ENCODING_BCS];
+    bytes.extend(bcs::to_bytes(checkpoint)?);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use sui_sdk_types::Bls12381Signature;
+    use sui_sdk_types::CheckpointContents;
+    use sui_sdk_types::CheckpointContentsDigest;
+    use sui_sdk_types::CheckpointData;
+    use sui_sdk_types::CheckpointSummary;
+    use sui_sdk_types::GasCostSummary;
+    use sui_sdk_types::SignedCheckpointSummary;
+    use sui_sdk_types::ValidatorAggregatedSignature;
+
+    /// Construct an empty checkpoint with the provided sequence number.
+    pub fn checkpoint(sequence_number: u64) -> CheckpointData {
+        CheckpointData {
+            checkpoint_summary: SignedCheckpointSummary {
+                checkpoint: CheckpointSummary {
+                    epoch: 0,
+                    sequence_number,
+                    network_total_transactions: 0,
+                    content_digest: CheckpointContentsDigest::ZERO,
+                    previous_digest: None,
+                    epoch_rolling_bfc_gas_cost_summary: GasCostSummary::default(),
+                    timestamp_ms: 0,
+                    checkpoint_commitments: vec![],
+                    end_of_epoch_data: None,
+                    version_specific_data: vec![],
+                },
+                signature: ValidatorAggregatedSignature {
+                    epoch: 0,
+                    signature: Bls12381Signature::new([0; Bls12381Signature::LENGTH]),
+                    bitmap: Default::default(),
+                },
+            },
+            checkpoint_contents: CheckpointContents::new(vec![]),
+            transactions: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_file_roundtrip() {
+        let checkpoint = test_util::checkpoint(7);
+        let bytes = encode_checkpoint(&checkpoint).unwrap();
+        assert_eq!(bytes[0], BLOB_ENCODING_BCS);
+        assert_eq!(decode_checkpoint(&bytes).unwrap(), checkpoint);
+
+        assert!(decode_checkpoint(&[]).is_err());
+        assert!(decode_checkpoint(&[2, 0]).is_err());
+    }
+}
@@ -0,0 +1,521 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+
+use sui_sdk_types::framework::Coin;
+use sui_sdk_types::Address;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+use sui_sdk_types::CheckpointTransaction;
+use sui_sdk_types::Object;
+use sui_sdk_types::ObjectId;
+use sui_sdk_types::ObjectOut;
+use sui_sdk_types::Owner;
+use sui_sdk_types::TransactionEffects;
+use sui_sdk_types::TypePattern;
+use sui_sdk_types::TypeTag;
+
+use crate::BoxError;
+use crate::Error;
+
+/// Version of the snapshot format written by [`LiveObjectSet::write_snapshot`].
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Selects which objects are tracked by a [`LiveObjectSet`].
+///
+/// An object is tracked if it matches both the owner and the type filter, an unset filter matches
+/// everything.
+#[derive(Clone, Debug, Default)]
+pub struct ObjectFilter {
+    owners: Option<HashSet<Address>>,
+    types: Option<Vec<TypePattern>>,
+}
+
+impl ObjectFilter {
+    /// A filter which matches every object.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Track objects owned by `owner`, either directly or as a consensus address owned object.
+    pub fn with_owner(mut self, owner: Address) -> Self {
+        self.owners
+            .get_or_insert_with(Default::default)
+            .insert(owner);
+        self
+    }
+
+    /// Track objects whose type matches `type_`.
+    ///
+    /// `type_` is either an exact [`StructTag`](sui_sdk_types::StructTag) or a [`TypePattern`]. A type without type
+    /// parameters matches every instantiation of it, e.g. `0x2::coin::Coin` matches coins of any
+    /// type.
+    pub fn with_type<T: Into<TypePattern>>(mut self, type_: T) -> Self {
+        self.types
+            .get_or_insert_with(Default::default)
+            .push(type_.into());
+        self
+    }
+
+    pub fn matches(&self, object: &Object) -> bool {
+        if let Some(owners) = &self.owners {
+            if !owner_address(object.owner()).is_some_and(|owner| owners.contains(&owner)) {
+                return false;
+            }
+        }
+
+        if let Some(types) = &self.types {
+            let Some(move_struct) = object.as_struct() else {
+                return false;
+            };
+            let object_type = move_struct.object_type();
+            if !types.iter().any(|type_| type_.matches_struct(object_type)) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn owner_address(owner: &Owner) -> Option<Address> {
+    match owner {
+        Owner::Address(address) => Some(*address),
+        Owner::ConsensusAddress { owner, .. } => Some(*owner),
+        _ => None,
+    }
+}
+
+/// The set of live objects, as of the last applied checkpoint, which match an [`ObjectFilter`].
+///
+/// Checkpoints must be applied in order, starting from either genesis or the checkpoint after
+/// the one a snapshot was taken at. Each transaction's output objects replace their previous
+/// versions, while objects which were deleted or wrapped are removed and are added back if they
+/// are later unwrapped.
+#[derive(Clone, Debug, Default)]
+pub struct LiveObjectSet {
+    filter: ObjectFilter,
+    objects: HashMap<ObjectId, Object>,
+    by_owner: HashMap<Address, BTreeSet<ObjectId>>,
+    checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl LiveObjectSet {
+    pub fn new(filter: ObjectFilter) -> Self {
+        Self {
+            filter,
+            ..Default::default()
+        }
+    }
+
+    /// The sequence number of the last applied checkpoint.
+    pub fn checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.checkpoint
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn get(&self, object_id: &ObjectId) -> Option<&Object> {
+        self.objects.get(object_id)
+    }
+
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.objects.values()
+    }
+
+    /// Objects owned by `owner`, ordered by their id.
+    pub fn owned_objects(&self, owner: &Address) -> impl Iterator<Item = &Object> {
+        self.by_owner
+            .get(owner)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.objects.get(id))
+    }
+
+    /// Coins of type `coin_type` owned by `owner`.
+    pub fn coins<'a>(
+        &'a self,
+        owner: &Address,
+        coin_type: &'a TypeTag,
+    ) -> impl Iterator<Item = Coin<'a>> {
+        self.owned_objects(owner)
+            .filter_map(Coin::try_from_object)
+            .filter(move |coin| coin.coin_type() == coin_type)
+    }
+
+    /// Apply the changes made by every transaction in `checkpoint`.
+    pub fn apply_checkpoint(&mut self, checkpoint: &CheckpointData) -> Result<(), Error> {
+        let sequence_number = checkpoint.checkpoint_summary.checkpoint.sequence_number;
+        if let Some(expected) = self.checkpoint.map(|c| c + 1) {
+            if sequence_number != expected {
+                return Err(Error::OutOfOrderCheckpoint {
+                    expected,
+                    actual: sequence_number,
+                });
+            }
+        }
+
+        for transaction in &checkpoint.transactions {
+            self.apply_transaction(transaction);
+        }
+
+        self.checkpoint = Some(sequence_number);
+        Ok(())
+    }
+
+    fn apply_transaction(&mut self, transaction: &CheckpointTransaction) {
+        for object_id in removed_objects(&transaction.effects) {
+            self.remove(&object_id);
+        }
+
+        for object in &transaction.output_objects {
+            if self.filter.matches(object) {
+                self.insert(object.clone());
+            } else {
+                // The object may have been transferred away or otherwise stopped matching
+                self.remove(&object.object_id());
+            }
+        }
+    }
+
+    fn insert(&mut self, object: Object) {
+        let object_id = object.object_id();
+        self.remove(&object_id);
+
+        if let Some(owner) = owner_address(object.owner()) {
+            self.by_owner.entry(owner).or_default().insert(object_id);
+        }
+        self.objects.insert(object_id, object);
+    }
+
+    fn remove(&mut self, object_id: &ObjectId) {
+        let Some(object) = self.objects.remove(object_id) else {
+            return;
+        };
+
+        if let Some(owner) = owner_address(object.owner()) {
+            if let Some(owned) = self.by_owner.get_mut(&owner) {
+                owned.remove(object_id);
+                if owned.is_empty() {
+                    self.by_owner.remove(&owner);
+                }
+            }
+        }
+    }
+
+    /// Write a snapshot of this set, which can later be restored using
+    /// [`LiveObjectSet::read_snapshot`] in order to resume applying checkpoints.
+    pub fn write_snapshot<W: std::io::Write>(&self, mut writer: W) -> Result<(), BoxError> {
+        let mut objects = self.objects.values().collect::<Vec<_>>();
+        objects.sort_by_key(|object| object.object_id());
+
+        writer.write_all(&[SNAPSHOT_VERSION])?;
+        writer.write_all(&bcs::to_bytes(&(self.checkpoint, objects))?)?;
+        Ok(())
+    }
+
+    /// Restore a set from a snapshot, only keeping the objects which match `filter`.
+    pub fn read_snapshot<R: std::io::Read>(
+        mut reader: R,
+        filter: ObjectFilter,
+    ) -> Result<Self, BoxError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let (checkpoint, objects): (Option<CheckpointSequenceNumber>, Vec<Object>) = match bytes
+            .split_first()
+        {
+            Some((&SNAPSHOT_VERSION, contents)) => bcs::from_bytes(contents)?,
+            Some((version, _)) => return Err(format!("unknown snapshot version {version}").into()),
+            None => return Err("empty snapshot".into()),
+        };
+
+        let mut set = Self::new(filter);
+        set.checkpoint = checkpoint;
+        for object in objects {
+            if set.filter.matches(&object) {
+                set.insert(object);
+            }
+        }
+        Ok(set)
+    }
+
+    /// Save a snapshot to the file at `path`.
+    ///
+    /// The snapshot is written to a temporary file which then replaces `path` so that a crash
+    /// while saving can't leave behind a corrupted snapshot.
+    pub fn save_snapshot<P: AsRef<Path>>(&self, path: P) -> Result<(), BoxError> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+        self.write_snapshot(&mut file)?;
+        file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Load a snapshot previously saved with [`LiveObjectSet::save_snapshot`].
+    pub fn load_snapshot<P: AsRef<Path>>(path: P, filter: ObjectFilter) -> Result<Self, BoxError> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        Self::read_snapshot(file, filter)
+    }
+}
+
+/// Ids of the objects which were deleted or wrapped by a transaction.
+fn removed_objects(effects: &TransactionEffects) -> Vec<ObjectId> {
+    match effects {
+        TransactionEffects::V1(effects) => effects
+            .deleted
+            .iter()
+            .chain(&effects.wrapped)
+            .chain(&effects.unwrapped_then_deleted)
+            .map(|object| *object.object_id())
+            .collect(),
+        TransactionEffects::V2(effects) => effects
+            .changed_objects
+            .iter()
+            .filter(|change| matches!(change.output_state, ObjectOut::NotExist))
+            .map(|change| change.object_id)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::checkpoint;
+    use sui_sdk_types::ChangedObject;
+    use sui_sdk_types::ExecutionStatus;
+    use sui_sdk_types::GasCostSummary;
+    use sui_sdk_types::IdOperation;
+    use sui_sdk_types::MoveStruct;
+    use sui_sdk_types::ObjectData;
+    use sui_sdk_types::ObjectIn;
+    use sui_sdk_types::ProgrammableTransaction;
+    use sui_sdk_types::SignedTransaction;
+    use sui_sdk_types::StructTag;
+    use sui_sdk_types::Transaction;
+    use sui_sdk_types::TransactionDigest;
+    use sui_sdk_types::TransactionEffectsV2;
+    use sui_sdk_types::TransactionExpiration;
+    use sui_sdk_types::TransactionKind;
+
+    fn coin(id: u8, owner: Owner, balance: u64) -> Object {
+        let mut contents = ObjectId::new([id; 32]).into_inner().to_vec();
+        contents.extend(balance.to_le_bytes());
+        Object::new(
+            ObjectData::Struct(MoveStruct::new(StructTag::gas_coin(), true, 1, contents).unwrap()),
+            owner,
+            TransactionDigest::ZERO,
+            0,
+        )
+    }
+
+    #[test]
+    fn type_filters() {
+        let coin = coin(1, Owner::Address(Address::TWO), 10);
+
+        let coin_type = StructTag::coin(TypeTag::Bool);
+        let any_coin = StructTag {
+            type_params: vec![],
+            ..coin_type.clone()
+        };
+        assert!(ObjectFilter::all().with_type(any_coin).matches(&coin));
+        assert!(!ObjectFilter::all().with_type(coin_type).matches(&coin));
+        assert!(ObjectFilter::all()
+            .with_type("0x2::coin::*".parse::<TypePattern>().unwrap())
+            .matches(&coin));
+        assert!(!ObjectFilter::all()
+            .with_type(StructTag::clock())
+            .matches(&coin));
+    }
+
+    /// A transaction which writes `outputs` and removes `removed`.
+    fn transaction(outputs: Vec<Object>, removed: Vec<u8>) -> CheckpointTransaction {
+        let changed_objects = removed
+            .into_iter()
+            .map(|id| ChangedObject {
+                object_id: ObjectId::new([id; 32]),
+                input_state: ObjectIn::NotExist,
+                output_state: ObjectOut::NotExist,
+                id_operation: IdOperation::None,
+            })
+            .collect();
+
+        CheckpointTransaction {
+            transaction: SignedTransaction {
+                transaction: Transaction {
+                    kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                        inputs: vec![],
+                        commands: vec![],
+                    }),
+                    sender: Address::ZERO,
+                    gas_payment: sui_sdk_types::GasPayment {
+                        objects: vec![],
+                        owner: Address::ZERO,
+                        price: 0,
+                        budget: 0,
+                    },
+                    expiration: TransactionExpiration::None,
+                },
+                signatures: vec![],
+            },
+            effects: TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+                status: ExecutionStatus::Success,
+                epoch: 0,
+                gas_used: GasCostSummary::default(),
+                transaction_digest: TransactionDigest::ZERO,
+                gas_object_index: None,
+                events_digest: None,
+                dependencies: vec![],
+                lamport_version: 1,
+                changed_objects,
+                unchanged_shared_objects: vec![],
+                auxiliary_data_digest: None,
+            })),
+            events: None,
+            input_objects: vec![],
+            output_objects: outputs,
+        }
+    }
+
+    fn apply(
+        set: &mut LiveObjectSet,
+        sequence_number: u64,
+        transactions: Vec<CheckpointTransaction>,
+    ) {
+        let mut checkpoint = checkpoint(sequence_number);
+        checkpoint.transactions = transactions;
+        set.apply_checkpoint(&checkpoint).unwrap();
+    }
+
+    fn balances(set: &LiveObjectSet, owner: Address) -> Vec<u64> {
+        let bfc = StructTag::gas_coin().is_coin().unwrap().clone();
+        set.coins(&owner, &bfc).map(|coin| coin.balance()).collect()
+    }
+
+    #[test]
+    fn tracks_owned_objects() {
+        let alice = Address::new([0xa; 32]);
+        let bob = Address::new([0xb; 32]);
+        let mut set = LiveObjectSet::new(ObjectFilter::all().with_owner(alice));
+
+        apply(
+            &mut set,
+            0,
+            vec![transaction(
+                vec![
+                    coin(1, Owner::Address(alice), 10),
+                    coin(2, Owner::Address(alice), 20),
+                ],
+                vec![],
+            )],
+        );
+        assert_eq!(balances(&set, alice), [10, 20]);
+
+        // Transfer coin 2 to bob, who isn't tracked, and delete coin 1
+        apply(
+            &mut set,
+            1,
+            vec![transaction(vec![coin(2, Owner::Address(bob), 20)], vec![1])],
+        );
+        assert!(set.is_empty());
+        assert_eq!(set.owned_objects(&bob).count(), 0);
+
+        // Coin 2 is transferred back
+        apply(
+            &mut set,
+            2,
+            vec![transaction(
+                vec![coin(2, Owner::Address(alice), 25)],
+                vec![],
+            )],
+        );
+        assert_eq!(balances(&set, alice), [25]);
+        assert_eq!(set.checkpoint(), Some(2));
+
+        let mut out_of_order = checkpoint(4);
+        out_of_order.transactions = vec![];
+        assert!(matches!(
+            set.apply_checkpoint(&out_of_order),
+            Err(Error::OutOfOrderCheckpoint {
+                expected: 3,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn wrapped_objects_are_removed() {
+        let alice = Address::new([0xa; 32]);
+        let mut set = LiveObjectSet::new(ObjectFilter::all());
+
+        apply(
+            &mut set,
+            0,
+            vec![transaction(
+                vec![coin(1, Owner::Address(alice), 10)],
+                vec![],
+            )],
+        );
+        // Wrapped, and then unwrapped in a later transaction
+        apply(&mut set, 1, vec![transaction(vec![], vec![1])]);
+        assert!(set.get(&ObjectId::new([1; 32])).is_none());
+        apply(
+            &mut set,
+            2,
+            vec![transaction(
+                vec![coin(1, Owner::Address(alice), 10)],
+                vec![],
+            )],
+        );
+        assert_eq!(balances(&set, alice), [10]);
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let alice = Address::new([0xa; 32]);
+        let bob = Address::new([0xb; 32]);
+        let mut set = LiveObjectSet::new(ObjectFilter::all());
+        apply(
+            &mut set,
+            5,
+            vec![transaction(
+                vec![
+                    coin(1, Owner::Address(alice), 10),
+                    coin(2, Owner::Address(bob), 20),
+                ],
+                vec![],
+            )],
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("sui-ingestion-snapshot-{}.bcs", std::process::id()));
+        set.save_snapshot(&path).unwrap();
+
+        let restored = LiveObjectSet::load_snapshot(&path, ObjectFilter::all()).unwrap();
+        assert_eq!(restored.checkpoint(), Some(5));
+        assert_eq!(restored.len(), 2);
+        assert_eq!(balances(&restored, bob), [20]);
+
+        // Filters are applied when restoring
+        let restored =
+            LiveObjectSet::load_snapshot(&path, ObjectFilter::all().with_owner(alice)).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(
+            restored.owned_objects(&alice).next().map(|o| o.object_id()),
+            Some(ObjectId::new([1; 32]))
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
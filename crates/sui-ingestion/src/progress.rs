@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use sui_sdk_types::CheckpointSequenceNumber;
+
+use crate::BoxError;
+use crate::Error;
+
+/// Persistent storage for the progress of each [`Worker`](crate::Worker).
+///
+/// The progress of a worker is the sequence number of the next checkpoint it needs to process,
+/// i.e. every checkpoint before it has been processed.
+#[async_trait::async_trait]
+pub trait ProgressStore: Send + Sync {
+    /// Load the progress of `worker`, returning `None` if it has never made any progress.
+    async fn load(&self, worker: &str) -> Result<Option<CheckpointSequenceNumber>, BoxError>;
+
+    /// Record that `worker` has processed every checkpoint before `next_checkpoint`.
+    async fn save(
+        &self,
+        worker: &str,
+        next_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), BoxError>;
+}
+
+/// A [`ProgressStore`] which keeps progress in memory, useful for tests and one off jobs.
+#[derive(Debug, Default)]
+pub struct MemoryProgressStore {
+    progress: Mutex<HashMap<String, CheckpointSequenceNumber>>,
+}
+
+impl MemoryProgressStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current progress of `worker`.
+    pub fn get(&self, worker: &str) -> Option<CheckpointSequenceNumber> {
+        self.progress.lock().unwrap().get(worker).copied()
+    }
+}
+
+#[async_trait::async_trait]
+impl ProgressStore for MemoryProgressStore {
+    async fn load(&self, worker: &str) -> Result<Option<CheckpointSequenceNumber>, BoxError> {
+        Ok(self.get(worker))
+    }
+
+    async fn save(
+        &self,
+        worker: &str,
+        next_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), BoxError> {
+        self.progress
+            .lock()
+            .unwrap()
+            .insert(worker.to_owned(), next_checkpoint);
+        Ok(())
+    }
+}
+
+/// A [`ProgressStore`] which persists the progress of every worker to a single JSON file.
+///
+/// Updates are written to a temporary file which then replaces the original so that a crash
+/// while saving can't leave behind a corrupted file.
+#[derive(Debug)]
+pub struct FileProgressStore {
+    path: PathBuf,
+    progress: tokio::sync::Mutex<BTreeMap<String, CheckpointSequenceNumber>>,
+}
+
+impl FileProgressStore {
+    /// Open the progress file at `path`, which is created on the first save if it doesn't exist.
+    pub async fn open<P: Into<PathBuf>>(path: P) -> Result<Self, Error> {
+        let path = path.into();
+        let progress = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| Error::Progress(e.into()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(Error::Progress(e.into())),
+        };
+
+        Ok(Self {
+            path,
+            progress: tokio::sync::Mutex::new(progress),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProgressStore for FileProgressStore {
+    async fn load(&self, worker: &str) -> Result<Option<CheckpointSequenceNumber>, BoxError> {
+        Ok(self.progress.lock().await.get(worker).copied())
+    }
+
+    async fn save(
+        &self,
+        worker: &str,
+        next_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), BoxError> {
+        // Hold the lock while writing so that concurrent saves can't be reordered on disk
+        let mut progress = self.progress.lock().await;
+        progress.insert(worker.to_owned(), next_checkpoint);
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(&*progress)?).await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_progress_store_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "sui-ingestion-progress-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let store = FileProgressStore::open(&path).await.unwrap();
+        assert_eq!(store.load("a").await.unwrap(), None);
+        store.save("a", 10).await.unwrap();
+        store.save("b", 3).await.unwrap();
+        store.save("a", 11).await.unwrap();
+
+        let store = FileProgressStore::open(&path).await.unwrap();
+        assert_eq!(store.load("a").await.unwrap(), Some(11));
+        assert_eq!(store.load("b").await.unwrap(), Some(3));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,307 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use prost_types::FieldMask;
+use sui_rpc::field::FieldMaskUtil;
+use sui_rpc::proto::sui::rpc::v2beta2::get_checkpoint_request::CheckpointId;
+use sui_rpc::proto::sui::rpc::v2beta2::Checkpoint;
+use sui_rpc::proto::sui::rpc::v2beta2::GetCheckpointRequest;
+use sui_rpc::proto::sui::rpc::v2beta2::SubscribeCheckpointsRequest;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+
+use super::Backoff;
+use super::CheckpointSource;
+use crate::Error;
+
+/// Streams checkpoints from a fullnode's gRPC checkpoint subscription.
+///
+/// A subscription only delivers checkpoints from the moment it was established, so checkpoints
+/// before that, as well as any missed while reconnecting, are backfilled from the node's
+/// `LedgerService`.
+///
+/// When the subscription fails or is closed by the node it is re-established after a delay given
+/// by the source's [`Backoff`], and the stream fails once the subscription has failed more than
+/// the backoff's max retries times in a row without delivering a checkpoint.
+#[derive(Clone)]
+pub struct GrpcSource {
+    client: sui_rpc::Client,
+    prefetch: usize,
+    backoff: Backoff,
+}
+
+impl GrpcSource {
+    pub fn new(client: sui_rpc::Client) -> Self {
+        Self {
+            client,
+            prefetch: super::DEFAULT_PREFETCH,
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Set the number of checkpoints requested concurrently when backfilling.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Set the backoff used when resubscribing and when retrying a failed backfill request.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Fetch a single checkpoint from the node's `LedgerService`.
+    pub async fn fetch_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CheckpointData, Error> {
+        fetch(&mut self.client.clone(), sequence_number).await
+    }
+}
+
+/// Fields needed in order to convert a `Checkpoint` into `CheckpointData`.
+fn read_mask() -> FieldMask {
+    FieldMask::from_paths([
+        "summary.bcs",
+        "signature",
+        "contents.bcs",
+        "transactions.transaction.bcs",
+        "transactions.signatures",
+        "transactions.effects.bcs",
+        "transactions.events",
+        "transactions.input_objects.bcs",
+        "transactions.output_objects.bcs",
+    ])
+}
+
+fn convert(checkpoint: &Checkpoint) -> Result<CheckpointData, Error> {
+    checkpoint
+        .try_into()
+        .map_err(|e: sui_rpc::proto::TryFromProtoError| Error::Source(e.into()))
+}
+
+async fn fetch(
+    client: &mut sui_rpc::Client,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<CheckpointData, Error> {
+    let request = GetCheckpointRequest {
+        read_mask: Some(read_mask()),
+        checkpoint_id: Some(CheckpointId::SequenceNumber(sequence_number)),
+    };
+    let response = client
+        .ledger_client()
+        .get_checkpoint(request)
+        .await
+        .map_err(|e| Error::Source(e.into()))?
+        .into_inner();
+    let checkpoint = response
+        .checkpoint
+        .ok_or_else(|| Error::Source(format!("checkpoint {sequence_number} missing").into()))?;
+
+    let checkpoint = convert(&checkpoint)?;
+    super::check_sequence_number(sequence_number, &checkpoint)?;
+    Ok(checkpoint)
+}
+
+impl CheckpointSource for GrpcSource {
+    fn stream(
+        &self,
+        start: CheckpointSequenceNumber,
+    ) -> BoxStream<'static, Result<CheckpointData, Error>> {
+        let client = self.client.clone();
+        let subscribe = move || {
+            let mut client = client.clone();
+            async move {
+                let request = SubscribeCheckpointsRequest {
+                    read_mask: Some(read_mask()),
+                };
+                let subscription = client
+                    .subscription_client()
+                    .subscribe_checkpoints(request)
+                    .await
+                    .map_err(|e| Error::Source(e.into()))?
+                    .into_inner()
+                    .filter_map(|response| async move {
+                        match response {
+                            Ok(response) => response
+                                .cursor
+                                .map(|cursor| Ok((cursor, response.checkpoint))),
+                            Err(e) => Some(Err(Error::Source(e.into()))),
+                        }
+                    })
+                    .boxed();
+                Ok(subscription)
+            }
+        };
+
+        let client = self.client.clone();
+        let fetch = move |sequence_number| {
+            let mut client = client.clone();
+            async move { fetch(&mut client, sequence_number).await }
+        };
+
+        subscription_stream(subscribe, fetch, start, self.prefetch, self.backoff)
+    }
+}
+
+/// A checkpoint subscription, yielding the cursor of each checkpoint along with the checkpoint.
+type Subscription =
+    BoxStream<'static, Result<(CheckpointSequenceNumber, Option<Checkpoint>), Error>>;
+
+/// Build a stream of checkpoints from a subscription established by `subscribe`, backfilling any
+/// checkpoints the subscription skips over using `fetch`.
+///
+/// Up to `prefetch` checkpoints are backfilled concurrently, and failed fetches are retried
+/// according to `backoff`.
+fn subscription_stream<S, SFut, F, FFut>(
+    subscribe: S,
+    fetch: F,
+    start: CheckpointSequenceNumber,
+    prefetch: usize,
+    backoff: Backoff,
+) -> BoxStream<'static, Result<CheckpointData, Error>>
+where
+    S: Fn() -> SFut + Send + 'static,
+    SFut: Future<Output = Result<Subscription, Error>> + Send,
+    F: Fn(CheckpointSequenceNumber) -> FFut + Send + Sync + 'static,
+    FFut: Future<Output = Result<CheckpointData, Error>> + Send,
+{
+    let fetch = Arc::new(fetch);
+
+    async_stream::try_stream! {
+        let mut next = start;
+        // Number of consecutive subscriptions which failed without delivering a checkpoint
+        let mut failures = 0;
+
+        loop {
+            let error = match subscribe().await {
+                Ok(mut subscription) => {
+                    let mut error = None;
+
+                    while let Some(response) = subscription.next().await {
+                        let (cursor, checkpoint) = match response {
+                            Ok(response) => response,
+                            Err(e) => {
+                                error = Some(e);
+                                break;
+                            }
+                        };
+
+                        // Backfill anything between where we are and where the subscription is
+                        let mut backfill = futures::stream::iter(next..cursor)
+                            .map(|sequence_number| {
+                                let fetch = fetch.clone();
+                                async move { backoff.retry(|| fetch(sequence_number)).await }
+                            })
+                            .buffered(prefetch.max(1));
+                        while let Some(checkpoint) = backfill.next().await {
+                            yield checkpoint?;
+                            next += 1;
+                            failures = 0;
+                        }
+
+                        if cursor == next {
+                            let checkpoint = checkpoint.as_ref().ok_or_else(|| {
+                                Error::Source(format!("checkpoint {cursor} missing").into())
+                            })?;
+                            let checkpoint = convert(checkpoint)?;
+                            super::check_sequence_number(cursor, &checkpoint)?;
+                            yield checkpoint;
+                            next += 1;
+                            failures = 0;
+                        }
+                    }
+
+                    error.unwrap_or_else(|| Error::Source("checkpoint subscription ended".into()))
+                }
+                Err(e) => e,
+            };
+
+            if failures < backoff.max_retries() {
+                let delay = backoff.delay(failures);
+                tracing::debug!("checkpoint subscription failed, resubscribing in {delay:?}: {error}");
+                tokio::time::sleep(delay).await;
+                failures += 1;
+            } else {
+                Err(error)?;
+            }
+        }
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::checkpoint;
+    use futures::TryStreamExt;
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn backfills_gaps_in_subscription() {
+        // Each subscription delivers these cursors before ending
+        let subscriptions = Arc::new(Mutex::new(VecDeque::from([vec![3, 4, 2], vec![7]])));
+        let subscribe = move || {
+            let cursors = subscriptions.lock().unwrap().pop_front();
+            async move {
+                let cursors = cursors.ok_or_else(|| Error::Source("unavailable".into()))?;
+                let subscription = futures::stream::iter(cursors)
+                    .map(|cursor| Ok((cursor, Some(Checkpoint::from(checkpoint(cursor))))))
+                    .boxed();
+                Ok(subscription)
+            }
+        };
+
+        let fetched = Arc::new(Mutex::new(Vec::new()));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let fetch = {
+            let fetched = fetched.clone();
+            let max_in_flight = max_in_flight.clone();
+            move |sequence_number| {
+                fetched.lock().unwrap().push(sequence_number);
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok(checkpoint(sequence_number))
+                }
+            }
+        };
+
+        let backoff =
+            Backoff::new(Duration::from_millis(10), Duration::from_millis(50)).with_max_retries(1);
+        let sequence_numbers = subscription_stream(subscribe, fetch, 0, 4, backoff)
+            .take(8)
+            .map_ok(|c| c.checkpoint_summary.checkpoint.sequence_number)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(sequence_numbers, (0..8).collect::<Vec<_>>());
+
+        // Only the checkpoints skipped by the subscriptions are fetched, concurrently
+        assert_eq!(*fetched.lock().unwrap(), [0, 1, 2, 5, 6]);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fails_once_resubscribing_is_exhausted() {
+        let subscribe = || async { Err::<Subscription, _>(Error::Source("unavailable".into())) };
+        let fetch = |sequence_number| async move { Ok(checkpoint(sequence_number)) };
+
+        let backoff =
+            Backoff::new(Duration::from_millis(10), Duration::from_millis(50)).with_max_retries(2);
+        let mut stream = subscription_stream(subscribe, fetch, 0, 4, backoff);
+        assert!(stream.next().await.unwrap().is_err());
+    }
+}
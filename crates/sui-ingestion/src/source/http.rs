@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+
+use super::Backoff;
+use super::CheckpointSource;
+use crate::decode_checkpoint;
+use crate::Error;
+
+/// Reads checkpoints from an HTTP checkpoint bucket which serves each checkpoint at
+/// `<base_url>/<sequence_number>.chk`.
+#[derive(Clone, Debug)]
+pub struct HttpBucketSource {
+    base_url: reqwest::Url,
+    client: reqwest::Client,
+    prefetch: usize,
+    poll_interval: Duration,
+    backoff: Backoff,
+}
+
+impl HttpBucketSource {
+    pub fn new(base_url: reqwest::Url) -> Self {
+        Self::new_with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Construct a source which makes requests using the provided `client`, e.g. in order to
+    /// configure timeouts or authentication headers.
+    pub fn new_with_client(mut base_url: reqwest::Url, client: reqwest::Client) -> Self {
+        // Ensure joining a file name appends to the path instead of replacing its last segment
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+
+        Self {
+            base_url,
+            client,
+            prefetch: super::DEFAULT_PREFETCH,
+            poll_interval: super::DEFAULT_POLL_INTERVAL,
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Set the number of checkpoints requested ahead of the one currently being streamed.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Set how often the bucket is checked for a checkpoint which hasn't been uploaded yet.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the backoff used when retrying a failed read.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Fetch a single checkpoint, returning `None` if it doesn't exist.
+    pub async fn fetch_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>, Error> {
+        let url = self
+            .base_url
+            .join(&format!("{sequence_number}.chk"))
+            .map_err(|e| Error::Source(e.into()))?;
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| Error::Source(e.into()))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let bytes = response
+            .error_for_status()
+            .map_err(|e| Error::Source(e.into()))?
+            .bytes()
+            .await
+            .map_err(|e| Error::Source(e.into()))?;
+        decode_checkpoint(&bytes).map(Some).map_err(Error::Source)
+    }
+}
+
+impl CheckpointSource for HttpBucketSource {
+    fn stream(
+        &self,
+        start: CheckpointSequenceNumber,
+    ) -> BoxStream<'static, Result<CheckpointData, Error>> {
+        let source = self.clone();
+        super::polling_stream(
+            move |sequence_number| {
+                let source = source.clone();
+                async move { source.fetch_checkpoint(sequence_number).await }
+            },
+            start,
+            self.prefetch,
+            self.poll_interval,
+            self.backoff,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_checkpoint;
+    use crate::test_util::checkpoint;
+    use futures::TryStreamExt;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+
+    /// Serve checkpoints `0..count` over HTTP from a local listener.
+    async fn serve_checkpoints(count: u64) -> reqwest::Url {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = vec![0; 1024];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                    let body = path
+                        .strip_prefix("/bucket/")
+                        .and_then(|file| file.strip_suffix(".chk"))
+                        .and_then(|n| n.parse::<u64>().ok())
+                        .filter(|n| *n < count)
+                        .map(|n| encode_checkpoint(&checkpoint(n)).unwrap());
+
+                    let (status, body) = match body {
+                        Some(body) => ("200 OK", body),
+                        None => ("404 Not Found", vec![]),
+                    };
+                    let header = format!(
+                        "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                        body.len()
+                    );
+                    socket.write_all(header.as_bytes()).await.unwrap();
+                    socket.write_all(&body).await.unwrap();
+                });
+            }
+        });
+
+        format!("http://{addr}/bucket").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn fetch_from_bucket() {
+        let source = HttpBucketSource::new(serve_checkpoints(4).await).with_prefetch(2);
+
+        let checkpoint = source.fetch_checkpoint(1).await.unwrap().unwrap();
+        assert_eq!(checkpoint.checkpoint_summary.checkpoint.sequence_number, 1);
+        assert!(source.fetch_checkpoint(4).await.unwrap().is_none());
+
+        let sequence_numbers = futures::StreamExt::take(source.stream(1), 3)
+            .map_ok(|c| c.checkpoint_summary.checkpoint.sequence_number)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(sequence_numbers, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+
+use super::Backoff;
+use super::CheckpointSource;
+use crate::decode_checkpoint;
+use crate::Error;
+
+/// Reads checkpoints from a local directory containing a `<sequence_number>.chk` file per
+/// checkpoint, e.g. the checkpoint directory written by a fullnode.
+#[derive(Clone, Debug)]
+pub struct LocalDirectorySource {
+    path: PathBuf,
+    prefetch: usize,
+    poll_interval: Duration,
+    backoff: Backoff,
+}
+
+impl LocalDirectorySource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            prefetch: super::DEFAULT_PREFETCH,
+            poll_interval: super::DEFAULT_POLL_INTERVAL,
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Set the number of checkpoints read ahead of the one currently being streamed.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Set how often the directory is checked for a checkpoint which hasn't been written yet.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the backoff used when retrying a failed read.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Read a single checkpoint, returning `None` if it doesn't exist.
+    pub async fn fetch_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>, Error> {
+        fetch(&self.path, sequence_number).await
+    }
+}
+
+async fn fetch(
+    path: &std::path::Path,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<Option<CheckpointData>, Error> {
+    let file = path.join(format!("{sequence_number}.chk"));
+    match tokio::fs::read(&file).await {
+        Ok(bytes) => decode_checkpoint(&bytes).map(Some).map_err(Error::Source),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Source(e.into())),
+    }
+}
+
+impl CheckpointSource for LocalDirectorySource {
+    fn stream(
+        &self,
+        start: CheckpointSequenceNumber,
+    ) -> BoxStream<'static, Result<CheckpointData, Error>> {
+        let path = self.path.clone();
+        super::polling_stream(
+            move |sequence_number| {
+                let path = path.clone();
+                async move { fetch(&path, sequence_number).await }
+            },
+            start,
+            self.prefetch,
+            self.poll_interval,
+            self.backoff,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_checkpoint;
+    use crate::test_util::checkpoint;
+    use futures::StreamExt;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn streams_files_in_order() {
+        let dir = std::env::temp_dir().join(format!("sui-ingestion-local-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for i in 0..5 {
+            std::fs::write(
+                dir.join(format!("{i}.chk")),
+                encode_checkpoint(&checkpoint(i)).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let source = LocalDirectorySource::new(&dir)
+            .with_prefetch(2)
+            .with_poll_interval(Duration::from_millis(10));
+        assert!(source.fetch_checkpoint(5).await.unwrap().is_none());
+
+        let sequence_numbers = source
+            .stream(2)
+            .take(3)
+            .map_ok(|c| c.checkpoint_summary.checkpoint.sequence_number)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(sequence_numbers, [2, 3, 4]);
+
+        // The stream waits for checkpoints which haven't been written yet
+        let mut stream = source.stream(5);
+        let writer = {
+            let dir = dir.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                std::fs::write(
+                    dir.join("5.chk"),
+                    encode_checkpoint(&checkpoint(5)).unwrap(),
+                )
+                .unwrap();
+            })
+        };
+        let next = stream.next().await.unwrap().unwrap();
+        assert_eq!(next.checkpoint_summary.checkpoint.sequence_number, 5);
+        writer.await.unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
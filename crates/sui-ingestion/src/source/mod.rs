@@ -0,0 +1,212 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use sui_sdk_types::CheckpointData;
+use sui_sdk_types::CheckpointSequenceNumber;
+
+use crate::Error;
+
+mod grpc;
+mod http;
+mod local;
+
+pub use grpc::GrpcSource;
+pub use http::HttpBucketSource;
+pub use local::LocalDirectorySource;
+
+/// Default number of checkpoints fetched ahead of the one currently being read.
+const DEFAULT_PREFETCH: usize = 16;
+
+/// Default interval at which a source is polled for a checkpoint which isn't available yet.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Capped exponential backoff used when retrying failed requests to a [`CheckpointSource`].
+///
+/// The delay before retry `n` is `initial * 2^n`, capped at `max`. A request which still fails
+/// after `max_retries` retries is reported as an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    max_retries: u32,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max: max.max(initial),
+            max_retries: 10,
+        }
+    }
+
+    /// Set the number of times a failed request is retried before giving up.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// The delay before retry `attempt`, starting from `0`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max)
+    }
+
+    /// Run `f` until it succeeds, sleeping between failed attempts, returning the last error if
+    /// every retry fails.
+    pub(crate) async fn retry<F, Fut, T>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.max_retries => {
+                    let delay = self.delay(attempt);
+                    tracing::debug!("checkpoint source request failed, retrying in {delay:?}: {e}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100), Duration::from_secs(10))
+    }
+}
+
+/// A source of checkpoints.
+pub trait CheckpointSource: Send + Sync {
+    /// Stream every checkpoint, in order, starting from checkpoint `start`.
+    ///
+    /// The stream waits for new checkpoints once it has caught up with the source and only ends
+    /// if the source itself does.
+    fn stream(
+        &self,
+        start: CheckpointSequenceNumber,
+    ) -> BoxStream<'static, Result<CheckpointData, Error>>;
+}
+
+/// Build a stream of checkpoints from a source which can fetch individual checkpoints by their
+/// sequence number, polling for checkpoints which are not available yet and retrying failed
+/// fetches according to `backoff`.
+fn polling_stream<F, Fut>(
+    fetch: F,
+    start: CheckpointSequenceNumber,
+    prefetch: usize,
+    poll_interval: Duration,
+    backoff: Backoff,
+) -> BoxStream<'static, Result<CheckpointData, Error>>
+where
+    F: Fn(CheckpointSequenceNumber) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Option<CheckpointData>, Error>> + Send + 'static,
+{
+    let fetch = Arc::new(fetch);
+
+    futures::stream::iter(start..)
+        .map(move |sequence_number| {
+            let fetch = fetch.clone();
+            async move {
+                loop {
+                    if let Some(checkpoint) = backoff.retry(|| fetch(sequence_number)).await? {
+                        check_sequence_number(sequence_number, &checkpoint)?;
+                        return Ok(checkpoint);
+                    }
+                    tokio::time::sleep(poll_interval).await;
+                }
+            }
+        })
+        .buffered(prefetch.max(1))
+        .boxed()
+}
+
+pub(crate) fn check_sequence_number(
+    expected: CheckpointSequenceNumber,
+    checkpoint: &CheckpointData,
+) -> Result<(), Error> {
+    let actual = checkpoint.checkpoint_summary.checkpoint.sequence_number;
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Error::Source(
+            format!("expected checkpoint {expected}, got {actual}").into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::checkpoint;
+    use futures::TryStreamExt;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn backoff_delays_are_capped() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        let delays = (0..6).map(|i| backoff.delay(i)).collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis)
+        );
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polling_stream_retries_failed_fetches() {
+        let failures = Arc::new(AtomicU32::new(0));
+        let backoff =
+            Backoff::new(Duration::from_millis(10), Duration::from_millis(50)).with_max_retries(3);
+
+        // Every fetch of checkpoint 1 fails twice before succeeding
+        let attempts = failures.clone();
+        let stream = polling_stream(
+            move |sequence_number| {
+                let attempts = attempts.clone();
+                async move {
+                    if sequence_number == 1 && attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                        return Err(Error::Source("unavailable".into()));
+                    }
+                    Ok(Some(checkpoint(sequence_number)))
+                }
+            },
+            0,
+            1,
+            Duration::from_millis(10),
+            backoff,
+        );
+        let sequence_numbers = stream
+            .take(3)
+            .map_ok(|c| c.checkpoint_summary.checkpoint.sequence_number)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(sequence_numbers, [0, 1, 2]);
+        assert_eq!(failures.load(Ordering::SeqCst), 3);
+
+        // A fetch which keeps failing is reported once the retries are exhausted
+        let mut stream = polling_stream(
+            |_| async { Err::<Option<CheckpointData>, _>(Error::Source("unavailable".into())) },
+            0,
+            1,
+            Duration::from_millis(10),
+            backoff,
+        );
+        assert!(stream.next().await.unwrap().is_err());
+    }
+}
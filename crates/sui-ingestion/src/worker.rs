@@ -0,0 +1,24 @@
+use sui_sdk_types::CheckpointData;
+
+use crate::BoxError;
+
+/// User defined processing of checkpoints, e.g. writing the objects or events which are relevant
+/// to an application into a database.
+///
+/// Workers may be handed multiple checkpoints at once, up to the concurrency they were
+/// registered with on the [`IngestionExecutor`](crate::IngestionExecutor), so
+/// `process_checkpoint` must not rely on being called with checkpoints in order. Progress is only
+/// recorded once every earlier checkpoint has also been processed, which means that after a
+/// restart a worker may be asked to process a checkpoint it has already seen and processing
+/// should be idempotent.
+#[async_trait::async_trait]
+pub trait Worker: Send + Sync {
+    /// Unique name of this worker, used as the key for its progress in a
+    /// [`ProgressStore`](crate::ProgressStore).
+    fn name(&self) -> &str;
+
+    /// Process a single checkpoint.
+    ///
+    /// Returning an error stops ingestion.
+    async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<(), BoxError>;
+}
@@ -0,0 +1,297 @@
+use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use futures::TryStreamExt;
+use prost_types::FieldMask;
+use sui_sdk_types::ObjectId;
+use sui_sdk_types::TransactionDigest;
+
+use super::Client;
+use super::Result;
+use crate::proto::google::rpc::Status;
+use crate::proto::sui::rpc::v2beta2::BatchGetObjectsRequest;
+use crate::proto::sui::rpc::v2beta2::BatchGetTransactionsRequest;
+use crate::proto::sui::rpc::v2beta2::ExecutedTransaction;
+use crate::proto::sui::rpc::v2beta2::GetObjectRequest;
+use crate::proto::sui::rpc::v2beta2::GetObjectResult;
+use crate::proto::sui::rpc::v2beta2::GetTransactionResult;
+use crate::proto::sui::rpc::v2beta2::Object;
+
+/// Maximum number of items requested in a single batch request until the server's limit is known.
+///
+/// Fullnodes don't advertise the limit they enforce on `BatchGetObjects` and
+/// `BatchGetTransactions` through `GetServiceInfo`, so it is instead learned from the server's
+/// response the first time a batch request is rejected for being too large.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1000;
+
+/// Default maximum number of batch requests which will be in flight at the same time.
+pub const DEFAULT_MAX_CONCURRENT_BATCHES: usize = 4;
+
+impl Client {
+    /// Set the maximum number of items requested in a single batch request.
+    ///
+    /// This is lowered automatically if the server rejects a batch request for being too large.
+    pub fn with_max_batch_size(mut self, limit: usize) -> Self {
+        self.max_batch_size = Arc::new(AtomicUsize::new(limit.max(1)));
+        self
+    }
+
+    /// The maximum number of items currently requested in a single batch request.
+    pub fn max_batch_size(&self) -> usize {
+        self.max_batch_size.load(Ordering::Relaxed)
+    }
+
+    /// Set the maximum number of batch requests which will be in flight at the same time.
+    pub fn with_max_concurrent_batches(mut self, limit: usize) -> Self {
+        self.max_concurrent_batches = limit.max(1);
+        self
+    }
+
+    /// Fetch the latest version of every object in `object_ids`.
+    ///
+    /// Requests are split into chunks of at most the configured max batch size which are then
+    /// issued concurrently. The returned `Vec` is in the same order as `object_ids`, with `None`
+    /// for objects which could not be found.
+    pub async fn get_objects(
+        &self,
+        object_ids: &[ObjectId],
+        read_mask: Option<FieldMask>,
+    ) -> Result<Vec<Option<Object>>> {
+        self.batched(object_ids, |mut client, chunk| {
+            let request = BatchGetObjectsRequest {
+                requests: chunk
+                    .iter()
+                    .map(|id| GetObjectRequest {
+                        object_id: Some(id.to_string()),
+                        ..Default::default()
+                    })
+                    .collect(),
+                read_mask: read_mask.clone(),
+            };
+
+            async move {
+                let objects = client
+                    .ledger_client()
+                    .batch_get_objects(request)
+                    .await?
+                    .into_inner()
+                    .objects;
+                collect_results(objects, chunk.len(), GetObjectResult::to_result)
+            }
+        })
+        .await
+    }
+
+    /// Fetch every transaction in `digests`.
+    ///
+    /// Requests are split into chunks of at most the configured max batch size which are then
+    /// issued concurrently. The returned `Vec` is in the same order as `digests`, with `None` for
+    /// transactions which could not be found.
+    pub async fn get_transactions(
+        &self,
+        digests: &[TransactionDigest],
+        read_mask: Option<FieldMask>,
+    ) -> Result<Vec<Option<ExecutedTransaction>>> {
+        self.batched(digests, |mut client, chunk| {
+            let request = BatchGetTransactionsRequest {
+                digests: chunk.iter().map(ToString::to_string).collect(),
+                read_mask: read_mask.clone(),
+            };
+
+            async move {
+                let transactions = client
+                    .ledger_client()
+                    .batch_get_transactions(request)
+                    .await?
+                    .into_inner()
+                    .transactions;
+                collect_results(transactions, chunk.len(), GetTransactionResult::to_result)
+            }
+        })
+        .await
+    }
+
+    /// Split `items` into chunks of at most the max batch size and fetch them concurrently using
+    /// `fetch`, preserving the order of `items`.
+    async fn batched<'a, I, T, F, Fut>(&self, items: &'a [I], fetch: F) -> Result<Vec<Option<T>>>
+    where
+        F: Fn(Self, &'a [I]) -> Fut,
+        Fut: Future<Output = Result<Vec<Option<T>>>>,
+    {
+        let fetch = &fetch;
+        let chunks = items
+            .chunks(self.max_batch_size())
+            .map(|chunk| self.fetch_chunk(chunk, fetch))
+            .collect::<Vec<_>>();
+
+        futures::stream::iter(chunks)
+            .buffered(self.max_concurrent_batches)
+            .try_concat()
+            .await
+    }
+
+    /// Fetch a single chunk, splitting it further if the server rejects it for being larger than
+    /// the server's limit.
+    async fn fetch_chunk<'a, I, T, F, Fut>(
+        &self,
+        chunk: &'a [I],
+        fetch: &F,
+    ) -> Result<Vec<Option<T>>>
+    where
+        F: Fn(Self, &'a [I]) -> Fut,
+        Fut: Future<Output = Result<Vec<Option<T>>>>,
+    {
+        let mut results = Vec::with_capacity(chunk.len());
+        let mut remaining = chunk;
+        while !remaining.is_empty() {
+            let (head, tail) = remaining.split_at(self.max_batch_size().min(remaining.len()));
+            match fetch(self.clone(), head).await {
+                Ok(items) => {
+                    results.extend(items);
+                    remaining = tail;
+                }
+                Err(status) if status.code() == tonic::Code::InvalidArgument && head.len() > 1 => {
+                    // Only a rejection reporting the server's limit says the batch was too large,
+                    // any other invalid argument is returned as is
+                    match batch_limit(&status) {
+                        Some(limit) if limit < head.len() => {
+                            self.max_batch_size
+                                .fetch_min(limit.max(1), Ordering::Relaxed);
+                        }
+                        _ => return Err(status),
+                    }
+                }
+                Err(status) => return Err(status),
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Parse the limit out of a server error rejecting a batch request for being too large, e.g.
+/// "number of batch requests exceed limit of 1000".
+fn batch_limit(status: &tonic::Status) -> Option<usize> {
+    let message = status.message();
+    let (_, limit) = message.rsplit_once("limit of ")?;
+    let digits = limit
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(limit, |end| &limit[..end]);
+    digits.parse().ok()
+}
+
+/// Convert the per-item results of a batch request, mapping items which weren't found to `None`
+/// and failing if any other error was returned.
+#[allow(clippy::result_large_err)]
+fn collect_results<R, T>(
+    results: Vec<R>,
+    expected_len: usize,
+    to_result: fn(R) -> std::result::Result<T, Status>,
+) -> Result<Vec<Option<T>>> {
+    if results.len() != expected_len {
+        return Err(tonic::Status::internal(format!(
+            "expected {expected_len} results from batch request, got {}",
+            results.len()
+        )));
+    }
+
+    results
+        .into_iter()
+        .map(|result| match to_result(result) {
+            Ok(item) => Ok(Some(item)),
+            Err(status) if status.code == i32::from(tonic::Code::NotFound) => Ok(None),
+            Err(status) => Err(tonic::Status::new(status.code.into(), status.message)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(version: u64) -> GetObjectResult {
+        GetObjectResult::new_object(Object {
+            version: Some(version),
+            ..Default::default()
+        })
+    }
+
+    fn error(code: tonic::Code) -> GetObjectResult {
+        GetObjectResult::new_error(Status {
+            code: code.into(),
+            message: "error".to_owned(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn collect_results_preserves_order_and_not_found() {
+        let results = vec![object(1), error(tonic::Code::NotFound), object(3)];
+        let objects = collect_results(results, 3, GetObjectResult::to_result).unwrap();
+        let versions = objects
+            .iter()
+            .map(|o| o.as_ref().and_then(|o| o.version))
+            .collect::<Vec<_>>();
+        assert_eq!(versions, [Some(1), None, Some(3)]);
+    }
+
+    #[test]
+    fn collect_results_errors() {
+        let results = vec![object(1), error(tonic::Code::Internal)];
+        let status = collect_results(results, 2, GetObjectResult::to_result).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+
+        let status = collect_results(vec![object(1)], 2, GetObjectResult::to_result).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+
+    #[test]
+    fn parse_batch_limit() {
+        let status = tonic::Status::invalid_argument("number of batch requests exceed limit of 50");
+        assert_eq!(batch_limit(&status), Some(50));
+        let status = tonic::Status::invalid_argument("too many requests (limit of 7)");
+        assert_eq!(batch_limit(&status), Some(7));
+        assert_eq!(batch_limit(&tonic::Status::invalid_argument("bad")), None);
+    }
+
+    #[tokio::test]
+    async fn learn_batch_limit_from_server() {
+        let client = Client::new("http://localhost")
+            .unwrap()
+            .with_max_batch_size(8);
+        let items = (0..20).collect::<Vec<u32>>();
+        let results = client
+            .batched(&items, |_, chunk: &[u32]| async move {
+                if chunk.len() > 3 {
+                    Err(tonic::Status::invalid_argument(
+                        "number of batch requests exceed limit of 3",
+                    ))
+                } else {
+                    Ok(chunk.iter().map(|i| Some(*i)).collect())
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(results, items.iter().copied().map(Some).collect::<Vec<_>>());
+        assert_eq!(client.max_batch_size(), 3);
+    }
+
+    #[tokio::test]
+    async fn invalid_argument_without_limit() {
+        let client = Client::new("http://localhost")
+            .unwrap()
+            .with_max_batch_size(8);
+        let items = (0..20).collect::<Vec<u32>>();
+        let status = client
+            .batched(&items, |_, _: &[u32]| async move {
+                Err::<Vec<Option<u32>>, _>(tonic::Status::invalid_argument("invalid object id"))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), "invalid object id");
+        assert_eq!(client.max_batch_size(), 8);
+    }
+}
@@ -0,0 +1,253 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use sui_sdk_types::CheckpointDigest;
+use tonic::codegen::BoxFuture;
+use tonic::codegen::Service;
+
+use super::BoxError;
+use super::ResponseExt;
+
+/// Error returned when a response violates the consistency guarantees configured on a
+/// [`ConsistencyChecker`].
+///
+/// When a violation is detected by the [`Client`](super::Client) the error is surfaced as the
+/// source of a `tonic::Status`, use [`ConsistencyError::from_status`] to recover it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsistencyError {
+    /// The response was served by a node on a different chain than the one expected.
+    ChainIdMismatch {
+        expected: CheckpointDigest,
+        actual: CheckpointDigest,
+    },
+
+    /// The response was served from a checkpoint lower than one which has already been observed.
+    StaleCheckpoint { highest_observed: u64, actual: u64 },
+}
+
+impl ConsistencyError {
+    /// Extract a `ConsistencyError` from a `tonic::Status` returned by one of the service
+    /// clients, if the status was caused by a consistency violation.
+    pub fn from_status(status: &tonic::Status) -> Option<&Self> {
+        let mut source = std::error::Error::source(status);
+        while let Some(error) = source {
+            if let Some(error) = error.downcast_ref::<Self>() {
+                return Some(error);
+            }
+            source = error.source();
+        }
+        None
+    }
+}
+
+impl std::fmt::Display for ConsistencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ChainIdMismatch { expected, actual } => {
+                write!(f, "chain id mismatch: expected {expected}, got {actual}")
+            }
+            Self::StaleCheckpoint {
+                highest_observed,
+                actual,
+            } => write!(
+                f,
+                "stale response: served from checkpoint {actual} but checkpoint {highest_observed} has already been observed"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConsistencyError {}
+
+/// Checks that responses are consistent with what has been previously observed.
+///
+/// Clones of a `ConsistencyChecker` share the highest observed checkpoint height, so a checker
+/// installed on a [`Client`](super::Client) continues to enforce monotonic reads across clones of
+/// that client.
+#[derive(Clone, Debug, Default)]
+pub struct ConsistencyChecker {
+    expected_chain_id: Option<CheckpointDigest>,
+    monotonic_reads: bool,
+    highest_checkpoint: Arc<AtomicU64>,
+}
+
+impl ConsistencyChecker {
+    /// Construct a new checker which doesn't enforce anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject responses which were served by a node on a chain other than `chain_id`.
+    pub fn with_expected_chain_id(mut self, chain_id: CheckpointDigest) -> Self {
+        self.expected_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Reject responses which were served from a checkpoint lower than the highest checkpoint
+    /// already observed.
+    pub fn with_monotonic_reads(mut self) -> Self {
+        self.monotonic_reads = true;
+        self
+    }
+
+    /// The chain id responses are required to have been served from, if any.
+    pub fn expected_chain_id(&self) -> Option<&CheckpointDigest> {
+        self.expected_chain_id.as_ref()
+    }
+
+    /// The highest checkpoint height observed so far, if any.
+    pub fn highest_observed_checkpoint(&self) -> Option<u64> {
+        match self.highest_checkpoint.load(Ordering::Acquire) {
+            0 => None,
+            height => Some(height - 1),
+        }
+    }
+
+    /// Check a response, or error status, against the configured constraints.
+    ///
+    /// Headers which are not present on the response are not checked.
+    pub fn check<R: ResponseExt + ?Sized>(&self, response: &R) -> Result<(), ConsistencyError> {
+        if let (Some(expected), Some(actual)) = (self.expected_chain_id, response.chain_id()) {
+            if expected != actual {
+                return Err(ConsistencyError::ChainIdMismatch { expected, actual });
+            }
+        }
+
+        if let Some(height) = response.checkpoint_height() {
+            // Heights are stored offset by one so that 0 can represent "nothing observed yet"
+            let previous = self
+                .highest_checkpoint
+                .fetch_max(height.saturating_add(1), Ordering::AcqRel);
+
+            if self.monotonic_reads && previous > height.saturating_add(1) {
+                return Err(ConsistencyError::StaleCheckpoint {
+                    highest_observed: previous - 1,
+                    actual: height,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Service which validates the response headers of every request made through it using a
+/// [`ConsistencyChecker`].
+#[derive(Clone, Debug)]
+pub struct ConsistencyService<S> {
+    inner: S,
+    checker: ConsistencyChecker,
+}
+
+impl<S> ConsistencyService<S> {
+    pub fn new(inner: S, checker: ConsistencyChecker) -> Self {
+        Self { inner, checker }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for ConsistencyService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<ResBody>;
+    type Error = BoxError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let checker = self.checker.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await.map_err(Into::into)?;
+            checker.check(response.headers())?;
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::X_SUI_CHAIN_ID;
+    use crate::headers::X_SUI_CHECKPOINT_HEIGHT;
+    use http::HeaderMap;
+
+    fn headers(chain_id: Option<CheckpointDigest>, height: Option<u64>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(chain_id) = chain_id {
+            headers.insert(X_SUI_CHAIN_ID, chain_id.to_string().parse().unwrap());
+        }
+        if let Some(height) = height {
+            headers.insert(X_SUI_CHECKPOINT_HEIGHT, height.into());
+        }
+        headers
+    }
+
+    #[test]
+    fn chain_id_mismatch() {
+        let expected = CheckpointDigest::new([1; 32]);
+        let other = CheckpointDigest::new([2; 32]);
+        let checker = ConsistencyChecker::new().with_expected_chain_id(expected);
+
+        checker.check(&headers(Some(expected), None)).unwrap();
+        checker.check(&headers(None, None)).unwrap();
+        assert_eq!(
+            checker.check(&headers(Some(other), None)),
+            Err(ConsistencyError::ChainIdMismatch {
+                expected,
+                actual: other
+            })
+        );
+    }
+
+    #[test]
+    fn monotonic_reads() {
+        let checker = ConsistencyChecker::new().with_monotonic_reads();
+        assert_eq!(checker.highest_observed_checkpoint(), None);
+
+        checker.check(&headers(None, Some(0))).unwrap();
+        assert_eq!(checker.highest_observed_checkpoint(), Some(0));
+        checker.check(&headers(None, Some(10))).unwrap();
+        checker.check(&headers(None, Some(10))).unwrap();
+
+        // Clones share the observed height
+        let clone = checker.clone();
+        assert_eq!(
+            clone.check(&headers(None, Some(9))),
+            Err(ConsistencyError::StaleCheckpoint {
+                highest_observed: 10,
+                actual: 9
+            })
+        );
+        assert_eq!(checker.highest_observed_checkpoint(), Some(10));
+
+        // Without monotonic reads enabled stale responses are accepted
+        let lenient = ConsistencyChecker::new();
+        lenient.check(&headers(None, Some(10))).unwrap();
+        lenient.check(&headers(None, Some(9))).unwrap();
+        assert_eq!(lenient.highest_observed_checkpoint(), Some(10));
+    }
+
+    #[test]
+    fn error_from_status() {
+        let error = ConsistencyError::StaleCheckpoint {
+            highest_observed: 2,
+            actual: 1,
+        };
+        let status = tonic::Status::from_error(Box::new(error.clone()));
+        assert_eq!(ConsistencyError::from_status(&status), Some(&error));
+        assert_eq!(
+            ConsistencyError::from_status(&tonic::Status::not_found("")),
+            None
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use prost_types::FieldMask;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sui_sdk_types::framework::derive_dynamic_field_id;
+use sui_sdk_types::framework::derive_dynamic_object_field_id;
+use sui_sdk_types::framework::AuthenticatorState;
+use sui_sdk_types::framework::AuthenticatorStateInner;
+use sui_sdk_types::framework::Field;
+use sui_sdk_types::framework::Wrapper;
+use sui_sdk_types::ActiveJwk;
+use sui_sdk_types::MoveType;
+use sui_sdk_types::ObjectId;
+use sui_sdk_types::StructTag;
+
+use super::Client;
+use super::Result;
+use crate::field::FieldMaskUtil;
+use crate::proto::sui::rpc::v2beta2::GetObjectRequest;
+use crate::proto::sui::rpc::v2beta2::Object;
+
+impl Client {
+    /// Read the value of the dynamic field of `parent` whose name is `key`.
+    ///
+    /// The id of the field is derived locally from the name of the field and its Move type, as
+    /// given by [`MoveType`], and the field object is then fetched directly by its id and decoded
+    /// as a `0x2::dynamic_field::Field<K, V>`.
+    ///
+    /// The `parent` can also be the id of a [`Table`](sui_sdk_types::framework::Table) or
+    /// [`Bag`](sui_sdk_types::framework::Bag) in order to read one of its entries.
+    pub async fn get_dynamic_field<K, V>(&mut self, parent: ObjectId, key: &K) -> Result<Option<V>>
+    where
+        K: MoveType + Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let field_id = derive_dynamic_field_id(parent, &K::type_tag(), key)
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+
+        Ok(self
+            .get_dynamic_field_object::<K, V>(field_id)
+            .await?
+            .map(|field| field.value))
+    }
+
+    /// Read the child object stored in the dynamic object field of `parent` whose name is `key`.
+    ///
+    /// The id of the field is derived locally from the name of the field, the field is fetched to
+    /// determine the id of the child object, and then the child object itself is fetched using
+    /// the provided `read_mask`.
+    ///
+    /// The `parent` can also be the id of an
+    /// [`ObjectTable`](sui_sdk_types::framework::ObjectTable) in order to read one of its entries.
+    pub async fn get_dynamic_object_field<K>(
+        &mut self,
+        parent: ObjectId,
+        key: &K,
+        read_mask: Option<FieldMask>,
+    ) -> Result<Option<Object>>
+    where
+        K: MoveType + Serialize + DeserializeOwned,
+    {
+        let field_id = derive_dynamic_object_field_id(parent, &K::type_tag(), key)
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+
+        let Some(field) = self
+            .get_dynamic_field_object::<Wrapper<K>, ObjectId>(field_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.get_object_opt(field.value, read_mask).await
+    }
+
+    /// Return the set of JWKs currently active for zklogin, as recorded in the on-chain
+    /// [`AuthenticatorState`] object.
+    ///
+    /// Each [`ActiveJwk`] carries the epoch in which it was most recently observed by the
+    /// validators, which can be used to expire stale keys.
+    pub async fn active_jwks(&mut self) -> Result<Vec<ActiveJwk>> {
+        let read_mask =
+            FieldMask::from_paths([Object::OBJECT_TYPE_FIELD.name, Object::CONTENTS_FIELD.name]);
+        let state = self
+            .get_object_opt(AuthenticatorState::ID, Some(read_mask))
+            .await?
+            .ok_or_else(|| tonic::Status::not_found("AuthenticatorState object not found"))?;
+
+        let is_authenticator_state = state
+            .object_type
+            .as_deref()
+            .and_then(|type_| type_.parse::<StructTag>().ok())
+            .is_some_and(|type_| type_ == StructTag::authenticator_state());
+        if !is_authenticator_state {
+            return Err(tonic::Status::failed_precondition(format!(
+                "object {} is not the AuthenticatorState",
+                AuthenticatorState::ID
+            )));
+        }
+        let state: AuthenticatorState = state
+            .contents
+            .as_ref()
+            .ok_or_else(|| tonic::Status::internal("missing AuthenticatorState contents"))?
+            .deserialize()
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+
+        let inner = self
+            .get_dynamic_field::<u64, AuthenticatorStateInner>(state.id, &state.version)
+            .await?
+            .ok_or_else(|| tonic::Status::not_found("AuthenticatorStateInner not found"))?;
+
+        Ok(inner.active_jwks)
+    }
+
+    /// Fetch and decode the `Field` object with the provided id.
+    async fn get_dynamic_field_object<K, V>(
+        &mut self,
+        field_id: ObjectId,
+    ) -> Result<Option<Field<K, V>>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let read_mask =
+            FieldMask::from_paths([Object::OBJECT_TYPE_FIELD.name, Object::CONTENTS_FIELD.name]);
+        let Some(object) = self.get_object_opt(field_id, Some(read_mask)).await? else {
+            return Ok(None);
+        };
+
+        let is_dynamic_field = object
+            .object_type
+            .as_deref()
+            .and_then(|type_| type_.parse::<StructTag>().ok())
+            .is_some_and(|type_| type_.is_dynamic_field().is_some());
+        if !is_dynamic_field {
+            return Err(tonic::Status::failed_precondition(format!(
+                "object {field_id} is not a dynamic field"
+            )));
+        }
+
+        object
+            .contents
+            .as_ref()
+            .map(|contents| contents.deserialize())
+            .transpose()
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)
+    }
+
+    /// Fetch an object, returning `None` if it could not be found.
+    async fn get_object_opt(
+        &mut self,
+        object_id: ObjectId,
+        read_mask: Option<FieldMask>,
+    ) -> Result<Option<Object>> {
+        let request = GetObjectRequest {
+            object_id: Some(object_id.to_string()),
+            version: None,
+            read_mask,
+        };
+
+        match self.ledger_client().get_object(request).await {
+            Ok(response) => Ok(response.into_inner().object),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
+            Err(status) => Err(status),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use futures::Stream;
+use futures::StreamExt;
+use prost_types::FieldMask;
+use serde::de::DeserializeOwned;
+use sui_sdk_types::EventRegistry;
+use sui_sdk_types::TransactionDigest;
+use sui_sdk_types::TypedEvent;
+
+use super::Client;
+use super::Result;
+use crate::field::FieldMaskUtil;
+use crate::proto::sui::rpc::v2beta2::Checkpoint;
+use crate::proto::sui::rpc::v2beta2::ExecutedTransaction;
+use crate::proto::sui::rpc::v2beta2::SubscribeCheckpointsRequest;
+use crate::proto::TryFromProtoError;
+
+impl Client {
+    /// Subscribe to the checkpoints executed by the node, yielding every event registered for `T`
+    /// in `registry`, decoded as `T`.
+    ///
+    /// Like the underlying checkpoint subscription, the stream starts at the latest checkpoint
+    /// and ends if the subscription is closed by the node. Use
+    /// [`EventRegistry::decode_checkpoint`] to decode events from historical checkpoints.
+    pub async fn events_typed<'a, T>(
+        &mut self,
+        registry: &'a EventRegistry,
+    ) -> Result<impl Stream<Item = Result<TypedEvent<T>>> + 'a>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let request = SubscribeCheckpointsRequest {
+            read_mask: Some(FieldMask::from_paths([
+                "transactions.digest",
+                "transactions.events.events.package_id",
+                "transactions.events.events.module",
+                "transactions.events.events.sender",
+                "transactions.events.events.event_type",
+                "transactions.events.events.contents",
+            ])),
+        };
+
+        let subscription = self
+            .subscription_client()
+            .subscribe_checkpoints(request)
+            .await?
+            .into_inner();
+
+        Ok(subscription.flat_map(move |response| {
+            let events = match response {
+                Ok(response) => match (response.cursor, response.checkpoint) {
+                    (Some(cursor), Some(checkpoint)) => {
+                        decode_events(registry, cursor, &checkpoint)
+                    }
+                    _ => Vec::new(),
+                },
+                Err(status) => vec![Err(status)],
+            };
+            futures::stream::iter(events)
+        }))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn decode_events<T>(
+    registry: &EventRegistry,
+    sequence_number: u64,
+    checkpoint: &Checkpoint,
+) -> Vec<Result<TypedEvent<T>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let mut decoded = Vec::new();
+
+    for transaction in &checkpoint.transactions {
+        let Some(events) = &transaction.events else {
+            continue;
+        };
+
+        let digest = match transaction
+            .digest
+            .as_deref()
+            .ok_or_else(|| TryFromProtoError::missing(ExecutedTransaction::DIGEST_FIELD.name))
+            .and_then(|digest| {
+                digest
+                    .parse::<TransactionDigest>()
+                    .map_err(|e| TryFromProtoError::invalid(ExecutedTransaction::DIGEST_FIELD, e))
+            }) {
+            Ok(digest) => digest,
+            Err(e) => {
+                decoded.push(Err(tonic::Status::from_error(e.into())));
+                continue;
+            }
+        };
+
+        for event in &events.events {
+            let result = sui_sdk_types::Event::try_from(event)
+                .map_err(Into::into)
+                .and_then(|event| {
+                    registry
+                        .decode_typed::<T>(&event, digest, Some(sequence_number))
+                        .transpose()
+                        .map_err(Into::into)
+                })
+                .map_err(tonic::Status::from_error);
+
+            match result {
+                Ok(Some(event)) => decoded.push(Ok(event)),
+                Ok(None) => {}
+                Err(status) => decoded.push(Err(status)),
+            }
+        }
+    }
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::sui::rpc::v2beta2::Event;
+    use crate::proto::sui::rpc::v2beta2::TransactionEvents;
+    use sui_sdk_types::Address;
+    use sui_sdk_types::Identifier;
+    use sui_sdk_types::ObjectId;
+
+    #[test]
+    fn decode_checkpoint_events() {
+        let event = |type_: &str, amount: u64| {
+            Event::from(sui_sdk_types::Event {
+                package_id: ObjectId::from(Address::TWO),
+                module: Identifier::new("token").unwrap(),
+                sender: Address::ZERO,
+                type_: type_.parse().unwrap(),
+                contents: bcs::to_bytes(&amount).unwrap(),
+            })
+        };
+        let digest = TransactionDigest::ZERO;
+
+        let checkpoint = Checkpoint {
+            transactions: vec![ExecutedTransaction {
+                digest: Some(digest.to_string()),
+                events: Some(TransactionEvents {
+                    events: vec![
+                        event("0x2::token::Transfer<u8>", 1),
+                        event("0x2::token::Mint", 2),
+                        event("0x2::token::Transfer<u64>", 3),
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let registry =
+            EventRegistry::new().with_type::<u64>("0x2::token::Transfer".parse().unwrap());
+        let events = decode_events::<u64>(&registry, 7, &checkpoint)
+            .into_iter()
+            .map(|event| event.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].value, 1);
+        assert_eq!(events[1].value, 3);
+        assert_eq!(events[1].transaction_digest, digest);
+        assert_eq!(events[1].checkpoint, Some(7));
+    }
+}
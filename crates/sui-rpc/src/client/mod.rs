@@ -1,3 +1,5 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
 use std::time::Duration;
 use tap::Pipe;
 use tonic::codec::CompressionEncoding;
@@ -9,6 +11,19 @@ pub use response_ext::ResponseExt;
 mod auth;
 pub use auth::AuthInterceptor;
 
+mod batch;
+pub use batch::DEFAULT_MAX_BATCH_SIZE;
+pub use batch::DEFAULT_MAX_CONCURRENT_BATCHES;
+
+mod consistency;
+pub use consistency::ConsistencyChecker;
+pub use consistency::ConsistencyError;
+pub use consistency::ConsistencyService;
+
+mod dynamic_field;
+mod events;
+mod move_layout;
+
 use crate::proto::sui::rpc::v2beta2::ledger_service_client::LedgerServiceClient;
 use crate::proto::sui::rpc::v2beta2::live_data_service_client::LiveDataServiceClient;
 use crate::proto::sui::rpc::v2beta2::move_package_service_client::MovePackageServiceClient;
@@ -19,7 +34,7 @@ use crate::proto::sui::rpc::v2beta2::transaction_execution_service_client::Trans
 type Result<T, E = tonic::Status> = std::result::Result<T, E>;
 type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 type Channel<'a> = tonic::service::interceptor::InterceptedService<
-    &'a mut tonic::transport::Channel,
+    ConsistencyService<&'a mut tonic::transport::Channel>,
     &'a mut AuthInterceptor,
 >;
 
@@ -28,7 +43,10 @@ pub struct Client {
     uri: http::Uri,
     channel: tonic::transport::Channel,
     auth: AuthInterceptor,
+    consistency: ConsistencyChecker,
     max_decoding_message_size: Option<usize>,
+    max_batch_size: Arc<AtomicUsize>,
+    max_concurrent_batches: usize,
 }
 
 impl Client {
@@ -58,7 +76,10 @@ impl Client {
             uri,
             channel,
             auth: Default::default(),
+            consistency: Default::default(),
             max_decoding_message_size: None,
+            max_batch_size: Arc::new(AtomicUsize::new(DEFAULT_MAX_BATCH_SIZE)),
+            max_concurrent_batches: DEFAULT_MAX_CONCURRENT_BATCHES,
         })
     }
 
@@ -67,6 +88,16 @@ impl Client {
         self
     }
 
+    /// Validate the response headers of every request made through this client with `checker`.
+    pub fn with_consistency_checker(mut self, checker: ConsistencyChecker) -> Self {
+        self.consistency = checker;
+        self
+    }
+
+    pub fn consistency_checker(&self) -> &ConsistencyChecker {
+        &self.consistency
+    }
+
     pub fn with_max_decoding_message_size(mut self, limit: usize) -> Self {
         self.max_decoding_message_size = Some(limit);
         self
@@ -77,74 +108,92 @@ impl Client {
     }
 
     pub fn ledger_client(&mut self) -> LedgerServiceClient<Channel> {
-        LedgerServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        LedgerServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 
     pub fn live_data_client(&mut self) -> LiveDataServiceClient<Channel> {
-        LiveDataServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        LiveDataServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 
     pub fn execution_client(&mut self) -> TransactionExecutionServiceClient<Channel> {
-        TransactionExecutionServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        TransactionExecutionServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 
     pub fn package_client(&mut self) -> MovePackageServiceClient<Channel> {
-        MovePackageServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        MovePackageServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 
     pub fn signature_verification_client(&mut self) -> SignatureVerificationServiceClient<Channel> {
-        SignatureVerificationServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        SignatureVerificationServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 
     pub fn subscription_client(&mut self) -> SubscriptionServiceClient<Channel> {
-        SubscriptionServiceClient::with_interceptor(&mut self.channel, &mut self.auth)
-            .accept_compressed(CompressionEncoding::Zstd)
-            .pipe(|client| {
-                if let Some(limit) = self.max_decoding_message_size {
-                    client.max_decoding_message_size(limit)
-                } else {
-                    client
-                }
-            })
+        SubscriptionServiceClient::with_interceptor(
+            ConsistencyService::new(&mut self.channel, self.consistency.clone()),
+            &mut self.auth,
+        )
+        .accept_compressed(CompressionEncoding::Zstd)
+        .pipe(|client| {
+            if let Some(limit) = self.max_decoding_message_size {
+                client.max_decoding_message_size(limit)
+            } else {
+                client
+            }
+        })
     }
 }
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+
+use sui_sdk_types::Address;
+use sui_sdk_types::Identifier;
+use sui_sdk_types::MoveEnumLayout;
+use sui_sdk_types::MoveFieldLayout;
+use sui_sdk_types::MoveStructLayout;
+use sui_sdk_types::MoveTypeLayout;
+use sui_sdk_types::MoveVariantLayout;
+use sui_sdk_types::StructTag;
+use sui_sdk_types::TypeTag;
+
+use super::Client;
+use super::Result;
+use crate::proto::sui::rpc::v2beta2::datatype_descriptor::DatatypeKind;
+use crate::proto::sui::rpc::v2beta2::open_signature_body::Type;
+use crate::proto::sui::rpc::v2beta2::DatatypeDescriptor;
+use crate::proto::sui::rpc::v2beta2::FieldDescriptor;
+use crate::proto::sui::rpc::v2beta2::GetDatatypeRequest;
+use crate::proto::sui::rpc::v2beta2::OpenSignatureBody;
+
+/// Maximum nesting depth of a layout, matching the limit on value depth enforced by the Move VM.
+const MAX_LAYOUT_DEPTH: usize = 128;
+
+impl Client {
+    /// Resolve the fully instantiated layout of `type_`, which can be used to decode BCS encoded
+    /// values of that type into [`MoveValue`](sui_sdk_types::MoveValue)s.
+    ///
+    /// The definition of every datatype reachable from `type_` is fetched from the
+    /// `MovePackageService`.
+    pub async fn get_type_layout(&mut self, type_: &TypeTag) -> Result<MoveTypeLayout> {
+        let datatypes = self.resolve_datatypes(type_).await?;
+        build_layout(type_, &datatypes, 0)
+    }
+
+    /// Resolve the fully instantiated layout of the struct `type_`, e.g. the type of an object or
+    /// event whose contents need to be decoded.
+    pub async fn get_struct_layout(&mut self, type_: &StructTag) -> Result<MoveStructLayout> {
+        match self
+            .get_type_layout(&TypeTag::Struct(Box::new(type_.clone())))
+            .await?
+        {
+            MoveTypeLayout::Struct(layout) => Ok(*layout),
+            _ => Err(tonic::Status::invalid_argument(format!(
+                "{type_} is not a struct"
+            ))),
+        }
+    }
+
+    /// Fetch and instantiate the definitions of every datatype reachable from `type_`.
+    async fn resolve_datatypes(&mut self, type_: &TypeTag) -> Result<HashMap<StructTag, Datatype>> {
+        let mut descriptors: HashMap<(Address, Identifier, Identifier), DatatypeDescriptor> =
+            HashMap::new();
+        let mut datatypes = HashMap::new();
+        let mut pending = Vec::new();
+        collect_struct_tags(type_, 0, &mut pending)?;
+
+        while let Some((tag, depth)) = pending.pop() {
+            if datatypes.contains_key(&tag) {
+                continue;
+            }
+
+            let key = (tag.address, tag.module.clone(), tag.name.clone());
+            let descriptor = match descriptors.get(&key) {
+                Some(descriptor) => descriptor,
+                None => {
+                    let request = GetDatatypeRequest {
+                        package_id: Some(tag.address.to_string()),
+                        module_name: Some(tag.module.to_string()),
+                        name: Some(tag.name.to_string()),
+                    };
+                    let descriptor = self
+                        .package_client()
+                        .get_datatype(request)
+                        .await?
+                        .into_inner()
+                        .datatype
+                        .ok_or_else(|| {
+                            tonic::Status::not_found(format!("datatype {tag} not found"))
+                        })?;
+                    descriptors.entry(key).or_insert(descriptor)
+                }
+            };
+
+            let datatype = Datatype::instantiate(descriptor, &tag.type_params)
+                .map_err(|e| tonic::Status::internal(format!("invalid datatype {tag}: {e}")))?;
+            for (_, field) in datatype.fields() {
+                collect_struct_tags(field, depth + 1, &mut pending)?;
+            }
+            datatypes.insert(tag, datatype);
+        }
+
+        Ok(datatypes)
+    }
+}
+
+/// A datatype definition with all of its type parameters substituted.
+#[derive(Debug)]
+enum Datatype {
+    Struct(Vec<(Identifier, TypeTag)>),
+    Enum(Vec<(Identifier, Vec<(Identifier, TypeTag)>)>),
+}
+
+impl Datatype {
+    fn instantiate(
+        descriptor: &DatatypeDescriptor,
+        type_params: &[TypeTag],
+    ) -> Result<Self, String> {
+        if descriptor.type_parameters.len() != type_params.len() {
+            return Err(format!(
+                "expected {} type parameters, got {}",
+                descriptor.type_parameters.len(),
+                type_params.len()
+            ));
+        }
+
+        match descriptor.kind() {
+            DatatypeKind::Struct => {
+                instantiate_fields(&descriptor.fields, type_params).map(Self::Struct)
+            }
+            DatatypeKind::Enum => {
+                let mut variants = descriptor.variants.iter().collect::<Vec<_>>();
+                variants.sort_by_key(|variant| variant.position);
+                variants
+                    .into_iter()
+                    .map(|variant| {
+                        Ok((
+                            parse_identifier(variant.name.as_deref())?,
+                            instantiate_fields(&variant.fields, type_params)?,
+                        ))
+                    })
+                    .collect::<Result<_, String>>()
+                    .map(Self::Enum)
+            }
+            DatatypeKind::Unknown => Err("unknown datatype kind".to_owned()),
+        }
+    }
+
+    fn fields(&self) -> Box<dyn Iterator<Item = &(Identifier, TypeTag)> + '_> {
+        match self {
+            Self::Struct(fields) => Box::new(fields.iter()),
+            Self::Enum(variants) => Box::new(variants.iter().flat_map(|(_, fields)| fields)),
+        }
+    }
+}
+
+fn instantiate_fields(
+    fields: &[FieldDescriptor],
+    type_params: &[TypeTag],
+) -> Result<Vec<(Identifier, TypeTag)>, String> {
+    let mut fields = fields.iter().collect::<Vec<_>>();
+    fields.sort_by_key(|field| field.position);
+    fields
+        .into_iter()
+        .map(|field| {
+            let signature = field
+                .r#type
+                .as_ref()
+                .ok_or_else(|| "missing field type".to_owned())?;
+            Ok((
+                parse_identifier(field.name.as_deref())?,
+                instantiate_signature(signature, type_params)?,
+            ))
+        })
+        .collect()
+}
+
+fn parse_identifier(name: Option<&str>) -> Result<Identifier, String> {
+    let name = name.ok_or_else(|| "missing name".to_owned())?;
+    Identifier::new(name).map_err(|e| format!("invalid identifier {name}: {e}"))
+}
+
+/// Convert a signature into a concrete type by substituting its type parameters.
+fn instantiate_signature(
+    signature: &OpenSignatureBody,
+    type_params: &[TypeTag],
+) -> Result<TypeTag, String> {
+    let type_ = match signature.r#type() {
+        Type::Address => TypeTag::Address,
+        Type::Bool => TypeTag::Bool,
+        Type::U8 => TypeTag::U8,
+        Type::U16 => TypeTag::U16,
+        Type::U32 => TypeTag::U32,
+        Type::U64 => TypeTag::U64,
+        Type::U128 => TypeTag::U128,
+        Type::U256 => TypeTag::U256,
+        Type::Vector => match signature.type_parameter_instantiation.as_slice() {
+            [element] => TypeTag::Vector(Box::new(instantiate_signature(element, type_params)?)),
+            _ => return Err("vector must have exactly one type parameter".to_owned()),
+        },
+        Type::Datatype => {
+            let name = signature
+                .type_name
+                .as_deref()
+                .ok_or_else(|| "missing datatype name".to_owned())?;
+            let mut tag = name
+                .parse::<StructTag>()
+                .map_err(|e| format!("invalid datatype name {name}: {e}"))?;
+            tag.type_params = signature
+                .type_parameter_instantiation
+                .iter()
+                .map(|param| instantiate_signature(param, type_params))
+                .collect::<Result<_, _>>()?;
+            TypeTag::Struct(Box::new(tag))
+        }
+        Type::Parameter => signature
+            .type_parameter
+            .and_then(|idx| type_params.get(idx as usize))
+            .cloned()
+            .ok_or_else(|| "invalid type parameter".to_owned())?,
+        Type::Unknown => return Err("unknown signature type".to_owned()),
+    };
+
+    Ok(type_)
+}
+
+#[allow(clippy::result_large_err)]
+fn collect_struct_tags(
+    type_: &TypeTag,
+    depth: usize,
+    pending: &mut Vec<(StructTag, usize)>,
+) -> Result<()> {
+    if depth > MAX_LAYOUT_DEPTH {
+        return Err(depth_exceeded());
+    }
+
+    match type_ {
+        TypeTag::Vector(element) => collect_struct_tags(element, depth + 1, pending),
+        TypeTag::Struct(tag) => {
+            pending.push((tag.as_ref().clone(), depth));
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn build_layout(
+    type_: &TypeTag,
+    datatypes: &HashMap<StructTag, Datatype>,
+    depth: usize,
+) -> Result<MoveTypeLayout> {
+    if depth > MAX_LAYOUT_DEPTH {
+        return Err(depth_exceeded());
+    }
+
+    let layout = match type_ {
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Signer => MoveTypeLayout::Signer,
+        TypeTag::Vector(element) => {
+            MoveTypeLayout::Vector(Box::new(build_layout(element, datatypes, depth + 1)?))
+        }
+        TypeTag::Struct(tag) => {
+            let datatype = datatypes
+                .get(tag)
+                .ok_or_else(|| tonic::Status::internal(format!("unresolved datatype {tag}")))?;
+            let build_fields = |fields: &[(Identifier, TypeTag)]| {
+                fields
+                    .iter()
+                    .map(|(name, type_)| {
+                        Ok(MoveFieldLayout {
+                            name: name.clone(),
+                            layout: build_layout(type_, datatypes, depth + 1)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            };
+
+            match datatype {
+                Datatype::Struct(fields) => MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+                    type_: tag.as_ref().clone(),
+                    fields: build_fields(fields)?,
+                })),
+                Datatype::Enum(variants) => MoveTypeLayout::Enum(Box::new(MoveEnumLayout {
+                    type_: tag.as_ref().clone(),
+                    variants: variants
+                        .iter()
+                        .map(|(name, fields)| {
+                            Ok(MoveVariantLayout {
+                                name: name.clone(),
+                                fields: build_fields(fields)?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                })),
+            }
+        }
+    };
+
+    Ok(layout)
+}
+
+fn depth_exceeded() -> tonic::Status {
+    tonic::Status::invalid_argument(format!(
+        "type layout exceeds maximum depth of {MAX_LAYOUT_DEPTH}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::sui::rpc::v2beta2::VariantDescriptor;
+
+    fn signature(type_: Type) -> OpenSignatureBody {
+        OpenSignatureBody {
+            r#type: Some(type_.into()),
+            ..Default::default()
+        }
+    }
+
+    fn field(name: &str, position: u32, signature: OpenSignatureBody) -> FieldDescriptor {
+        FieldDescriptor {
+            name: Some(name.to_owned()),
+            position: Some(position),
+            r#type: Some(signature),
+        }
+    }
+
+    #[test]
+    fn instantiate_generic_struct() {
+        // struct Pair<T> { b: vector<T>, a: 0x2::balance::Balance<T> } with fields out of order
+        let descriptor = DatatypeDescriptor {
+            kind: Some(DatatypeKind::Struct.into()),
+            type_parameters: vec![Default::default()],
+            fields: vec![
+                field(
+                    "b",
+                    1,
+                    OpenSignatureBody {
+                        type_parameter_instantiation: vec![OpenSignatureBody {
+                            type_parameter: Some(0),
+                            ..signature(Type::Parameter)
+                        }],
+                        ..signature(Type::Vector)
+                    },
+                ),
+                field(
+                    "a",
+                    0,
+                    OpenSignatureBody {
+                        type_name: Some("0x2::balance::Balance".to_owned()),
+                        type_parameter_instantiation: vec![OpenSignatureBody {
+                            type_parameter: Some(0),
+                            ..signature(Type::Parameter)
+                        }],
+                        ..signature(Type::Datatype)
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let Datatype::Struct(fields) = Datatype::instantiate(&descriptor, &[TypeTag::U64]).unwrap()
+        else {
+            panic!("expected struct");
+        };
+        assert_eq!(fields[0].0.as_str(), "a");
+        assert_eq!(
+            fields[0].1,
+            "0x2::balance::Balance<u64>".parse::<TypeTag>().unwrap()
+        );
+        assert_eq!(fields[1].0.as_str(), "b");
+        assert_eq!(fields[1].1, TypeTag::Vector(Box::new(TypeTag::U64)));
+
+        // Wrong number of type parameters
+        Datatype::instantiate(&descriptor, &[]).unwrap_err();
+    }
+
+    #[test]
+    fn build_enum_layout() {
+        let descriptor = DatatypeDescriptor {
+            kind: Some(DatatypeKind::Enum.into()),
+            variants: vec![
+                VariantDescriptor {
+                    name: Some("B".to_owned()),
+                    position: Some(1),
+                    fields: vec![field("x", 0, signature(Type::U8))],
+                },
+                VariantDescriptor {
+                    name: Some("A".to_owned()),
+                    position: Some(0),
+                    fields: vec![],
+                },
+            ],
+            ..Default::default()
+        };
+        let tag: StructTag = "0x1::m::E".parse().unwrap();
+        let type_ = TypeTag::Vector(Box::new(TypeTag::Struct(Box::new(tag.clone()))));
+
+        let datatypes = HashMap::from([(
+            tag.clone(),
+            Datatype::instantiate(&descriptor, &[]).unwrap(),
+        )]);
+        let MoveTypeLayout::Vector(layout) = build_layout(&type_, &datatypes, 0).unwrap() else {
+            panic!("expected vector");
+        };
+        let MoveTypeLayout::Enum(layout) = *layout else {
+            panic!("expected enum");
+        };
+        assert_eq!(layout.type_, tag);
+        assert_eq!(layout.variants[0].name.as_str(), "A");
+        assert_eq!(layout.variants[1].fields[0].layout, MoveTypeLayout::U8);
+
+        // Unresolved datatypes are an error
+        build_layout(&type_, &HashMap::new(), 0).unwrap_err();
+    }
+}
@@ -590,3 +590,84 @@ impl Merge<&Checkpoint> for Checkpoint {
         }
     }
 }
+
+impl From<sui_sdk_types::CheckpointData> for Checkpoint {
+    fn from(value: sui_sdk_types::CheckpointData) -> Self {
+        let sui_sdk_types::CheckpointData {
+            checkpoint_summary:
+                sui_sdk_types::SignedCheckpointSummary {
+                    checkpoint,
+                    signature,
+                },
+            checkpoint_contents,
+            transactions,
+        } = value;
+
+        Self {
+            sequence_number: Some(checkpoint.sequence_number),
+            digest: Some(checkpoint.digest().to_string()),
+            summary: Some(checkpoint.into()),
+            signature: Some(signature.into()),
+            contents: Some(checkpoint_contents.into()),
+            transactions: transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<&Checkpoint> for sui_sdk_types::CheckpointData {
+    type Error = TryFromProtoError;
+
+    #[allow(clippy::result_large_err)]
+    fn try_from(value: &Checkpoint) -> Result<Self, Self::Error> {
+        let summary = value
+            .summary
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing(Checkpoint::SUMMARY_FIELD))?;
+        let checkpoint = match &summary.bcs {
+            Some(bcs) => bcs
+                .deserialize()
+                .map_err(|e| TryFromProtoError::invalid(CheckpointSummary::BCS_FIELD, e)),
+            None => summary.try_into(),
+        }
+        .map_err(|e| e.nested(Checkpoint::SUMMARY_FIELD))?;
+
+        let signature = value
+            .signature
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing(Checkpoint::SIGNATURE_FIELD))?
+            .try_into()
+            .map_err(|e: TryFromProtoError| e.nested(Checkpoint::SIGNATURE_FIELD))?;
+
+        let contents = value
+            .contents
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing(Checkpoint::CONTENTS_FIELD))?;
+        let checkpoint_contents = match &contents.bcs {
+            Some(bcs) => bcs
+                .deserialize()
+                .map_err(|e| TryFromProtoError::invalid(CheckpointContents::BCS_FIELD, e)),
+            None => contents.try_into(),
+        }
+        .map_err(|e| e.nested(Checkpoint::CONTENTS_FIELD))?;
+
+        let transactions = value
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(i, transaction)| {
+                transaction
+                    .try_into()
+                    .map_err(|e: TryFromProtoError| e.nested_at(Checkpoint::TRANSACTIONS_FIELD, i))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            checkpoint_summary: sui_sdk_types::SignedCheckpointSummary {
+                checkpoint,
+                signature,
+            },
+            checkpoint_contents,
+            transactions,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
 use crate::field::FieldMaskTree;
 use crate::merge::Merge;
+use crate::proto::TryFromProtoError;
 
 impl Merge<&ExecutedTransaction> for ExecutedTransaction {
     fn merge(&mut self, source: &ExecutedTransaction, mask: &FieldMaskTree) {
@@ -73,3 +74,107 @@ impl Merge<&ExecutedTransaction> for ExecutedTransaction {
         }
     }
 }
+
+impl From<sui_sdk_types::CheckpointTransaction> for ExecutedTransaction {
+    fn from(value: sui_sdk_types::CheckpointTransaction) -> Self {
+        let sui_sdk_types::CheckpointTransaction {
+            transaction:
+                sui_sdk_types::SignedTransaction {
+                    transaction,
+                    signatures,
+                },
+            effects,
+            events,
+            input_objects,
+            output_objects,
+        } = value;
+
+        Self {
+            digest: Some(transaction.digest().to_string()),
+            transaction: Some(transaction.into()),
+            signatures: signatures.into_iter().map(Into::into).collect(),
+            effects: Some(effects.into()),
+            events: events.map(Into::into),
+            input_objects: input_objects.into_iter().map(Into::into).collect(),
+            output_objects: output_objects.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<&ExecutedTransaction> for sui_sdk_types::CheckpointTransaction {
+    type Error = TryFromProtoError;
+
+    #[allow(clippy::result_large_err)]
+    fn try_from(value: &ExecutedTransaction) -> Result<Self, Self::Error> {
+        let transaction = value
+            .transaction
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing(ExecutedTransaction::TRANSACTION_FIELD))?
+            .try_into()
+            .map_err(|e: TryFromProtoError| e.nested(ExecutedTransaction::TRANSACTION_FIELD))?;
+
+        let signatures = value
+            .signatures
+            .iter()
+            .enumerate()
+            .map(|(i, signature)| {
+                signature.try_into().map_err(|e: TryFromProtoError| {
+                    e.nested_at(ExecutedTransaction::SIGNATURES_FIELD, i)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let effects = value
+            .effects
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing(ExecutedTransaction::EFFECTS_FIELD))?
+            .try_into()
+            .map_err(|e: TryFromProtoError| e.nested(ExecutedTransaction::EFFECTS_FIELD))?;
+
+        let events = value
+            .events
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|e: TryFromProtoError| e.nested(ExecutedTransaction::EVENTS_FIELD))?;
+
+        Ok(Self {
+            transaction: sui_sdk_types::SignedTransaction {
+                transaction,
+                signatures,
+            },
+            effects,
+            events,
+            input_objects: try_from_objects(
+                &value.input_objects,
+                ExecutedTransaction::INPUT_OBJECTS_FIELD.name,
+            )?,
+            output_objects: try_from_objects(
+                &value.output_objects,
+                ExecutedTransaction::OUTPUT_OBJECTS_FIELD.name,
+            )?,
+        })
+    }
+}
+
+/// Convert a list of objects, preferring their BCS representation when it is available.
+#[allow(clippy::result_large_err)]
+fn try_from_objects(
+    objects: &[Object],
+    field: &str,
+) -> Result<Vec<sui_sdk_types::Object>, TryFromProtoError> {
+    objects
+        .iter()
+        .enumerate()
+        .map(|(i, object)| {
+            match &object.bcs {
+                Some(bcs) => bcs
+                    .deserialize()
+                    .map_err(|e| TryFromProtoError::invalid(Object::BCS_FIELD, e)),
+                None => object.try_into(),
+            }
+            .map_err(|e| e.nested_at(field, i))
+        })
+        .collect()
+}
@@ -52,6 +52,8 @@ protobuf_roundtrip_test!(
     super::ValidatorAggregatedSignature
 );
 protobuf_roundtrip_test!(ExecutionStatus, super::ExecutionStatus);
+protobuf_roundtrip_test!(CheckpointTransaction, super::ExecutedTransaction);
+protobuf_roundtrip_test!(CheckpointData, super::Checkpoint);
 
 #[proptest]
 fn test_protobuf_roundtrip_transaction_no_bcs(instance: Transaction) {
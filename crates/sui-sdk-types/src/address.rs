@@ -63,6 +63,7 @@ pub struct Address(
 impl Address {
     pub const LENGTH: usize = 32;
     pub const ZERO: Self = Self([0u8; Self::LENGTH]);
+    pub const ONE: Self = Self::from_u8(1);
     pub const TWO: Self = Self::from_u8(2);
     pub const THREE: Self = Self::from_u8(3);
 
@@ -100,6 +101,23 @@ impl Address {
         &self.0
     }
 
+    /// Display this address with its leading zeros removed, which is the conventional way of
+    /// writing the addresses of framework packages, e.g. `0x2`.
+    ///
+    /// ```
+    /// use sui_sdk_types::Address;
+    ///
+    /// assert_eq!(Address::TWO.to_short_string(), "0x2");
+    /// assert_eq!(Address::ZERO.to_short_string(), "0x0");
+    /// ```
+    pub fn to_short_string(&self) -> String {
+        let hex = self.to_string();
+        match hex[2..].trim_start_matches('0') {
+            "" => "0x0".to_owned(),
+            digits => format!("0x{digits}"),
+        }
+    }
+
     pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, AddressParseError> {
         let mut hex = hex.as_ref();
         if (hex.starts_with(b"BFC") || hex.starts_with(b"bfc")) && hex.len() > 4 {
@@ -56,6 +56,49 @@ pub enum MultisigMemberPublicKey {
     Passkey(PasskeyPublicKey),
 }
 
+impl MultisigMemberPublicKey {
+    /// Return the flag for this member's signature scheme
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            MultisigMemberPublicKey::Ed25519(_) => SignatureScheme::Ed25519,
+            MultisigMemberPublicKey::Secp256k1(_) => SignatureScheme::Secp256k1,
+            MultisigMemberPublicKey::Secp256r1(_) => SignatureScheme::Secp256r1,
+            MultisigMemberPublicKey::ZkLogin(_) => SignatureScheme::ZkLogin,
+            MultisigMemberPublicKey::Passkey(_) => SignatureScheme::Passkey,
+        }
+    }
+}
+
+impl From<Ed25519PublicKey> for MultisigMemberPublicKey {
+    fn from(public_key: Ed25519PublicKey) -> Self {
+        Self::Ed25519(public_key)
+    }
+}
+
+impl From<Secp256k1PublicKey> for MultisigMemberPublicKey {
+    fn from(public_key: Secp256k1PublicKey) -> Self {
+        Self::Secp256k1(public_key)
+    }
+}
+
+impl From<Secp256r1PublicKey> for MultisigMemberPublicKey {
+    fn from(public_key: Secp256r1PublicKey) -> Self {
+        Self::Secp256r1(public_key)
+    }
+}
+
+impl From<ZkLoginPublicIdentifier> for MultisigMemberPublicKey {
+    fn from(public_identifier: ZkLoginPublicIdentifier) -> Self {
+        Self::ZkLogin(public_identifier)
+    }
+}
+
+impl From<PasskeyPublicKey> for MultisigMemberPublicKey {
+    fn from(public_key: PasskeyPublicKey) -> Self {
+        Self::Passkey(public_key)
+    }
+}
+
 /// A member in a multisig committee
 ///
 /// # BCS
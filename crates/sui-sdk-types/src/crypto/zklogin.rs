@@ -1,5 +1,6 @@
 use super::SimpleSignature;
 use crate::checkpoint::EpochId;
+use crate::u256::U256ParseError;
 use crate::u256::U256;
 
 /// A zklogin authenticator
@@ -197,7 +198,7 @@ impl ZkLoginClaim {
                 }
             }
 
-            let last_char_offset = (index_mod_4 + s.len() as u8 - 1) % 4;
+            let last_char_offset = (*index_mod_4 as usize + s.len() - 1) % 4;
             match last_char_offset {
                 3 => {}
                 2 => {
@@ -447,12 +448,10 @@ impl Bn254FieldElement {
     }
 
     pub const fn from_str_radix_10(s: &str) -> Result<Self, Bn254FieldElementParseError> {
-        let u256 = match U256::from_str_radix(s, 10) {
-            Ok(u256) => u256,
-            Err(e) => return Err(Bn254FieldElementParseError(e)),
-        };
-        let be = u256.to_be();
-        Ok(Self(*be.digits()))
+        match U256::from_str_radix(s, 10) {
+            Ok(u256) => Ok(Self(u256.to_be_bytes())),
+            Err(e) => Err(Bn254FieldElementParseError(e)),
+        }
     }
 
     pub fn unpadded(&self) -> &[u8] {
@@ -477,14 +476,13 @@ impl Bn254FieldElement {
 
 impl std::fmt::Display for Bn254FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let u256 = U256::from_be(U256::from_digits(self.0));
-        let radix10 = u256.to_str_radix(10);
-        f.write_str(&radix10)
+        let u256 = U256::from_be_bytes(self.0);
+        std::fmt::Display::fmt(&u256, f)
     }
 }
 
 #[derive(Debug)]
-pub struct Bn254FieldElementParseError(bnum::errors::ParseIntError);
+pub struct Bn254FieldElementParseError(U256ParseError);
 
 impl std::fmt::Display for Bn254FieldElementParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -499,8 +497,7 @@ impl std::str::FromStr for Bn254FieldElement {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let u256 = U256::from_str_radix(s, 10).map_err(Bn254FieldElementParseError)?;
-        let be = u256.to_be();
-        Ok(Self(*be.digits()))
+        Ok(Self(u256.to_be_bytes()))
     }
 }
 
@@ -0,0 +1,366 @@
+use std::any::Any;
+use std::any::TypeId;
+
+use serde::de::DeserializeOwned;
+
+use super::CheckpointData;
+use super::CheckpointSequenceNumber;
+use super::Event;
+use super::StructTag;
+use super::TransactionDigest;
+use super::TypePattern;
+
+type DecodeFn = fn(&[u8]) -> Result<Box<dyn Any + Send + Sync>, bcs::Error>;
+
+struct Registration {
+    type_: StructTag,
+    pattern: TypePattern,
+    type_id: TypeId,
+    type_name: &'static str,
+    decode: DecodeFn,
+}
+
+/// A registry mapping Move event types to the Rust types they are decoded into.
+///
+/// Rust types are registered against a [`StructTag`]. A tag without type parameters matches
+/// every instantiation of its type, e.g. `0x2::coin::CoinCreated` matches the event emitted for
+/// any coin type, while a tag with type parameters only matches that exact instantiation.
+///
+/// The same registry can be used to decode events fetched over GraphQL, streamed over gRPC, or
+/// read offline from [`CheckpointData`].
+///
+/// ```
+/// use sui_sdk_types::EventRegistry;
+/// use sui_sdk_types::ObjectId;
+///
+/// #[derive(serde_derive::Deserialize)]
+/// struct ListingCreated {
+///     listing: ObjectId,
+///     price: u64,
+/// }
+///
+/// let registry = EventRegistry::new()
+///     .with_type::<ListingCreated>("0xabc::market::ListingCreated".parse().unwrap());
+/// assert!(
+///     registry.is_registered::<ListingCreated>(&"0xabc::market::ListingCreated".parse().unwrap())
+/// );
+/// ```
+#[derive(Default)]
+pub struct EventRegistry {
+    registrations: Vec<Registration>,
+}
+
+impl EventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `T` as the Rust representation of events of type `type_`.
+    pub fn register<T>(&mut self, type_: StructTag) -> &mut Self
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.registrations.push(Registration {
+            pattern: type_.clone().into(),
+            type_,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            decode: decode_boxed::<T>,
+        });
+        self
+    }
+
+    /// Register `T` as the Rust representation of events of type `type_`.
+    pub fn with_type<T>(mut self, type_: StructTag) -> Self
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.register::<T>(type_);
+        self
+    }
+
+    /// The event types registered for `T`.
+    pub fn types_for<T: 'static>(&self) -> impl Iterator<Item = &StructTag> {
+        self.registrations
+            .iter()
+            .filter(|registration| registration.type_id == TypeId::of::<T>())
+            .map(|registration| &registration.type_)
+    }
+
+    /// Returns true if events of type `type_` are decoded as `T`.
+    pub fn is_registered<T: 'static>(&self, type_: &StructTag) -> bool {
+        self.registrations
+            .iter()
+            .filter(|registration| registration.type_id == TypeId::of::<T>())
+            .any(|registration| registration.pattern.matches_struct(type_))
+    }
+
+    /// Decode `event` as `T`.
+    ///
+    /// Returns `None` if the type of `event` isn't registered for `T`.
+    pub fn decode<T: DeserializeOwned + 'static>(
+        &self,
+        event: &Event,
+    ) -> Option<Result<T, EventDecodeError>> {
+        if !self.is_registered::<T>(&event.type_) {
+            return None;
+        }
+
+        let result = bcs::from_bytes(&event.contents).map_err(|source| EventDecodeError {
+            type_: event.type_.clone(),
+            rust_type: std::any::type_name::<T>(),
+            source,
+        });
+        Some(result)
+    }
+
+    /// Decode `event` as whichever Rust type was registered first for its type.
+    ///
+    /// Returns `None` if the type of `event` isn't registered.
+    pub fn decode_any(&self, event: &Event) -> Option<Result<DecodedEvent, EventDecodeError>> {
+        let registration = self
+            .registrations
+            .iter()
+            .find(|registration| registration.pattern.matches_struct(&event.type_))?;
+
+        let result = (registration.decode)(&event.contents)
+            .map(|value| DecodedEvent {
+                type_: event.type_.clone(),
+                value,
+            })
+            .map_err(|source| EventDecodeError {
+                type_: event.type_.clone(),
+                rust_type: registration.type_name,
+                source,
+            });
+        Some(result)
+    }
+
+    /// Decode every event in `checkpoint` which is registered for `T`, in the order they were
+    /// emitted.
+    pub fn decode_checkpoint<'a, T: DeserializeOwned + 'static>(
+        &'a self,
+        checkpoint: &'a CheckpointData,
+    ) -> impl Iterator<Item = Result<TypedEvent<T>, EventDecodeError>> + 'a {
+        let sequence_number = checkpoint.checkpoint_summary.checkpoint.sequence_number;
+
+        checkpoint
+            .checkpoint_contents
+            .transactions()
+            .iter()
+            .zip(&checkpoint.transactions)
+            .flat_map(|(info, transaction)| {
+                transaction
+                    .events
+                    .iter()
+                    .flat_map(|events| &events.0)
+                    .map(move |event| (info.transaction, event))
+            })
+            .filter_map(move |(digest, event)| {
+                self.decode_typed(event, digest, Some(sequence_number))
+            })
+    }
+
+    /// Decode `event`, emitted by transaction `transaction_digest`, as a [`TypedEvent`].
+    ///
+    /// Returns `None` if the type of `event` isn't registered for `T`.
+    pub fn decode_typed<T: DeserializeOwned + 'static>(
+        &self,
+        event: &Event,
+        transaction_digest: TransactionDigest,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Option<Result<TypedEvent<T>, EventDecodeError>> {
+        let result = self.decode::<T>(event)?.map(|value| TypedEvent {
+            value,
+            event: event.clone(),
+            transaction_digest,
+            checkpoint,
+        });
+        Some(result)
+    }
+}
+
+impl std::fmt::Debug for EventRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.registrations
+                    .iter()
+                    .map(|registration| (&registration.type_, registration.type_name)),
+            )
+            .finish()
+    }
+}
+
+fn decode_boxed<T>(bytes: &[u8]) -> Result<Box<dyn Any + Send + Sync>, bcs::Error>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    bcs::from_bytes::<T>(bytes).map(|value| Box::new(value) as _)
+}
+
+/// An event decoded into the Rust type registered for it in an [`EventRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedEvent<T> {
+    /// The decoded contents of the event.
+    pub value: T,
+
+    /// The raw event.
+    pub event: Event,
+
+    /// Digest of the transaction which emitted the event.
+    pub transaction_digest: TransactionDigest,
+
+    /// Sequence number of the checkpoint which included the transaction, if known.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// An event decoded by [`EventRegistry::decode_any`].
+#[derive(Debug)]
+pub struct DecodedEvent {
+    type_: StructTag,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl DecodedEvent {
+    pub fn type_(&self) -> &StructTag {
+        &self.type_
+    }
+
+    pub fn is<T: 'static>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Take the decoded value as a `T`, returning `self` if it is some other type.
+    pub fn downcast<T: 'static>(self) -> Result<T, Self> {
+        match self.value.downcast() {
+            Ok(value) => Ok(*value),
+            Err(value) => Err(Self {
+                type_: self.type_,
+                value,
+            }),
+        }
+    }
+}
+
+/// Error returned when the contents of an event don't match the Rust type registered for it.
+#[derive(Debug)]
+pub struct EventDecodeError {
+    type_: StructTag,
+    rust_type: &'static str,
+    source: bcs::Error,
+}
+
+impl EventDecodeError {
+    /// The type of the event which failed to decode.
+    pub fn event_type(&self) -> &StructTag {
+        &self.type_
+    }
+}
+
+impl std::fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unable to decode event {} as {}: {}",
+            self.type_, self.rust_type, self.source
+        )
+    }
+}
+
+impl std::error::Error for EventDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+    use crate::Identifier;
+    use crate::ObjectId;
+    use crate::TypeTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct CoinCreated {
+        id: ObjectId,
+        decimals: u8,
+    }
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Transfer {
+        amount: u64,
+    }
+
+    fn event<T: serde::Serialize>(type_: &str, value: &T) -> Event {
+        Event {
+            package_id: ObjectId::from(Address::TWO),
+            module: Identifier::new("coin").unwrap(),
+            sender: Address::ZERO,
+            type_: type_.parse().unwrap(),
+            contents: bcs::to_bytes(value).unwrap(),
+        }
+    }
+
+    #[test]
+    fn generic_matching() {
+        let registry = EventRegistry::new()
+            .with_type::<CoinCreated>("0x2::coin::CoinCreated".parse().unwrap())
+            .with_type::<Transfer>("0xabc::token::Transfer<0x2::bfc::BFC>".parse().unwrap());
+
+        let created = CoinCreated {
+            id: ObjectId::from(Address::THREE),
+            decimals: 9,
+        };
+        let e = event("0x2::coin::CoinCreated<0xabc::token::TOKEN>", &created);
+        assert_eq!(
+            registry.decode::<CoinCreated>(&e).unwrap().unwrap(),
+            created
+        );
+        assert!(registry.decode::<Transfer>(&e).is_none());
+
+        let transfer = Transfer { amount: 5 };
+        let e = event("0xabc::token::Transfer<0x2::bfc::BFC>", &transfer);
+        assert_eq!(registry.decode::<Transfer>(&e).unwrap().unwrap(), transfer);
+
+        // Only the registered instantiation matches
+        let e = event("0xabc::token::Transfer<0xabc::token::TOKEN>", &transfer);
+        assert!(registry.decode::<Transfer>(&e).is_none());
+        assert!(registry.decode_any(&e).is_none());
+    }
+
+    #[test]
+    fn decode_any() {
+        let registry =
+            EventRegistry::new().with_type::<Transfer>("0xabc::token::Transfer".parse().unwrap());
+
+        let e = event("0xabc::token::Transfer<u8>", &Transfer { amount: 1 });
+        let decoded = registry.decode_any(&e).unwrap().unwrap();
+        assert_eq!(decoded.type_().type_params, vec![TypeTag::U8],);
+        assert!(decoded.is::<Transfer>());
+        let decoded = decoded.downcast::<CoinCreated>().unwrap_err();
+        assert_eq!(
+            decoded.downcast::<Transfer>().unwrap(),
+            Transfer { amount: 1 }
+        );
+    }
+
+    #[test]
+    fn decode_error() {
+        let registry = EventRegistry::new()
+            .with_type::<CoinCreated>("0x2::coin::CoinCreated".parse().unwrap());
+
+        let e = event("0x2::coin::CoinCreated", &Transfer { amount: 1 });
+        let error = registry.decode::<CoinCreated>(&e).unwrap().unwrap_err();
+        assert_eq!(error.event_type(), &e.type_);
+        assert!(error.to_string().contains("CoinCreated"));
+    }
+}
@@ -0,0 +1,75 @@
+use super::super::ActiveJwk;
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+
+/// Rust representation of the Move `0x2::authenticator_state::AuthenticatorState` shared
+/// object, which lives at `0x7`.
+///
+/// The state itself, including the set of JWKs currently valid for zklogin, is stored in an
+/// [`AuthenticatorStateInner`] dynamic field of this object whose name is `version`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct AuthenticatorState {
+    pub id: ObjectId,
+    pub version: u64,
+}
+
+impl AuthenticatorState {
+    /// The id of the `AuthenticatorState` object, `0x7`.
+    pub const ID: ObjectId = {
+        let mut bytes = [0; ObjectId::LENGTH];
+        bytes[ObjectId::LENGTH - 1] = 7;
+        ObjectId::new(bytes)
+    };
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl AuthenticatorState {
+    /// Try to interpret `object` as the `AuthenticatorState`, returning `None` if the object is
+    /// of a different type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| {
+            type_ == &crate::StructTag::authenticator_state()
+        })
+    }
+}
+
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+impl AuthenticatorState {
+    /// The id of the dynamic field holding the [`AuthenticatorStateInner`] for the current
+    /// version.
+    pub fn inner_id(&self) -> ObjectId {
+        super::derive_dynamic_field_id(self.id, &crate::TypeTag::U64, &self.version)
+            .expect("u64 keys can always be serialized")
+    }
+}
+
+/// Rust representation of the Move `0x2::authenticator_state::AuthenticatorStateInner`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct AuthenticatorStateInner {
+    pub version: u64,
+    /// The JWKs currently valid for zklogin, along with the epoch in which each was most
+    /// recently validated.
+    pub active_jwks: Vec<ActiveJwk>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl AuthenticatorStateInner {
+    /// Try to interpret `object` as the dynamic field holding the `AuthenticatorStateInner`,
+    /// returning `None` if the object is of a different type or its contents could not be
+    /// decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::Field::<u64, Self>::try_from_object(object).map(|field| field.value)
+    }
+}
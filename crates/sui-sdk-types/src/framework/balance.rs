@@ -0,0 +1,22 @@
+/// Rust representation of a Move `0x2::balance::Balance<T>`.
+///
+/// A `Balance` is not an object itself, it is stored inside of other objects such as a
+/// [`Coin`](super::Coin) or a [`StakedBfc`](super::StakedBfc).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Balance {
+    pub value: u64,
+}
+
+/// Rust representation of a Move `0x2::balance::Supply<T>`, the total supply of a coin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Supply {
+    pub value: u64,
+}
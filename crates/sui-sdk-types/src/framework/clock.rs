@@ -0,0 +1,26 @@
+use super::super::CheckpointTimestamp;
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+
+/// Rust representation of the Move `0x2::clock::Clock` shared object, which lives at `0x6`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Clock {
+    pub id: ObjectId,
+    /// Timestamp of the start of the current consensus commit, in milliseconds.
+    pub timestamp_ms: CheckpointTimestamp,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl Clock {
+    /// Try to interpret `object` as the `Clock`, returning `None` if the object is of a different
+    /// type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_ == &crate::StructTag::clock())
+    }
+}
@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+
+use super::super::Object;
+use super::super::ObjectData;
+use super::super::ObjectId;
+use super::super::TypeTag;
+use super::MoveString;
+use super::Supply;
+use super::Url;
+
+#[derive(Debug, Clone)]
+pub struct Coin<'a> {
+    coin_type: Cow<'a, TypeTag>,
+    id: ObjectId,
+    balance: u64,
+}
+
+impl<'a> Coin<'a> {
+    pub fn coin_type(&self) -> &TypeTag {
+        &self.coin_type
+    }
+
+    pub fn id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    pub fn try_from_object(object: &'a Object) -> Option<Self> {
+        match &object.data {
+            ObjectData::Struct(move_struct) => {
+                let coin_type = move_struct.type_.is_coin()?;
+
+                let contents = &move_struct.contents;
+                if contents.len() != ObjectId::LENGTH + std::mem::size_of::<u64>() {
+                    return None;
+                }
+
+                let id = ObjectId::new((&contents[..ObjectId::LENGTH]).try_into().unwrap());
+                let balance =
+                    u64::from_le_bytes((&contents[ObjectId::LENGTH..]).try_into().unwrap());
+
+                Some(Self {
+                    coin_type: Cow::Borrowed(coin_type),
+                    id,
+                    balance,
+                })
+            }
+            _ => None, // package
+        }
+    }
+
+    pub fn into_owned(self) -> Coin<'static> {
+        Coin {
+            coin_type: Cow::Owned(self.coin_type.into_owned()),
+            id: self.id,
+            balance: self.balance,
+        }
+    }
+}
+
+/// Rust representation of a Move `0x2::coin::CoinMetadata<T>`, the metadata of coin type `T`.
+///
+/// The coin type itself can be retrieved from the object's type with
+/// [`StructTag::is_coin_metadata`](crate::StructTag::is_coin_metadata).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct CoinMetadata {
+    pub id: ObjectId,
+    /// Number of decimal places the coin uses.
+    pub decimals: u8,
+    pub name: MoveString,
+    /// Symbol for the coin, a `0x1::ascii::String`.
+    pub symbol: MoveString,
+    pub description: MoveString,
+    pub icon_url: Option<Url>,
+}
+
+/// Rust representation of a Move `0x2::coin::TreasuryCap<T>`, the capability to mint and burn
+/// coins of type `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct TreasuryCap {
+    pub id: ObjectId,
+    pub total_supply: Supply,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl CoinMetadata {
+    /// Try to interpret `object` as a `CoinMetadata`, returning `None` if the object is of a
+    /// different type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_.is_coin_metadata().is_some())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl TreasuryCap {
+    /// Try to interpret `object` as a `TreasuryCap`, returning `None` if the object is of a
+    /// different type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_.is_treasury_cap().is_some())
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+#[cfg(all(feature = "hash", feature = "serde"))]
+use super::super::StructTag;
+#[cfg(all(feature = "hash", feature = "serde"))]
+use super::super::TypeTag;
+
+/// Rust representation of a Move `0x2::dynamic_field::Field<Name, Value>`.
+///
+/// Every dynamic field is stored as a `Field` object owned by its parent whose id is derived from
+/// the parent's id along with the type and value of the field's name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Field<N, V> {
+    pub id: ObjectId,
+    pub name: N,
+    pub value: V,
+}
+
+/// Rust representation of a Move `0x2::dynamic_object_field::Wrapper<Name>`.
+///
+/// The names of dynamic object fields are wrapped in this type in order to distinguish them from
+/// dynamic fields with the same name. The value of a dynamic object field's `Field` is the id of
+/// the child object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Wrapper<N> {
+    pub name: N,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl<N, V> Field<N, V>
+where
+    N: serde::de::DeserializeOwned,
+    V: serde::de::DeserializeOwned,
+{
+    /// Decode a `Field` from the BCS contents of a Move object.
+    pub fn from_contents(contents: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(contents)
+    }
+
+    /// Try to interpret `object` as a `Field`, returning `None` if the object is not a dynamic
+    /// field or if its contents could not be decoded as a `Field<N, V>`.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        let move_struct = object.as_struct()?;
+        move_struct.type_.is_dynamic_field()?;
+        Self::from_contents(&move_struct.contents).ok()
+    }
+}
+
+/// Derive the id of the `Field` object backing the dynamic field of `parent` with the provided
+/// name.
+///
+/// `key_type` is the Move type of `key`, which is BCS serialized to form the name of the field.
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+pub fn derive_dynamic_field_id<K: serde::Serialize + ?Sized>(
+    parent: ObjectId,
+    key_type: &TypeTag,
+    key: &K,
+) -> Result<ObjectId, bcs::Error> {
+    let key_bytes = bcs::to_bytes(key)?;
+    Ok(parent.derive_dynamic_child_id(key_type, &key_bytes))
+}
+
+/// Derive the id of the `Field` object backing the dynamic object field of `parent` with the
+/// provided name.
+///
+/// The value of this `Field` is the id of the child object itself.
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+pub fn derive_dynamic_object_field_id<K: serde::Serialize + ?Sized>(
+    parent: ObjectId,
+    key_type: &TypeTag,
+    key: &K,
+) -> Result<ObjectId, bcs::Error> {
+    let wrapper_type = StructTag::dynamic_object_field_wrapper(key_type.clone()).into();
+    // `Wrapper<K>` is a single field struct so its BCS representation is identical to `K`
+    derive_dynamic_field_id(parent, &wrapper_type, key)
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use crate::Address;
+    use crate::MoveStruct;
+    use crate::ObjectData;
+    use crate::Owner;
+    use crate::StructTag;
+    use crate::TransactionDigest;
+    use crate::TypeTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn field_object(type_: StructTag, contents: Vec<u8>) -> Object {
+        Object::new(
+            ObjectData::Struct(MoveStruct::new(type_, false, 1, contents).unwrap()),
+            Owner::Object(ObjectId::ZERO),
+            TransactionDigest::ZERO,
+            0,
+        )
+    }
+
+    /// BCS contents of a `Field<u64, address>` with id `0x0101..01`, name `7` and value `0x2`.
+    fn field_contents() -> Vec<u8> {
+        let mut contents = vec![1; 32];
+        contents.extend([7, 0, 0, 0, 0, 0, 0, 0]);
+        contents.extend([0; 31]);
+        contents.push(2);
+        contents
+    }
+
+    #[test]
+    fn decode_field() {
+        let field = Field::<u64, Address>::from_contents(&field_contents()).unwrap();
+        assert_eq!(field.id, ObjectId::new([1; 32]));
+        assert_eq!(field.name, 7);
+        assert_eq!(field.value, Address::TWO);
+
+        // The contents are too short to be a `Field<u64, u128>`
+        assert!(Field::<u64, u128>::from_contents(&field_contents()).is_err());
+    }
+
+    #[test]
+    fn decode_dynamic_object_field() {
+        // `Field<Wrapper<vector<u8>>, ID>` with name `b"ab"` and value `0x0303..03`
+        let mut contents = vec![1; 32];
+        contents.extend([2, b'a', b'b']);
+        contents.extend([3; 32]);
+
+        let field = Field::<Wrapper<Vec<u8>>, ObjectId>::from_contents(&contents).unwrap();
+        assert_eq!(field.name.name, b"ab");
+        assert_eq!(field.value, ObjectId::new([3; 32]));
+    }
+
+    #[test]
+    fn field_from_object() {
+        let object = field_object(
+            StructTag::dynamic_field(TypeTag::U64, TypeTag::Address),
+            field_contents(),
+        );
+        let field = Field::<u64, Address>::try_from_object(&object).unwrap();
+        assert_eq!(field.value, Address::TWO);
+
+        let object = field_object(StructTag::gas_coin(), field_contents());
+        assert_eq!(Field::<u64, Address>::try_from_object(&object), None);
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn derive_field_ids() {
+        let parent = ObjectId::new([2; 32]);
+
+        assert_eq!(
+            derive_dynamic_field_id(parent, &TypeTag::U64, &5u64).unwrap(),
+            "0xc55ee4514f6cae2046c380f2af9268c31cbdab7c0077f0a191191f1d3f5181f0"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            derive_dynamic_field_id(parent, &TypeTag::Address, &Address::new([3; 32])).unwrap(),
+            "0xc5b753a0ec9f5f588e93b23a4721c2252adf0cead46bdf3a9eabe4ad034b5577"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            derive_dynamic_object_field_id(parent, &TypeTag::U64, &5u64).unwrap(),
+            "0x1bd3c8f3e075f62d130d4309c54c0caa466b2313fc5450a59fae00352c8772c8"
+                .parse()
+                .unwrap()
+        );
+    }
+}
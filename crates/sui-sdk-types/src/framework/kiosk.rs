@@ -0,0 +1,33 @@
+use super::super::Address;
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+use super::Balance;
+
+/// Rust representation of a Move `0x2::kiosk::Kiosk`.
+///
+/// The items listed in a kiosk are stored as dynamic object fields on the kiosk's id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Kiosk {
+    pub id: ObjectId,
+    /// Proceeds from sales which haven't been withdrawn yet.
+    pub profits: Balance,
+    pub owner: Address,
+    /// Number of items stored in the kiosk.
+    pub item_count: u32,
+    pub allow_extensions: bool,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl Kiosk {
+    /// Try to interpret `object` as a `Kiosk`, returning `None` if the object is of a different
+    /// type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_ == &crate::StructTag::kiosk())
+    }
+}
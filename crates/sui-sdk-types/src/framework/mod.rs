@@ -0,0 +1,174 @@
+//! Rust definitions of move/sui framework types.
+
+mod authenticator_state;
+mod balance;
+mod clock;
+mod coin;
+mod dynamic_field;
+mod kiosk;
+mod package;
+mod staking_pool;
+mod string;
+mod table;
+
+pub use authenticator_state::AuthenticatorState;
+pub use authenticator_state::AuthenticatorStateInner;
+pub use balance::Balance;
+pub use balance::Supply;
+pub use clock::Clock;
+pub use coin::Coin;
+pub use coin::CoinMetadata;
+pub use coin::TreasuryCap;
+
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+pub use dynamic_field::derive_dynamic_field_id;
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+pub use dynamic_field::derive_dynamic_object_field_id;
+pub use dynamic_field::Field;
+pub use dynamic_field::Wrapper;
+pub use kiosk::Kiosk;
+pub use package::UpgradeCap;
+pub use staking_pool::StakedBfc;
+pub use string::MoveString;
+pub use string::Url;
+pub use table::Bag;
+pub use table::ObjectTable;
+pub use table::Table;
+
+#[cfg(feature = "serde")]
+use super::Object;
+
+/// Decode the contents of `object` as a `T` if the type of the object satisfies `is_type`.
+#[cfg(feature = "serde")]
+fn decode_object<T: serde::de::DeserializeOwned>(
+    object: &Object,
+    is_type: impl FnOnce(&super::StructTag) -> bool,
+) -> Option<T> {
+    let move_struct = object.as_struct()?;
+    if !is_type(&move_struct.type_) {
+        return None;
+    }
+    bcs::from_bytes(&move_struct.contents).ok()
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use crate::Address;
+    use crate::MoveStruct;
+    use crate::ObjectData;
+    use crate::ObjectId;
+    use crate::Owner;
+    use crate::StructTag;
+    use crate::TransactionDigest;
+    use crate::TypeTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn object<T: serde::Serialize>(type_: StructTag, contents: &T) -> Object {
+        let contents = bcs::to_bytes(contents).unwrap();
+        Object::new(
+            ObjectData::Struct(MoveStruct::new(type_, true, 1, contents).unwrap()),
+            Owner::Address(Address::ZERO),
+            TransactionDigest::ZERO,
+            0,
+        )
+    }
+
+    #[test]
+    fn coin_metadata() {
+        let metadata = CoinMetadata {
+            id: ObjectId::new([1; 32]),
+            decimals: 9,
+            name: "Token".into(),
+            symbol: "TOK".into(),
+            description: "".into(),
+            icon_url: Some(Url {
+                url: "https://example.com/icon.png".into(),
+            }),
+        };
+        let coin_type: TypeTag = "0x5::token::TOKEN".parse().unwrap();
+        let type_ = StructTag::coin_metadata(coin_type.clone());
+        assert_eq!(type_.is_coin_metadata(), Some(&coin_type));
+        assert_eq!(type_.is_treasury_cap(), None);
+
+        let object = object(type_, &metadata);
+        assert_eq!(CoinMetadata::try_from_object(&object), Some(metadata));
+        assert_eq!(TreasuryCap::try_from_object(&object), None);
+    }
+
+    #[test]
+    fn staked_bfc() {
+        let stake = StakedBfc {
+            id: ObjectId::new([1; 32]),
+            pool_id: ObjectId::new([2; 32]),
+            stake_activation_epoch: 10,
+            principal: Balance { value: 1_000 },
+        };
+
+        let object = object(StructTag::staked_sui(), &stake);
+        assert_eq!(StakedBfc::try_from_object(&object), Some(stake));
+        assert_eq!(Kiosk::try_from_object(&object), None);
+    }
+
+    #[test]
+    fn clock() {
+        let clock = Clock {
+            id: ObjectId::new([6; 32]),
+            timestamp_ms: 1_700_000_000_000,
+        };
+
+        let object = object(StructTag::clock(), &clock);
+        assert_eq!(Clock::try_from_object(&object), Some(clock));
+
+        // Contents which don't match the type are rejected
+        let object = self::object(StructTag::upgrade_cap(), &ObjectId::ZERO);
+        assert_eq!(UpgradeCap::try_from_object(&object), None);
+    }
+
+    #[test]
+    fn authenticator_state() {
+        let state = AuthenticatorState {
+            id: AuthenticatorState::ID,
+            version: 1,
+        };
+        let object = self::object(StructTag::authenticator_state(), &state);
+        assert_eq!(AuthenticatorState::try_from_object(&object), Some(state));
+
+        let inner = AuthenticatorStateInner {
+            version: 1,
+            active_jwks: vec![crate::ActiveJwk {
+                jwk_id: crate::JwkId {
+                    iss: "https://accounts.google.com".into(),
+                    kid: "1".into(),
+                },
+                jwk: crate::Jwk {
+                    kty: "RSA".into(),
+                    e: "AQAB".into(),
+                    n: "abc".into(),
+                    alg: "RS256".into(),
+                },
+                epoch: 5,
+            }],
+        };
+        let field = Field {
+            id: ObjectId::new([8; 32]),
+            name: 1u64,
+            value: inner.clone(),
+        };
+        let type_ = StructTag::dynamic_field(
+            TypeTag::U64,
+            "0x2::authenticator_state::AuthenticatorStateInner"
+                .parse()
+                .unwrap(),
+        );
+        let object = self::object(type_, &field);
+        assert_eq!(
+            AuthenticatorStateInner::try_from_object(&object),
+            Some(inner)
+        );
+    }
+}
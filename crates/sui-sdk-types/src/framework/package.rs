@@ -0,0 +1,37 @@
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+
+/// Rust representation of a Move `0x2::package::UpgradeCap`, the capability to upgrade a package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct UpgradeCap {
+    pub id: ObjectId,
+    /// Id of the latest version of the package this cap can upgrade.
+    pub package: ObjectId,
+    /// Number of upgrades which have been applied to the package.
+    pub version: u64,
+    /// The most permissive kind of upgrade currently allowed, see [`UpgradeCap::COMPATIBLE`],
+    /// [`UpgradeCap::ADDITIVE`] and [`UpgradeCap::DEP_ONLY`].
+    pub policy: u8,
+}
+
+impl UpgradeCap {
+    /// Upgrades which maintain the public API of the package.
+    pub const COMPATIBLE: u8 = 0;
+    /// Upgrades which may only add new functions or types.
+    pub const ADDITIVE: u8 = 128;
+    /// Upgrades which may only change the dependencies of the package.
+    pub const DEP_ONLY: u8 = 192;
+
+    /// Try to interpret `object` as an `UpgradeCap`, returning `None` if the object is of a
+    /// different type or its contents could not be decoded.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_ == &crate::StructTag::upgrade_cap())
+    }
+}
@@ -0,0 +1,32 @@
+use super::super::EpochId;
+#[cfg(feature = "serde")]
+use super::super::Object;
+use super::super::ObjectId;
+use super::Balance;
+
+/// Rust representation of a Move `0x3::staking_pool::StakedBfc`, a stake in a validator's staking
+/// pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct StakedBfc {
+    pub id: ObjectId,
+    /// Id of the staking pool the stake was deposited into.
+    pub pool_id: ObjectId,
+    /// The epoch at which the stake becomes active.
+    pub stake_activation_epoch: EpochId,
+    /// The staked BFC.
+    pub principal: Balance,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl StakedBfc {
+    /// Try to interpret `object` as a `StakedBfc`, returning `None` if the object is of a
+    /// different type or its contents could not be decoded.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        super::decode_object(object, |type_| type_ == &crate::StructTag::staked_sui())
+    }
+}
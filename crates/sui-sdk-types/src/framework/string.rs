@@ -0,0 +1,60 @@
+/// Rust representation of a Move `0x1::string::String`.
+///
+/// The BCS representation of a Move string is the same as that of a Rust [`String`], this type
+/// exists to make it clear which Move type a field corresponds to. `0x1::ascii::String` shares the
+/// same representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(transparent)
+)]
+pub struct MoveString(String);
+
+impl MoveString {
+    pub fn new<T: Into<String>>(s: T) -> Self {
+        Self(s.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl std::fmt::Display for MoveString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for MoveString {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&str> for MoveString {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
+
+impl From<MoveString> for String {
+    fn from(s: MoveString) -> Self {
+        s.0
+    }
+}
+
+/// Rust representation of a Move `0x2::url::Url`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Url {
+    pub url: MoveString,
+}
@@ -0,0 +1,136 @@
+#[cfg(all(feature = "hash", feature = "serde"))]
+use super::super::MoveType;
+use super::super::ObjectId;
+
+/// Rust representation of a Move `0x2::table::Table<K, V>`.
+///
+/// The entries of a `Table` are stored as dynamic fields on the table's id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Table {
+    pub id: ObjectId,
+    pub size: u64,
+}
+
+/// Rust representation of a Move `0x2::bag::Bag`.
+///
+/// The entries of a `Bag` are stored as dynamic fields on the bag's id and, unlike a [`Table`],
+/// may have heterogeneous key and value types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Bag {
+    pub id: ObjectId,
+    pub size: u64,
+}
+
+/// Rust representation of a Move `0x2::object_table::ObjectTable<K, V>`.
+///
+/// The entries of an `ObjectTable` are stored as dynamic object fields on the table's id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct ObjectTable {
+    pub id: ObjectId,
+    pub size: u64,
+}
+
+macro_rules! impl_collection {
+    ($ty:ident, $derive:ident) => {
+        impl $ty {
+            pub fn id(&self) -> &ObjectId {
+                &self.id
+            }
+
+            /// Number of entries in this collection.
+            pub fn len(&self) -> u64 {
+                self.size
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.size == 0
+            }
+
+            /// Derive the id of the `Field` object storing the entry for `key`.
+            #[cfg(all(feature = "hash", feature = "serde"))]
+            #[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+            pub fn entry_id<K: MoveType + serde::Serialize + ?Sized>(
+                &self,
+                key: &K,
+            ) -> Result<ObjectId, bcs::Error> {
+                super::$derive(self.id, &K::type_tag(), key)
+            }
+        }
+    };
+}
+
+impl_collection!(Table, derive_dynamic_field_id);
+impl_collection!(Bag, derive_dynamic_field_id);
+impl_collection!(ObjectTable, derive_dynamic_object_field_id);
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    /// BCS contents of a collection with id `0x0202..02` and 3 entries.
+    fn contents() -> Vec<u8> {
+        let mut contents = vec![2; 32];
+        contents.extend([3, 0, 0, 0, 0, 0, 0, 0]);
+        contents
+    }
+
+    #[test]
+    fn decode_collections() {
+        let table: Table = bcs::from_bytes(&contents()).unwrap();
+        assert_eq!(table.id(), &ObjectId::new([2; 32]));
+        assert_eq!(table.len(), 3);
+        assert!(!table.is_empty());
+
+        let bag: Bag = bcs::from_bytes(&contents()).unwrap();
+        assert_eq!(bag.len(), 3);
+
+        let empty: ObjectTable = bcs::from_bytes(&[[2; 32].as_slice(), &[0; 8]].concat()).unwrap();
+        assert!(empty.is_empty());
+
+        // Trailing bytes are rejected
+        assert!(bcs::from_bytes::<Table>(&[contents(), vec![0]].concat()).is_err());
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn entry_ids() {
+        let table: Table = bcs::from_bytes(&contents()).unwrap();
+        assert_eq!(
+            table.entry_id(&5u64).unwrap(),
+            "0xc55ee4514f6cae2046c380f2af9268c31cbdab7c0077f0a191191f1d3f5181f0"
+                .parse()
+                .unwrap()
+        );
+
+        let bag: Bag = bcs::from_bytes(&contents()).unwrap();
+        assert_eq!(
+            bag.entry_id("hello").unwrap(),
+            "0xd93934115fdb214c59fd0d52738061da94d23c17c1f53feb4c2bf016d3b3466c"
+                .parse()
+                .unwrap()
+        );
+
+        let object_table: ObjectTable = bcs::from_bytes(&contents()).unwrap();
+        assert_eq!(
+            object_table.entry_id(&5u64).unwrap(),
+            "0x1bd3c8f3e075f62d130d4309c54c0caa466b2313fc5450a59fae00352c8772c8"
+                .parse()
+                .unwrap()
+        );
+    }
+}
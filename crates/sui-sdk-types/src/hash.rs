@@ -234,6 +234,28 @@ impl crate::PasskeyPublicKey {
     }
 }
 
+impl crate::MultisigMemberPublicKey {
+    /// Derive the `Address` controlled solely by this multisig member's public key.
+    ///
+    /// For a zklogin member this is the address derived via
+    /// [`ZkLoginPublicIdentifier::derive_address_padded`], matching how the member contributes
+    /// to the address of a [`MultisigCommittee`].
+    ///
+    /// [`ZkLoginPublicIdentifier::derive_address_padded`]: crate::ZkLoginPublicIdentifier::derive_address_padded
+    /// [`MultisigCommittee`]: crate::MultisigCommittee
+    pub fn derive_address(&self) -> Address {
+        use crate::MultisigMemberPublicKey::*;
+
+        match self {
+            Ed25519(p) => p.derive_address(),
+            Secp256k1(p) => p.derive_address(),
+            Secp256r1(p) => p.derive_address(),
+            ZkLogin(p) => p.derive_address_padded(),
+            Passkey(p) => p.derive_address(),
+        }
+    }
+}
+
 impl crate::MultisigCommittee {
     /// Derive an `Address` from this MultisigCommittee.
     ///
@@ -276,6 +298,38 @@ impl crate::MultisigCommittee {
     }
 }
 
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl crate::UserSignature {
+    /// Derive the addresses which could have produced this signature.
+    ///
+    /// This yields a single address for all signature schemes except zklogin, where two addresses
+    /// are yielded when the `address_seed` has a leading zero-byte (see
+    /// [`ZkLoginPublicIdentifier::derive_address`]). Returns an error if the `iss` claim of a
+    /// zklogin signature is invalid.
+    ///
+    /// [`ZkLoginPublicIdentifier::derive_address`]: crate::ZkLoginPublicIdentifier::derive_address
+    pub fn derive_addresses(&self) -> Result<Vec<Address>, crate::InvalidZkLoginClaimError> {
+        use crate::SimpleSignature::*;
+        use crate::UserSignature::*;
+
+        let addresses = match self {
+            Simple(Ed25519 { public_key, .. }) => vec![public_key.derive_address()],
+            Simple(Secp256k1 { public_key, .. }) => vec![public_key.derive_address()],
+            Simple(Secp256r1 { public_key, .. }) => vec![public_key.derive_address()],
+            Multisig(multisig) => vec![multisig.committee().derive_address()],
+            ZkLogin(zklogin) => zklogin
+                .inputs
+                .public_identifier()?
+                .derive_address()
+                .collect(),
+            Passkey(passkey) => vec![passkey.public_key().derive_address()],
+        };
+
+        Ok(addresses)
+    }
+}
+
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 mod type_digest {
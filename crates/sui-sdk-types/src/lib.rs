@@ -100,10 +100,14 @@ mod checkpoint;
 mod crypto;
 mod digest;
 mod effects;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod event_registry;
 mod events;
 mod execution_status;
 pub mod framework;
 mod gas;
+mod move_value;
 mod object;
 mod object_id;
 mod transaction;
@@ -140,6 +144,7 @@ pub use crypto::Intent;
 pub use crypto::IntentAppId;
 pub use crypto::IntentScope;
 pub use crypto::IntentVersion;
+pub use crypto::InvalidZkLoginClaimError;
 pub use crypto::Jwk;
 pub use crypto::JwkId;
 pub use crypto::MultisigAggregatedSignature;
@@ -187,6 +192,18 @@ pub use effects::TransactionEffectsV1;
 pub use effects::TransactionEffectsV2;
 pub use effects::UnchangedSharedKind;
 pub use effects::UnchangedSharedObject;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use event_registry::DecodedEvent;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use event_registry::EventDecodeError;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use event_registry::EventRegistry;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use event_registry::TypedEvent;
 pub use events::BalanceChange;
 pub use events::Event;
 pub use events::TransactionEvents;
@@ -198,6 +215,16 @@ pub use execution_status::PackageUpgradeError;
 pub use execution_status::TypeArgumentError;
 pub use gas::GasCostSummary;
 pub use gas::GasCostSummaryAdjusted;
+pub use move_value::MoveEnumLayout;
+pub use move_value::MoveFieldLayout;
+pub use move_value::MoveInteger;
+pub use move_value::MoveStructLayout;
+pub use move_value::MoveStructValue;
+pub use move_value::MoveTypeLayout;
+pub use move_value::MoveValue;
+pub use move_value::MoveValueDecodeError;
+pub use move_value::MoveVariantLayout;
+pub use move_value::MoveVariantValue;
 pub use object::GenesisObject;
 pub use object::MovePackage;
 pub use object::MoveStruct;
@@ -234,6 +261,7 @@ pub use transaction::MakeMoveVector;
 pub use transaction::MergeCoins;
 pub use transaction::MoveCall;
 pub use transaction::ProgrammableTransaction;
+pub use transaction::ProgrammableTransactionDisplay;
 pub use transaction::Publish;
 pub use transaction::RandomnessStateUpdate;
 pub use transaction::SignedTransaction;
@@ -248,9 +276,16 @@ pub use transaction::ValidatorExecutionTimeObservation;
 pub use transaction::VersionAssignment;
 pub use transaction::VersionAssignmentV2;
 pub use type_tag::Identifier;
+pub use type_tag::MoveType;
+pub use type_tag::OpenStructTag;
+pub use type_tag::OpenTypeTag;
 pub use type_tag::StructTag;
 pub use type_tag::TypeParseError;
+pub use type_tag::TypePattern;
 pub use type_tag::TypeTag;
+pub use u256::U256OverflowError;
+pub use u256::U256ParseError;
+pub use u256::U256;
 
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
@@ -0,0 +1,831 @@
+//! Layout driven decoding of BCS encoded Move values.
+//!
+//! The BCS encoding of a Move value isn't self-describing, so decoding the contents of an object
+//! or event requires knowing the layout of its type: the types of every field, recursively, with
+//! all type parameters instantiated.
+
+use super::Address;
+use super::Identifier;
+use super::StructTag;
+use super::TypeTag;
+use super::U256;
+
+/// The fully instantiated layout of a Move type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MoveTypeLayout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<MoveTypeLayout>),
+    Struct(Box<MoveStructLayout>),
+    Enum(Box<MoveEnumLayout>),
+}
+
+/// The layout of a Move struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveStructLayout {
+    /// The fully instantiated type of the struct.
+    pub type_: StructTag,
+    /// The fields of the struct in declaration order.
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+/// The layout of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveEnumLayout {
+    /// The fully instantiated type of the enum.
+    pub type_: StructTag,
+    /// The variants of the enum, ordered by their tag.
+    pub variants: Vec<MoveVariantLayout>,
+}
+
+/// The layout of a single variant of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveVariantLayout {
+    pub name: Identifier,
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+/// The layout of a field of a Move struct or enum variant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveFieldLayout {
+    pub name: Identifier,
+    pub layout: MoveTypeLayout,
+}
+
+/// A decoded Move value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MoveValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    Address(Address),
+    Signer(Address),
+    Vector(Vec<MoveValue>),
+    Struct(MoveStructValue),
+    Variant(MoveVariantValue),
+}
+
+/// A decoded Move struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveStructValue {
+    pub type_: StructTag,
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
+/// A decoded variant of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveVariantValue {
+    pub type_: StructTag,
+    pub variant_name: Identifier,
+    pub tag: u16,
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
+/// Error returned when BCS bytes could not be decoded using a [`MoveTypeLayout`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveValueDecodeError {
+    /// The input ended before the value was fully decoded.
+    UnexpectedEnd,
+    /// The value was decoded but the input contained this many extra bytes.
+    TrailingBytes(usize),
+    /// A `bool` was encoded as something other than `0` or `1`.
+    InvalidBool(u8),
+    /// A length or enum tag was not a valid ULEB128 encoded `u32`.
+    InvalidUleb128,
+    /// An enum tag did not correspond to one of the enum's variants.
+    UnknownVariant { type_: StructTag, tag: u32 },
+}
+
+impl std::fmt::Display for MoveValueDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::TrailingBytes(n) => write!(f, "{n} trailing bytes after value"),
+            Self::InvalidBool(b) => write!(f, "invalid bool {b}"),
+            Self::InvalidUleb128 => write!(f, "invalid uleb128 encoding"),
+            Self::UnknownVariant { type_, tag } => write!(f, "unknown variant {tag} for {type_}"),
+        }
+    }
+}
+
+impl std::error::Error for MoveValueDecodeError {}
+
+impl MoveTypeLayout {
+    /// Decode the BCS encoded `bytes` of a value with this layout.
+    pub fn decode(&self, bytes: &[u8]) -> Result<MoveValue, MoveValueDecodeError> {
+        let mut reader = Reader(bytes);
+        let value = reader.read_value(self)?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+
+impl MoveStructLayout {
+    /// Decode the BCS encoded `bytes` of a struct with this layout, e.g. the contents of a
+    /// [`MoveStruct`](crate::MoveStruct) object or an [`Event`](crate::Event).
+    pub fn decode(&self, bytes: &[u8]) -> Result<MoveStructValue, MoveValueDecodeError> {
+        let mut reader = Reader(bytes);
+        let value = reader.read_struct(self)?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+
+impl MoveStructValue {
+    /// Lookup the value of a field by name.
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.as_str() == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl MoveVariantValue {
+    /// Lookup the value of a field by name.
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.as_str() == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl MoveValue {
+    /// Returns the value if it is an integer.
+    pub fn as_integer(&self) -> Option<MoveInteger> {
+        let integer = match self {
+            MoveValue::U8(n) => MoveInteger::U8(*n),
+            MoveValue::U16(n) => MoveInteger::U16(*n),
+            MoveValue::U32(n) => MoveInteger::U32(*n),
+            MoveValue::U64(n) => MoveInteger::U64(*n),
+            MoveValue::U128(n) => MoveInteger::U128(*n),
+            MoveValue::U256(n) => MoveInteger::U256(*n),
+            _ => return None,
+        };
+
+        Some(integer)
+    }
+}
+
+/// A value of one of the Move integer types.
+///
+/// Arithmetic is only defined between integers of the same type, mirroring Move, and returns
+/// `None` if the operands' types differ or the result doesn't fit in that type.
+///
+/// ```
+/// use sui_sdk_types::MoveInteger;
+/// use sui_sdk_types::TypeTag;
+///
+/// let balance = MoveInteger::U64(u64::MAX - 1);
+/// assert_eq!(
+///     balance.checked_add(MoveInteger::U64(1)),
+///     Some(MoveInteger::U64(u64::MAX))
+/// );
+/// assert_eq!(balance.checked_add(MoveInteger::U64(2)), None);
+/// assert_eq!(balance.checked_add(MoveInteger::U8(1)), None);
+///
+/// let parsed = MoveInteger::parse(&TypeTag::U8, "255").unwrap();
+/// assert_eq!(parsed, MoveInteger::U8(255));
+/// assert_eq!(parsed.to_le_bytes(), vec![255]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MoveInteger {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+}
+
+macro_rules! move_integer_op {
+    ($method:ident) => {
+        pub fn $method(self, rhs: Self) -> Option<Self> {
+            match (self, rhs) {
+                (Self::U8(a), Self::U8(b)) => a.$method(b).map(Self::U8),
+                (Self::U16(a), Self::U16(b)) => a.$method(b).map(Self::U16),
+                (Self::U32(a), Self::U32(b)) => a.$method(b).map(Self::U32),
+                (Self::U64(a), Self::U64(b)) => a.$method(b).map(Self::U64),
+                (Self::U128(a), Self::U128(b)) => a.$method(b).map(Self::U128),
+                (Self::U256(a), Self::U256(b)) => a.$method(b).map(Self::U256),
+                _ => None,
+            }
+        }
+    };
+}
+
+impl MoveInteger {
+    /// The Move type of this integer.
+    pub fn type_tag(&self) -> TypeTag {
+        match self {
+            Self::U8(_) => TypeTag::U8,
+            Self::U16(_) => TypeTag::U16,
+            Self::U32(_) => TypeTag::U32,
+            Self::U64(_) => TypeTag::U64,
+            Self::U128(_) => TypeTag::U128,
+            Self::U256(_) => TypeTag::U256,
+        }
+    }
+
+    /// Convert `value` to an integer of type `type_tag`, returning `None` if `type_tag` isn't an
+    /// integer type or `value` doesn't fit in it.
+    pub fn from_u256(type_tag: &TypeTag, value: U256) -> Option<Self> {
+        let integer = match type_tag {
+            TypeTag::U8 => Self::U8(value.try_into().ok()?),
+            TypeTag::U16 => Self::U16(value.try_into().ok()?),
+            TypeTag::U32 => Self::U32(value.try_into().ok()?),
+            TypeTag::U64 => Self::U64(value.try_into().ok()?),
+            TypeTag::U128 => Self::U128(value.try_into().ok()?),
+            TypeTag::U256 => Self::U256(value),
+            _ => return None,
+        };
+
+        Some(integer)
+    }
+
+    /// Parse a decimal, or `0x` prefixed hex, string as an integer of type `type_tag`.
+    pub fn parse(type_tag: &TypeTag, s: &str) -> Option<Self> {
+        Self::from_u256(type_tag, s.parse().ok()?)
+    }
+
+    /// Widen this integer to a `u256`.
+    pub fn to_u256(&self) -> U256 {
+        match *self {
+            Self::U8(n) => n.into(),
+            Self::U16(n) => n.into(),
+            Self::U32(n) => n.into(),
+            Self::U64(n) => n.into(),
+            Self::U128(n) => n.into(),
+            Self::U256(n) => n,
+        }
+    }
+
+    /// The little-endian bytes of this integer, which is also its BCS serialized form, e.g. for
+    /// use as a pure transaction input.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::U8(n) => n.to_le_bytes().to_vec(),
+            Self::U16(n) => n.to_le_bytes().to_vec(),
+            Self::U32(n) => n.to_le_bytes().to_vec(),
+            Self::U64(n) => n.to_le_bytes().to_vec(),
+            Self::U128(n) => n.to_le_bytes().to_vec(),
+            Self::U256(n) => n.to_le_bytes().to_vec(),
+        }
+    }
+
+    move_integer_op!(checked_add);
+    move_integer_op!(checked_sub);
+    move_integer_op!(checked_mul);
+    move_integer_op!(checked_div);
+    move_integer_op!(checked_rem);
+}
+
+macro_rules! impl_from_integer {
+    ($($variant:ident($integer:ty)),*) => {
+        $(
+            impl From<$integer> for MoveInteger {
+                fn from(value: $integer) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(U8(u8), U16(u16), U32(u32), U64(u64), U128(u128), U256(U256));
+
+impl From<MoveInteger> for MoveValue {
+    fn from(value: MoveInteger) -> Self {
+        match value {
+            MoveInteger::U8(n) => Self::U8(n),
+            MoveInteger::U16(n) => Self::U16(n),
+            MoveInteger::U32(n) => Self::U32(n),
+            MoveInteger::U64(n) => Self::U64(n),
+            MoveInteger::U128(n) => Self::U128(n),
+            MoveInteger::U256(n) => Self::U256(n),
+        }
+    }
+}
+
+impl std::fmt::Display for MoveInteger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::U8(n) => n.fmt(f),
+            Self::U16(n) => n.fmt(f),
+            Self::U32(n) => n.fmt(f),
+            Self::U64(n) => n.fmt(f),
+            Self::U128(n) => n.fmt(f),
+            Self::U256(n) => n.fmt(f),
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn finish(&self) -> Result<(), MoveValueDecodeError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(MoveValueDecodeError::TrailingBytes(self.0.len()))
+        }
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], MoveValueDecodeError> {
+        if self.0.len() < N {
+            return Err(MoveValueDecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn read_uleb128(&mut self) -> Result<u32, MoveValueDecodeError> {
+        let mut value: u64 = 0;
+        for shift in (0..32).step_by(7) {
+            let [byte] = self.read_bytes::<1>()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                // Reject non-canonical encodings with trailing zero bytes
+                if shift > 0 && byte == 0 {
+                    return Err(MoveValueDecodeError::InvalidUleb128);
+                }
+                return u32::try_from(value).map_err(|_| MoveValueDecodeError::InvalidUleb128);
+            }
+        }
+        Err(MoveValueDecodeError::InvalidUleb128)
+    }
+
+    fn read_value(&mut self, layout: &MoveTypeLayout) -> Result<MoveValue, MoveValueDecodeError> {
+        let value = match layout {
+            MoveTypeLayout::Bool => match self.read_bytes::<1>()? {
+                [0] => MoveValue::Bool(false),
+                [1] => MoveValue::Bool(true),
+                [b] => return Err(MoveValueDecodeError::InvalidBool(b)),
+            },
+            MoveTypeLayout::U8 => MoveValue::U8(u8::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::U16 => MoveValue::U16(u16::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::U32 => MoveValue::U32(u32::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::U64 => MoveValue::U64(u64::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::U128 => MoveValue::U128(u128::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::U256 => MoveValue::U256(U256::from_le_bytes(self.read_bytes()?)),
+            MoveTypeLayout::Address => MoveValue::Address(Address::new(self.read_bytes()?)),
+            MoveTypeLayout::Signer => MoveValue::Signer(Address::new(self.read_bytes()?)),
+            MoveTypeLayout::Vector(element) => {
+                let len = self.read_uleb128()? as usize;
+                // Every element takes up at least one byte, which bounds the allocation below by
+                // the size of the input
+                let mut elements = Vec::with_capacity(len.min(self.0.len()));
+                for _ in 0..len {
+                    elements.push(self.read_value(element)?);
+                }
+                MoveValue::Vector(elements)
+            }
+            MoveTypeLayout::Struct(layout) => MoveValue::Struct(self.read_struct(layout)?),
+            MoveTypeLayout::Enum(layout) => {
+                let tag = self.read_uleb128()?;
+                let variant = layout.variants.get(tag as usize).ok_or_else(|| {
+                    MoveValueDecodeError::UnknownVariant {
+                        type_: layout.type_.clone(),
+                        tag,
+                    }
+                })?;
+
+                MoveValue::Variant(MoveVariantValue {
+                    type_: layout.type_.clone(),
+                    variant_name: variant.name.clone(),
+                    tag: tag as u16,
+                    fields: self.read_fields(&variant.fields)?,
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn read_struct(
+        &mut self,
+        layout: &MoveStructLayout,
+    ) -> Result<MoveStructValue, MoveValueDecodeError> {
+        Ok(MoveStructValue {
+            type_: layout.type_.clone(),
+            fields: self.read_fields(&layout.fields)?,
+        })
+    }
+
+    fn read_fields(
+        &mut self,
+        fields: &[MoveFieldLayout],
+    ) -> Result<Vec<(Identifier, MoveValue)>, MoveValueDecodeError> {
+        fields
+            .iter()
+            .map(|field| Ok((field.name.clone(), self.read_value(&field.layout)?)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod json {
+    use super::*;
+    use serde_json::Value;
+
+    impl MoveValue {
+        /// Render this value as JSON in the same format used by fullnodes and the GraphQL
+        /// service.
+        ///
+        /// - `u8`, `u16` and `u32` are rendered as numbers while `u64`, `u128` and `u256` are
+        ///   rendered as decimal strings.
+        /// - Addresses are rendered as `0x` prefixed hex strings.
+        /// - Structs are rendered as objects and enum variants are rendered as objects with an
+        ///   additional `@variant` field containing the name of the variant.
+        /// - `0x1::string::String`, `0x1::ascii::String`, `0x2::url::Url`, `0x2::object::UID`,
+        ///   `0x2::object::ID`, `0x2::balance::Balance` and `0x1::option::Option` are rendered as
+        ///   their underlying values.
+        pub fn to_json(&self) -> Value {
+            match self {
+                MoveValue::Bool(b) => Value::Bool(*b),
+                MoveValue::U8(n) => Value::from(*n),
+                MoveValue::U16(n) => Value::from(*n),
+                MoveValue::U32(n) => Value::from(*n),
+                MoveValue::U64(n) => Value::String(n.to_string()),
+                MoveValue::U128(n) => Value::String(n.to_string()),
+                MoveValue::U256(n) => Value::String(n.to_string()),
+                MoveValue::Address(address) | MoveValue::Signer(address) => {
+                    Value::String(address.to_string())
+                }
+                MoveValue::Vector(elements) => {
+                    Value::Array(elements.iter().map(MoveValue::to_json).collect())
+                }
+                MoveValue::Struct(s) => s.to_json(),
+                MoveValue::Variant(v) => {
+                    let mut map = serde_json::Map::new();
+                    map.insert(
+                        "@variant".to_owned(),
+                        Value::String(v.variant_name.as_str().to_owned()),
+                    );
+                    for (name, value) in &v.fields {
+                        map.insert(name.as_str().to_owned(), value.to_json());
+                    }
+                    Value::Object(map)
+                }
+            }
+        }
+    }
+
+    impl MoveStructValue {
+        /// Render this struct as JSON, see [`MoveValue::to_json`].
+        pub fn to_json(&self) -> Value {
+            if let Some(value) = self.to_json_special() {
+                return value;
+            }
+
+            Value::Object(
+                self.fields
+                    .iter()
+                    .map(|(name, value)| (name.as_str().to_owned(), value.to_json()))
+                    .collect(),
+            )
+        }
+
+        fn to_json_special(&self) -> Option<Value> {
+            let StructTag {
+                address,
+                module,
+                name,
+                ..
+            } = &self.type_;
+            let single_field = match self.fields.as_slice() {
+                [(_, value)] => value,
+                _ => return None,
+            };
+
+            match (address, module.as_str(), name.as_str()) {
+                (&Address::ONE, "string", "String") | (&Address::ONE, "ascii", "String") => {
+                    let MoveValue::Vector(bytes) = single_field else {
+                        return None;
+                    };
+                    let bytes = bytes
+                        .iter()
+                        .map(|b| match b {
+                            MoveValue::U8(b) => Some(*b),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<u8>>>()?;
+                    String::from_utf8(bytes).ok().map(Value::String)
+                }
+                (&Address::ONE, "option", "Option") => match single_field {
+                    MoveValue::Vector(elements) => match elements.as_slice() {
+                        [] => Some(Value::Null),
+                        [element] => Some(element.to_json()),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                (&Address::TWO, "object", "UID")
+                | (&Address::TWO, "object", "ID")
+                | (&Address::TWO, "balance", "Balance")
+                | (&Address::TWO, "url", "Url") => Some(single_field.to_json()),
+                _ => None,
+            }
+        }
+    }
+
+    impl serde::Serialize for MoveValue {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.to_json().serialize(serializer)
+        }
+    }
+
+    impl serde::Serialize for MoveStructValue {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.to_json().serialize(serializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn field(name: &str, layout: MoveTypeLayout) -> MoveFieldLayout {
+        MoveFieldLayout {
+            name: Identifier::new(name).unwrap(),
+            layout,
+        }
+    }
+
+    fn struct_layout(type_: &str, fields: Vec<MoveFieldLayout>) -> MoveTypeLayout {
+        MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+            type_: type_.parse().unwrap(),
+            fields,
+        }))
+    }
+
+    fn string_layout() -> MoveTypeLayout {
+        struct_layout(
+            "0x1::string::String",
+            vec![field(
+                "bytes",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            )],
+        )
+    }
+
+    fn uid_layout() -> MoveTypeLayout {
+        struct_layout(
+            "0x2::object::UID",
+            vec![field(
+                "id",
+                struct_layout(
+                    "0x2::object::ID",
+                    vec![field("bytes", MoveTypeLayout::Address)],
+                ),
+            )],
+        )
+    }
+
+    fn option_layout(inner: MoveTypeLayout) -> MoveTypeLayout {
+        struct_layout(
+            "0x1::option::Option<u64>",
+            vec![field("vec", MoveTypeLayout::Vector(Box::new(inner)))],
+        )
+    }
+
+    #[test]
+    fn decode_primitives() {
+        assert_eq!(
+            MoveTypeLayout::Bool.decode(&[1]).unwrap(),
+            MoveValue::Bool(true)
+        );
+        assert_eq!(
+            MoveTypeLayout::Bool.decode(&[2]).unwrap_err(),
+            MoveValueDecodeError::InvalidBool(2)
+        );
+        assert_eq!(
+            MoveTypeLayout::U16.decode(&[1, 2]).unwrap(),
+            MoveValue::U16(0x0201)
+        );
+        assert_eq!(
+            MoveTypeLayout::U64.decode(&[1, 2]).unwrap_err(),
+            MoveValueDecodeError::UnexpectedEnd
+        );
+        assert_eq!(
+            MoveTypeLayout::U8.decode(&[1, 2]).unwrap_err(),
+            MoveValueDecodeError::TrailingBytes(1)
+        );
+
+        let vector = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U32));
+        let bytes = bcs::to_bytes(&vec![1u32, 2, 3]).unwrap();
+        assert_eq!(
+            vector.decode(&bytes).unwrap(),
+            MoveValue::Vector(vec![
+                MoveValue::U32(1),
+                MoveValue::U32(2),
+                MoveValue::U32(3)
+            ])
+        );
+
+        // A huge length with no data shouldn't allocate or succeed
+        assert_eq!(
+            vector.decode(&[0xff, 0xff, 0xff, 0xff, 0x0f]).unwrap_err(),
+            MoveValueDecodeError::UnexpectedEnd
+        );
+    }
+
+    #[test]
+    fn decode_enum() {
+        let layout = MoveTypeLayout::Enum(Box::new(MoveEnumLayout {
+            type_: "0x1::m::E".parse().unwrap(),
+            variants: vec![
+                MoveVariantLayout {
+                    name: Identifier::new("A").unwrap(),
+                    fields: vec![],
+                },
+                MoveVariantLayout {
+                    name: Identifier::new("B").unwrap(),
+                    fields: vec![field("x", MoveTypeLayout::U8)],
+                },
+            ],
+        }));
+
+        let MoveValue::Variant(variant) = layout.decode(&[1, 7]).unwrap() else {
+            panic!("expected variant");
+        };
+        assert_eq!(variant.variant_name.as_str(), "B");
+        assert_eq!(variant.tag, 1);
+        assert_eq!(variant.field("x"), Some(&MoveValue::U8(7)));
+
+        assert!(matches!(
+            layout.decode(&[2]).unwrap_err(),
+            MoveValueDecodeError::UnknownVariant { tag: 2, .. }
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let layout = MoveStructLayout {
+            type_: "0x2::m::S".parse().unwrap(),
+            fields: vec![
+                field("id", uid_layout()),
+                field("name", string_layout()),
+                field("small", MoveTypeLayout::U32),
+                field("big", MoveTypeLayout::U64),
+                field("huge", MoveTypeLayout::U256),
+                field("none", option_layout(MoveTypeLayout::U64)),
+                field("some", option_layout(MoveTypeLayout::U64)),
+                field(
+                    "flags",
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Bool)),
+                ),
+            ],
+        };
+
+        let mut huge = [0u8; 32];
+        huge[0] = 1;
+        huge[1] = 1;
+
+        let bytes = bcs::to_bytes(&(
+            Address::TWO,
+            "hello",
+            5u32,
+            u64::MAX,
+            huge,
+            Option::<u64>::None,
+            Some(9u64),
+            vec![true, false],
+        ))
+        .unwrap();
+
+        let value = layout.decode(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            serde_json::json!({
+                "id": Address::TWO.to_string(),
+                "name": "hello",
+                "small": 5,
+                "big": u64::MAX.to_string(),
+                "huge": "257",
+                "none": null,
+                "some": "9",
+                "flags": [true, false],
+            })
+        );
+    }
+
+    #[test]
+    fn move_integer_conversions() {
+        let max_u64 = U256::from(u64::MAX);
+        assert_eq!(
+            MoveInteger::from_u256(&TypeTag::U64, max_u64),
+            Some(MoveInteger::U64(u64::MAX))
+        );
+        assert_eq!(
+            MoveInteger::from_u256(&TypeTag::U64, max_u64 + U256::ONE),
+            None
+        );
+        assert_eq!(
+            MoveInteger::from_u256(&TypeTag::U8, U256::from(256u64)),
+            None
+        );
+        assert_eq!(
+            MoveInteger::from_u256(&TypeTag::U256, U256::MAX),
+            Some(MoveInteger::U256(U256::MAX))
+        );
+        assert_eq!(MoveInteger::from_u256(&TypeTag::Bool, U256::ONE), None);
+        assert_eq!(MoveInteger::from_u256(&TypeTag::Address, U256::ONE), None);
+
+        assert_eq!(
+            MoveInteger::parse(&TypeTag::U16, "0xffff"),
+            Some(MoveInteger::U16(u16::MAX))
+        );
+        assert_eq!(MoveInteger::parse(&TypeTag::U16, "65536"), None);
+        assert_eq!(MoveInteger::parse(&TypeTag::U32, "-1"), None);
+        assert_eq!(MoveInteger::parse(&TypeTag::U32, ""), None);
+
+        for integer in [
+            MoveInteger::U8(u8::MAX),
+            MoveInteger::U16(0),
+            MoveInteger::U32(u32::MAX),
+            MoveInteger::U64(1),
+            MoveInteger::U128(u128::MAX),
+            MoveInteger::U256(U256::MAX),
+        ] {
+            let value = MoveValue::from(integer);
+            assert_eq!(value.as_integer(), Some(integer));
+            assert_eq!(
+                MoveInteger::from_u256(&integer.type_tag(), integer.to_u256()),
+                Some(integer)
+            );
+            assert_eq!(integer.to_string(), integer.to_u256().to_string());
+        }
+        assert_eq!(MoveValue::Bool(true).as_integer(), None);
+
+        assert_eq!(MoveInteger::U16(0x0102).to_le_bytes(), [2, 1]);
+        assert_eq!(
+            MoveInteger::U256(U256::ONE).to_le_bytes(),
+            bcs::to_bytes(&U256::ONE).unwrap()
+        );
+    }
+
+    #[test]
+    fn move_integer_arithmetic() {
+        assert_eq!(
+            MoveInteger::U8(200).checked_add(MoveInteger::U8(55)),
+            Some(MoveInteger::U8(255))
+        );
+        assert_eq!(MoveInteger::U8(200).checked_add(MoveInteger::U8(56)), None);
+        assert_eq!(MoveInteger::U32(0).checked_sub(MoveInteger::U32(1)), None);
+        assert_eq!(
+            MoveInteger::U64(u64::MAX).checked_mul(MoveInteger::U64(1)),
+            Some(MoveInteger::U64(u64::MAX))
+        );
+        assert_eq!(
+            MoveInteger::U64(u64::MAX).checked_mul(MoveInteger::U64(2)),
+            None
+        );
+        assert_eq!(
+            MoveInteger::U128(7).checked_div(MoveInteger::U128(2)),
+            Some(MoveInteger::U128(3))
+        );
+        assert_eq!(MoveInteger::U128(7).checked_div(MoveInteger::U128(0)), None);
+        assert_eq!(
+            MoveInteger::U16(7).checked_rem(MoveInteger::U16(4)),
+            Some(MoveInteger::U16(3))
+        );
+        assert_eq!(MoveInteger::U16(7).checked_rem(MoveInteger::U16(0)), None);
+        assert_eq!(
+            MoveInteger::U256(U256::MAX).checked_add(MoveInteger::U256(U256::ONE)),
+            None
+        );
+
+        // Operands of different types never combine, even if the result would fit in both
+        assert_eq!(MoveInteger::U64(1).checked_add(MoveInteger::U128(1)), None);
+        assert_eq!(
+            MoveInteger::U8(1).checked_sub(MoveInteger::U256(U256::ZERO)),
+            None
+        );
+    }
+}
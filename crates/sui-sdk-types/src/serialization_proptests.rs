@@ -152,3 +152,4 @@ serialization_test!(Upgrade);
 serialization_test!(Identifier);
 serialization_test!(StructTag);
 serialization_test!(TypeTag);
+serialization_test!(U256);
@@ -0,0 +1,710 @@
+use std::collections::BTreeMap;
+
+use super::Argument;
+use super::Command;
+use super::Input;
+use super::ProgrammableTransaction;
+use crate::Address;
+use crate::MoveTypeLayout;
+use crate::MoveValue;
+use crate::TypeTag;
+
+/// Human readable rendering of a [`ProgrammableTransaction`], created with
+/// [`ProgrammableTransaction::display`]
+///
+/// Arguments are rendered in place: pure inputs whose type is known are rendered as their decoded
+/// value, object inputs as their object id, and the results of other commands as `Result(i)` or
+/// `NestedResult(i, j)`.
+///
+/// The types of pure inputs are inferred from how they are used, e.g. the amounts of a
+/// `SplitCoins` are `u64`s and the recipient of a `TransferObjects` is an `address`. Types which
+/// can't be inferred, like those of the arguments to a Move call, can be provided with
+/// [`ProgrammableTransactionDisplay::with_input_layout`]. Pure inputs of unknown type are
+/// rendered as `Input(i)` and listed as raw bytes.
+///
+/// The default format lists every input followed by every command, one per line, while the
+/// alternate format (`{:#}`) renders only the commands on a single line:
+///
+/// ```
+/// use sui_sdk_types::Address;
+/// use sui_sdk_types::Argument;
+/// use sui_sdk_types::Command;
+/// use sui_sdk_types::Input;
+/// use sui_sdk_types::ProgrammableTransaction;
+/// use sui_sdk_types::SplitCoins;
+/// use sui_sdk_types::TransferObjects;
+///
+/// let ptb = ProgrammableTransaction {
+///     inputs: vec![
+///         Input::Pure {
+///             value: 1_000_000_000u64.to_le_bytes().to_vec(),
+///         },
+///         Input::Pure {
+///             value: Address::TWO.as_bytes().to_vec(),
+///         },
+///     ],
+///     commands: vec![
+///         Command::SplitCoins(SplitCoins {
+///             coin: Argument::Gas,
+///             amounts: vec![Argument::Input(0)],
+///         }),
+///         Command::TransferObjects(TransferObjects {
+///             objects: vec![Argument::Result(0)],
+///             address: Argument::Input(1),
+///         }),
+///     ],
+/// };
+///
+/// assert_eq!(
+///     format!("{:#}", ptb.display()),
+///     format!(
+///         "SplitCoins(Gas, [1000000000]) → TransferObjects([Result(0)], {})",
+///         Address::TWO
+///     ),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProgrammableTransactionDisplay<'a> {
+    ptb: &'a ProgrammableTransaction,
+    layouts: BTreeMap<u16, MoveTypeLayout>,
+}
+
+impl ProgrammableTransaction {
+    /// Render this transaction for humans, see [`ProgrammableTransactionDisplay`].
+    pub fn display(&self) -> ProgrammableTransactionDisplay<'_> {
+        ProgrammableTransactionDisplay {
+            ptb: self,
+            layouts: infer_input_layouts(self),
+        }
+    }
+}
+
+impl<'a> ProgrammableTransactionDisplay<'a> {
+    /// Decode the pure input at `index` using `layout`, e.g. the type of the corresponding
+    /// parameter of the function it is passed to, overriding any inferred type.
+    pub fn with_input_layout(mut self, index: u16, layout: MoveTypeLayout) -> Self {
+        self.layouts.insert(index, layout);
+        self
+    }
+
+    /// The decoded value of the pure input at `index`, if its type is known and its bytes are a
+    /// valid value of that type.
+    pub fn pure_value(&self, index: u16) -> Option<(&MoveTypeLayout, MoveValue)> {
+        let Some(Input::Pure { value }) = self.ptb.inputs.get(index as usize) else {
+            return None;
+        };
+        let layout = self.layouts.get(&index)?;
+        layout.decode(value).ok().map(|decoded| (layout, decoded))
+    }
+
+    fn fmt_input(&self, f: &mut std::fmt::Formatter<'_>, index: u16) -> std::fmt::Result {
+        match &self.ptb.inputs[index as usize] {
+            Input::Pure { value } => match self.pure_value(index) {
+                Some((layout, decoded)) => {
+                    write!(f, "Pure {}: ", LayoutDisplay(layout))?;
+                    fmt_value(f, &decoded)
+                }
+                None => write!(f, "Pure 0x{} ({} bytes)", hex::encode(value), value.len()),
+            },
+            Input::ImmutableOrOwned(object) => write!(
+                f,
+                "ImmutableOrOwned {} (version {})",
+                object.object_id(),
+                object.version()
+            ),
+            Input::Shared {
+                object_id,
+                initial_shared_version,
+                mutable,
+            } => write!(
+                f,
+                "Shared {object_id} (initial shared version {initial_shared_version}, {})",
+                if *mutable { "mutable" } else { "immutable" }
+            ),
+            Input::Receiving(object) => write!(
+                f,
+                "Receiving {} (version {})",
+                object.object_id(),
+                object.version()
+            ),
+        }
+    }
+
+    fn fmt_argument(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        argument: &Argument,
+    ) -> std::fmt::Result {
+        match argument {
+            Argument::Gas => f.write_str("Gas"),
+            Argument::Input(index) => match self.ptb.inputs.get(*index as usize) {
+                Some(Input::Pure { .. }) => match self.pure_value(*index) {
+                    Some((_, value)) => fmt_value(f, &value),
+                    None => write!(f, "Input({index})"),
+                },
+                Some(Input::ImmutableOrOwned(object) | Input::Receiving(object)) => {
+                    write!(f, "{}", object.object_id())
+                }
+                Some(Input::Shared { object_id, .. }) => write!(f, "{object_id}"),
+                None => write!(f, "Input({index})"),
+            },
+            Argument::Result(index) => write!(f, "Result({index})"),
+            Argument::NestedResult(index, nested) => write!(f, "NestedResult({index}, {nested})"),
+        }
+    }
+
+    fn fmt_arguments(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        arguments: &[Argument],
+    ) -> std::fmt::Result {
+        for (i, argument) in arguments.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            self.fmt_argument(f, argument)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_command(&self, f: &mut std::fmt::Formatter<'_>, command: &Command) -> std::fmt::Result {
+        match command {
+            Command::MoveCall(call) => {
+                let package = Address::from(call.package).to_short_string();
+                write!(f, "{package}::{}::{}", call.module, call.function)?;
+                if !call.type_arguments.is_empty() {
+                    f.write_str("<")?;
+                    for (i, type_argument) in call.type_arguments.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        write!(f, "{type_argument:#}")?;
+                    }
+                    f.write_str(">")?;
+                }
+                f.write_str("(")?;
+                self.fmt_arguments(f, &call.arguments)?;
+                f.write_str(")")
+            }
+            Command::TransferObjects(transfer) => {
+                f.write_str("TransferObjects([")?;
+                self.fmt_arguments(f, &transfer.objects)?;
+                f.write_str("], ")?;
+                self.fmt_argument(f, &transfer.address)?;
+                f.write_str(")")
+            }
+            Command::SplitCoins(split) => {
+                f.write_str("SplitCoins(")?;
+                self.fmt_argument(f, &split.coin)?;
+                f.write_str(", [")?;
+                self.fmt_arguments(f, &split.amounts)?;
+                f.write_str("])")
+            }
+            Command::MergeCoins(merge) => {
+                f.write_str("MergeCoins(")?;
+                self.fmt_argument(f, &merge.coin)?;
+                f.write_str(", [")?;
+                self.fmt_arguments(f, &merge.coins_to_merge)?;
+                f.write_str("])")
+            }
+            Command::Publish(publish) => {
+                write!(
+                    f,
+                    "Publish({} modules, dependencies [",
+                    publish.modules.len()
+                )?;
+                fmt_dependencies(f, &publish.dependencies)?;
+                f.write_str("])")
+            }
+            Command::MakeMoveVector(make) => {
+                f.write_str("MakeMoveVec")?;
+                if let Some(type_) = &make.type_ {
+                    write!(f, "<{type_:#}>")?;
+                }
+                f.write_str("([")?;
+                self.fmt_arguments(f, &make.elements)?;
+                f.write_str("])")
+            }
+            Command::Upgrade(upgrade) => {
+                write!(
+                    f,
+                    "Upgrade({}, {} modules, dependencies [",
+                    upgrade.package,
+                    upgrade.modules.len()
+                )?;
+                fmt_dependencies(f, &upgrade.dependencies)?;
+                f.write_str("], ")?;
+                self.fmt_argument(f, &upgrade.ticket)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ProgrammableTransactionDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            for (i, command) in self.ptb.commands.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" → ")?;
+                }
+                self.fmt_command(f, command)?;
+            }
+            return Ok(());
+        }
+
+        writeln!(f, "Inputs:")?;
+        for index in 0..self.ptb.inputs.len() {
+            write!(f, "  Input({index}): ")?;
+            self.fmt_input(f, index as u16)?;
+            writeln!(f)?;
+        }
+
+        writeln!(f, "Commands:")?;
+        for (index, command) in self.ptb.commands.iter().enumerate() {
+            write!(f, "  Result({index}): ")?;
+            self.fmt_command(f, command)?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Infer the types of pure inputs from the commands which use them.
+fn infer_input_layouts(ptb: &ProgrammableTransaction) -> BTreeMap<u16, MoveTypeLayout> {
+    let mut layouts = BTreeMap::new();
+    let mut infer = |argument: &Argument, layout: MoveTypeLayout| {
+        if let Argument::Input(index) = argument {
+            layouts.entry(*index).or_insert(layout);
+        }
+    };
+
+    for command in &ptb.commands {
+        match command {
+            Command::SplitCoins(split) => {
+                for amount in &split.amounts {
+                    infer(amount, MoveTypeLayout::U64);
+                }
+            }
+            Command::TransferObjects(transfer) => infer(&transfer.address, MoveTypeLayout::Address),
+            Command::MakeMoveVector(make) => {
+                if let Some(layout) = make.type_.as_ref().and_then(primitive_layout) {
+                    for element in &make.elements {
+                        infer(element, layout.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    layouts
+}
+
+/// The layout of `type_tag` if it is a primitive type, or a vector of primitive types.
+fn primitive_layout(type_tag: &TypeTag) -> Option<MoveTypeLayout> {
+    let layout = match type_tag {
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Vector(element) => MoveTypeLayout::Vector(Box::new(primitive_layout(element)?)),
+        TypeTag::Signer | TypeTag::Struct(_) => return None,
+    };
+
+    Some(layout)
+}
+
+struct LayoutDisplay<'a>(&'a MoveTypeLayout);
+
+impl std::fmt::Display for LayoutDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            MoveTypeLayout::Bool => f.write_str("bool"),
+            MoveTypeLayout::U8 => f.write_str("u8"),
+            MoveTypeLayout::U16 => f.write_str("u16"),
+            MoveTypeLayout::U32 => f.write_str("u32"),
+            MoveTypeLayout::U64 => f.write_str("u64"),
+            MoveTypeLayout::U128 => f.write_str("u128"),
+            MoveTypeLayout::U256 => f.write_str("u256"),
+            MoveTypeLayout::Address => f.write_str("address"),
+            MoveTypeLayout::Signer => f.write_str("signer"),
+            MoveTypeLayout::Vector(element) => write!(f, "vector<{}>", LayoutDisplay(element)),
+            MoveTypeLayout::Struct(s) => write!(f, "{:#}", s.type_),
+            MoveTypeLayout::Enum(e) => write!(f, "{:#}", e.type_),
+        }
+    }
+}
+
+fn fmt_value(f: &mut std::fmt::Formatter<'_>, value: &MoveValue) -> std::fmt::Result {
+    match value {
+        MoveValue::Bool(b) => write!(f, "{b}"),
+        MoveValue::U8(n) => write!(f, "{n}"),
+        MoveValue::U16(n) => write!(f, "{n}"),
+        MoveValue::U32(n) => write!(f, "{n}"),
+        MoveValue::U64(n) => write!(f, "{n}"),
+        MoveValue::U128(n) => write!(f, "{n}"),
+        MoveValue::U256(n) => write!(f, "{n}"),
+        MoveValue::Address(address) | MoveValue::Signer(address) => write!(f, "{address}"),
+        MoveValue::Vector(elements) => {
+            f.write_str("[")?;
+            for (i, element) in elements.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                fmt_value(f, element)?;
+            }
+            f.write_str("]")
+        }
+        MoveValue::Struct(s) => {
+            if let Some(string) = as_string(value) {
+                return write!(f, "{string:?}");
+            }
+            write!(f, "{}", s.type_.name)?;
+            fmt_fields(f, &s.fields)
+        }
+        MoveValue::Variant(v) => {
+            write!(f, "{}::{}", v.type_.name, v.variant_name)?;
+            fmt_fields(f, &v.fields)
+        }
+    }
+}
+
+fn fmt_fields(
+    f: &mut std::fmt::Formatter<'_>,
+    fields: &[(crate::Identifier, MoveValue)],
+) -> std::fmt::Result {
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    f.write_str(" { ")?;
+    for (i, (name, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{name}: ")?;
+        fmt_value(f, value)?;
+    }
+    f.write_str(" }")
+}
+
+/// Returns the contents of a `0x1::string::String` or `0x1::ascii::String`.
+fn as_string(value: &MoveValue) -> Option<String> {
+    let MoveValue::Struct(s) = value else {
+        return None;
+    };
+    if s.type_.address != Address::ONE
+        || !matches!(s.type_.module.as_str(), "string" | "ascii")
+        || s.type_.name.as_str() != "String"
+    {
+        return None;
+    }
+
+    let [(_, MoveValue::Vector(bytes))] = s.fields.as_slice() else {
+        return None;
+    };
+    let bytes = bytes
+        .iter()
+        .map(|b| match b {
+            MoveValue::U8(b) => Some(*b),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+fn fmt_dependencies(
+    f: &mut std::fmt::Formatter<'_>,
+    dependencies: &[crate::ObjectId],
+) -> std::fmt::Result {
+    for (i, dependency) in dependencies.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        f.write_str(&Address::from(*dependency).to_short_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod json {
+    use super::*;
+    use base64ct::Encoding;
+    use serde_json::json;
+    use serde_json::Value;
+
+    impl ProgrammableTransactionDisplay<'_> {
+        /// Render the transaction as JSON, decoding pure inputs whose type is known.
+        ///
+        /// Decoded values are rendered using [`MoveValue::to_json`] while the bytes of pure
+        /// inputs of unknown type are rendered as base64. Arguments are rendered as references to
+        /// inputs and results, e.g. `{"input": 0}`, which can be resolved using the rendered
+        /// inputs.
+        pub fn to_json(&self) -> Value {
+            let inputs = (0..self.ptb.inputs.len())
+                .map(|index| self.input_to_json(index as u16))
+                .collect::<Vec<_>>();
+            let commands = self
+                .ptb
+                .commands
+                .iter()
+                .map(command_to_json)
+                .collect::<Vec<_>>();
+
+            json!({
+                "inputs": inputs,
+                "commands": commands,
+            })
+        }
+
+        fn input_to_json(&self, index: u16) -> Value {
+            match &self.ptb.inputs[index as usize] {
+                Input::Pure { value } => match self.pure_value(index) {
+                    Some((layout, decoded)) => json!({
+                        "kind": "pure",
+                        "type": LayoutDisplay(layout).to_string(),
+                        "value": decoded.to_json(),
+                    }),
+                    None => json!({
+                        "kind": "pure",
+                        "bytes": base64ct::Base64::encode_string(value),
+                    }),
+                },
+                Input::ImmutableOrOwned(object) => json!({
+                    "kind": "immutable_or_owned",
+                    "object_id": object.object_id().to_string(),
+                    "version": object.version().to_string(),
+                    "digest": object.digest().to_string(),
+                }),
+                Input::Shared {
+                    object_id,
+                    initial_shared_version,
+                    mutable,
+                } => json!({
+                    "kind": "shared",
+                    "object_id": object_id.to_string(),
+                    "initial_shared_version": initial_shared_version.to_string(),
+                    "mutable": mutable,
+                }),
+                Input::Receiving(object) => json!({
+                    "kind": "receiving",
+                    "object_id": object.object_id().to_string(),
+                    "version": object.version().to_string(),
+                    "digest": object.digest().to_string(),
+                }),
+            }
+        }
+    }
+
+    fn command_to_json(command: &Command) -> Value {
+        let arguments =
+            |arguments: &[Argument]| arguments.iter().map(argument_to_json).collect::<Vec<_>>();
+
+        match command {
+            Command::MoveCall(call) => json!({
+                "command": "MoveCall",
+                "package": call.package.to_string(),
+                "module": call.module.as_str(),
+                "function": call.function.as_str(),
+                "type_arguments": call
+                    .type_arguments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                "arguments": arguments(&call.arguments),
+            }),
+            Command::TransferObjects(transfer) => json!({
+                "command": "TransferObjects",
+                "objects": arguments(&transfer.objects),
+                "address": argument_to_json(&transfer.address),
+            }),
+            Command::SplitCoins(split) => json!({
+                "command": "SplitCoins",
+                "coin": argument_to_json(&split.coin),
+                "amounts": arguments(&split.amounts),
+            }),
+            Command::MergeCoins(merge) => json!({
+                "command": "MergeCoins",
+                "coin": argument_to_json(&merge.coin),
+                "coins_to_merge": arguments(&merge.coins_to_merge),
+            }),
+            Command::Publish(publish) => json!({
+                "command": "Publish",
+                "modules": publish.modules.len(),
+                "dependencies": publish
+                    .dependencies
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            }),
+            Command::MakeMoveVector(make) => json!({
+                "command": "MakeMoveVec",
+                "type": make.type_.as_ref().map(ToString::to_string),
+                "elements": arguments(&make.elements),
+            }),
+            Command::Upgrade(upgrade) => json!({
+                "command": "Upgrade",
+                "modules": upgrade.modules.len(),
+                "dependencies": upgrade
+                    .dependencies
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                "package": upgrade.package.to_string(),
+                "ticket": argument_to_json(&upgrade.ticket),
+            }),
+        }
+    }
+
+    fn argument_to_json(argument: &Argument) -> Value {
+        match argument {
+            Argument::Gas => json!("gas"),
+            Argument::Input(index) => json!({ "input": index }),
+            Argument::Result(index) => json!({ "result": index }),
+            Argument::NestedResult(index, nested) => json!({ "nested_result": [index, nested] }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Identifier;
+    use crate::MakeMoveVector;
+    use crate::MoveCall;
+    use crate::ObjectDigest;
+    use crate::ObjectId;
+    use crate::ObjectReference;
+    use crate::SplitCoins;
+    use crate::StructTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn ptb() -> ProgrammableTransaction {
+        let pool = ObjectId::from("0xab".parse::<Address>().unwrap());
+        ProgrammableTransaction {
+            inputs: vec![
+                Input::Pure {
+                    value: 5u64.to_le_bytes().to_vec(),
+                },
+                Input::Shared {
+                    object_id: pool,
+                    initial_shared_version: 3,
+                    mutable: true,
+                },
+                Input::ImmutableOrOwned(ObjectReference::new(
+                    ObjectId::from("0xcd".parse::<Address>().unwrap()),
+                    7,
+                    ObjectDigest::ZERO,
+                )),
+                Input::Pure {
+                    value: vec![1, 2, 3, 4],
+                },
+                Input::Pure {
+                    value: bcs::to_bytes("hello").unwrap(),
+                },
+            ],
+            commands: vec![
+                Command::SplitCoins(SplitCoins {
+                    coin: Argument::Input(2),
+                    amounts: vec![Argument::Input(0)],
+                }),
+                Command::MoveCall(MoveCall {
+                    package: ObjectId::from(Address::TWO),
+                    module: Identifier::new("pool").unwrap(),
+                    function: Identifier::new("deposit").unwrap(),
+                    type_arguments: vec![StructTag::gas_coin().into()],
+                    arguments: vec![
+                        Argument::Input(1),
+                        Argument::Result(0),
+                        Argument::Input(3),
+                        Argument::Input(4),
+                    ],
+                }),
+                Command::MakeMoveVector(MakeMoveVector {
+                    type_: Some(TypeTag::U64),
+                    elements: vec![Argument::Input(0), Argument::NestedResult(1, 0)],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn display() {
+        let ptb = ptb();
+        let pool = "0xab".parse::<Address>().unwrap();
+        let coin = "0xcd".parse::<Address>().unwrap();
+
+        let string_layout = MoveTypeLayout::Struct(Box::new(crate::MoveStructLayout {
+            type_: StructTag::string(),
+            fields: vec![crate::MoveFieldLayout {
+                name: Identifier::new("bytes").unwrap(),
+                layout: MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            }],
+        }));
+
+        assert_eq!(
+            ptb.display().with_input_layout(4, string_layout).to_string(),
+            format!(
+                "Inputs:
+  Input(0): Pure u64: 5
+  Input(1): Shared {pool} (initial shared version 3, mutable)
+  Input(2): ImmutableOrOwned {coin} (version 7)
+  Input(3): Pure 0x01020304 (4 bytes)
+  Input(4): Pure 0x1::string::String: \"hello\"
+Commands:
+  Result(0): SplitCoins({coin}, [5])
+  Result(1): 0x2::pool::deposit<0x2::coin::Coin<0x2::bfc::BFC>>({pool}, Result(0), Input(3), \"hello\")
+  Result(2): MakeMoveVec<u64>([5, NestedResult(1, 0)])
+"
+            )
+        );
+
+        // A pure input which can't be decoded as its inferred type is left undecoded
+        let undecoded = ptb.display().with_input_layout(3, MoveTypeLayout::U64);
+        assert!(undecoded.pure_value(3).is_none());
+        assert!(undecoded
+            .to_string()
+            .contains("Input(3): Pure 0x01020304 (4 bytes)"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let json = ptb()
+            .display()
+            .with_input_layout(3, MoveTypeLayout::U32)
+            .to_json();
+
+        assert_eq!(
+            json["inputs"][0],
+            serde_json::json!({ "kind": "pure", "type": "u64", "value": "5" })
+        );
+        assert_eq!(
+            json["inputs"][3],
+            serde_json::json!({ "kind": "pure", "type": "u32", "value": 67305985 })
+        );
+        assert_eq!(
+            json["inputs"][4],
+            serde_json::json!({ "kind": "pure", "bytes": "BWhlbGxv" })
+        );
+        assert_eq!(json["inputs"][1]["kind"], "shared");
+        assert_eq!(json["commands"][0]["command"], "SplitCoins");
+        assert_eq!(
+            json["commands"][1]["arguments"],
+            serde_json::json!([{ "input": 1 }, { "result": 0 }, { "input": 3 }, { "input": 4 }])
+        );
+        assert_eq!(
+            json["commands"][2]["elements"][1],
+            serde_json::json!({ "nested_result": [1, 0] })
+        );
+    }
+}
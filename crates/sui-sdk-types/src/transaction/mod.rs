@@ -16,9 +16,11 @@ use super::TypeTag;
 use super::UserSignature;
 use super::Version;
 
+mod display;
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 mod serialization;
+pub use display::ProgrammableTransactionDisplay;
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 pub(crate) use serialization::SignedTransactionWithIntentMessage;
@@ -860,6 +862,41 @@ pub struct ProgrammableTransaction {
     pub commands: Vec<Command>,
 }
 
+impl ProgrammableTransaction {
+    /// The bytes of the pure input referred to by `argument`, if it is one.
+    pub fn pure_bytes(&self, argument: &Argument) -> Option<&[u8]> {
+        let Argument::Input(index) = argument else {
+            return None;
+        };
+        match self.inputs.get(*index as usize)? {
+            Input::Pure { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The pure input referred to by `argument`, if it is one and is a valid `address`.
+    pub fn pure_address(&self, argument: &Argument) -> Option<Address> {
+        Address::from_bytes(self.pure_bytes(argument)?).ok()
+    }
+
+    /// The pure input referred to by `argument`, if it is one and is a valid `u64`.
+    pub fn pure_u64(&self, argument: &Argument) -> Option<u64> {
+        Some(u64::from_le_bytes(
+            self.pure_bytes(argument)?.try_into().ok()?,
+        ))
+    }
+
+    /// The amounts split off by `split`, or `None` if any of them isn't a pure `u64` input, e.g.
+    /// because it is computed by an earlier command.
+    pub fn split_amounts(&self, split: &SplitCoins) -> Option<Vec<u64>> {
+        split
+            .amounts
+            .iter()
+            .map(|amount| self.pure_u64(amount))
+            .collect()
+    }
+}
+
 /// An input to a user transaction
 ///
 /// # BCS
@@ -1,10 +1,21 @@
+mod move_type;
+mod open;
 mod parse;
+mod pattern;
 
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 mod serialization;
 
+pub use move_type::MoveType;
+pub use open::OpenStructTag;
+pub use open::OpenTypeTag;
+use pattern::PatternKind;
+pub use pattern::TypePattern;
+
 use super::Address;
+use super::ObjectId;
+use super::TypeOrigin;
 
 /// Type of a move value
 ///
@@ -37,6 +48,12 @@ use super::Address;
 /// type-tag-vector = %x06 type-tag
 /// type-tag-struct = %x07 struct-tag
 /// ```
+///
+/// # Display
+///
+/// Addresses are displayed in full, e.g. `0x0000..0002::coin::Coin<u64>`. The alternate form
+/// (`{:#}`), also available via [`TypeTag::to_short_string`], displays addresses with their
+/// leading zeros removed, e.g. `0x2::coin::Coin<u64>`.
 #[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Hash)]
 #[cfg_attr(feature = "proptest", derive(test_strategy::Arbitrary))]
 pub enum TypeTag {
@@ -67,18 +84,76 @@ impl std::fmt::Display for TypeTag {
             TypeTag::Address => write!(f, "address"),
             TypeTag::Signer => write!(f, "signer"),
             TypeTag::Vector(t) => {
-                write!(f, "vector<{t}>")
+                f.write_str("vector<")?;
+                t.fmt(f)?;
+                f.write_str(">")
             }
             TypeTag::Struct(s) => s.fmt(f),
         }
     }
 }
 
+impl TypeTag {
+    /// Display this type with the leading zeros of its addresses removed, e.g.
+    /// `0x2::coin::Coin<0x2::bfc::BFC>`.
+    pub fn to_short_string(&self) -> String {
+        format!("{self:#}")
+    }
+
+    /// Rewrite the address of every struct in this type, including those in type parameters.
+    ///
+    /// `f` is called with each struct tag, after its type parameters have been rewritten, and
+    /// returns the new address for the struct, or `None` to leave it unchanged.
+    pub fn rewrite_addresses<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&StructTag) -> Option<Address>,
+    {
+        self.rewrite_addresses_with(&mut f)
+    }
+
+    fn rewrite_addresses_with<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&StructTag) -> Option<Address>,
+    {
+        match self {
+            TypeTag::Vector(t) => t.rewrite_addresses_with(f),
+            TypeTag::Struct(s) => s.rewrite_addresses_with(f),
+            _ => {}
+        }
+    }
+
+    /// Convert the types defined by the package with `original_id` from being addressed by the
+    /// package's original id to the id of the package version which defined them.
+    ///
+    /// See [`StructTag::into_defining_ids`].
+    pub fn into_defining_ids(
+        mut self,
+        original_id: &ObjectId,
+        type_origins: &[TypeOrigin],
+    ) -> Self {
+        self.rewrite_addresses(defining_id(original_id, type_origins));
+        self
+    }
+
+    /// Convert the types defined by any version of the package with `original_id` to being
+    /// addressed by the package's original id.
+    ///
+    /// See [`StructTag::into_original_ids`].
+    pub fn into_original_ids(
+        mut self,
+        original_id: &ObjectId,
+        type_origins: &[TypeOrigin],
+    ) -> Self {
+        self.rewrite_addresses(original_id_of(original_id, type_origins));
+        self
+    }
+}
+
 impl std::str::FromStr for TypeTag {
     type Err = TypeParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse::parse_type_tag(s).map_err(|_| TypeParseError { source: s.into() })
+        parse::parse_type_tag(s)
     }
 }
 
@@ -88,14 +163,32 @@ impl From<StructTag> for TypeTag {
     }
 }
 
+/// Error returned when parsing a type, or one of its components, from a string
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TypeParseError {
     source: String,
+    position: usize,
+}
+
+impl TypeParseError {
+    /// The string which failed to parse.
+    pub fn input(&self) -> &str {
+        &self.source
+    }
+
+    /// The byte offset into the input at which parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
 }
 
 impl std::fmt::Display for TypeParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self, f)
+        write!(
+            f,
+            "invalid type `{}`: unexpected input at position {}",
+            self.source, self.position
+        )
     }
 }
 
@@ -129,11 +222,7 @@ pub struct Identifier(
 
 impl Identifier {
     pub fn new<T: AsRef<str>>(identifier: T) -> Result<Self, TypeParseError> {
-        parse::parse_identifier(identifier.as_ref())
-            .map(|ident| Self(ident.into()))
-            .map_err(|_| TypeParseError {
-                source: identifier.as_ref().into(),
-            })
+        parse::parse_identifier(identifier.as_ref()).map(|ident| Self(ident.into()))
     }
 
     pub fn into_inner(self) -> Box<str> {
@@ -155,9 +244,7 @@ impl std::str::FromStr for Identifier {
     type Err = TypeParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse::parse_identifier(s)
-            .map(|ident| Self(ident.into()))
-            .map_err(|_| TypeParseError { source: s.into() })
+        parse::parse_identifier(s).map(|ident| Self(ident.into()))
     }
 }
 
@@ -179,6 +266,11 @@ impl PartialEq<str> for Identifier {
 ///              identifier         ; name of the type
 ///              (vector type-tag)  ; type parameters
 /// ```
+///
+/// # Display
+///
+/// As with [`TypeTag`], the alternate form (`{:#}`) displays addresses with their leading zeros
+/// removed.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "proptest", derive(test_strategy::Arbitrary))]
 pub struct StructTag {
@@ -219,6 +311,135 @@ impl StructTag {
         }
     }
 
+    /// `0x2::balance::Balance<coin_type>`
+    pub fn balance(coin_type: TypeTag) -> Self {
+        Self::framework(Address::TWO, "balance", "Balance", vec![coin_type])
+    }
+
+    /// `0x2::coin::CoinMetadata<coin_type>`
+    pub fn coin_metadata(coin_type: TypeTag) -> Self {
+        Self::framework(Address::TWO, "coin", "CoinMetadata", vec![coin_type])
+    }
+
+    /// `0x2::coin::TreasuryCap<coin_type>`
+    pub fn treasury_cap(coin_type: TypeTag) -> Self {
+        Self::framework(Address::TWO, "coin", "TreasuryCap", vec![coin_type])
+    }
+
+    /// `0x2::package::UpgradeCap`
+    pub fn upgrade_cap() -> Self {
+        Self::framework(Address::TWO, "package", "UpgradeCap", vec![])
+    }
+
+    /// `0x2::authenticator_state::AuthenticatorState`
+    pub fn authenticator_state() -> Self {
+        Self::framework(
+            Address::TWO,
+            "authenticator_state",
+            "AuthenticatorState",
+            vec![],
+        )
+    }
+
+    /// `0x2::kiosk::Kiosk`
+    pub fn kiosk() -> Self {
+        Self::framework(Address::TWO, "kiosk", "Kiosk", vec![])
+    }
+
+    /// `0x2::clock::Clock`
+    pub fn clock() -> Self {
+        Self::framework(Address::TWO, "clock", "Clock", vec![])
+    }
+
+    /// `0x2::object::ID`
+    pub fn id() -> Self {
+        Self::framework(Address::TWO, "object", "ID", vec![])
+    }
+
+    /// `0x1::string::String`
+    pub fn string() -> Self {
+        Self::framework(Address::ONE, "string", "String", vec![])
+    }
+
+    /// `0x1::ascii::String`
+    pub fn ascii_string() -> Self {
+        Self::framework(Address::ONE, "ascii", "String", vec![])
+    }
+
+    fn framework(address: Address, module: &str, name: &str, type_params: Vec<TypeTag>) -> Self {
+        Self {
+            address,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params,
+        }
+    }
+
+    /// `0x2::dynamic_field::Field<name, value>`
+    pub fn dynamic_field(name: TypeTag, value: TypeTag) -> Self {
+        Self {
+            address: Address::TWO,
+            module: Identifier::new("dynamic_field").unwrap(),
+            name: Identifier::new("Field").unwrap(),
+            type_params: vec![name, value],
+        }
+    }
+
+    /// `0x2::dynamic_object_field::Wrapper<name>`, the type used to wrap the names of dynamic
+    /// object fields.
+    pub fn dynamic_object_field_wrapper(name: TypeTag) -> Self {
+        Self {
+            address: Address::TWO,
+            module: Identifier::new("dynamic_object_field").unwrap(),
+            name: Identifier::new("Wrapper").unwrap(),
+            type_params: vec![name],
+        }
+    }
+
+    /// Checks if this is a dynamic field, returning the types of the field's name and value.
+    pub fn is_dynamic_field(&self) -> Option<(&TypeTag, &TypeTag)> {
+        let Self {
+            address,
+            module,
+            name,
+            type_params,
+        } = self;
+
+        if address == &Address::TWO && module == "dynamic_field" && name == "Field" {
+            if let [name, value] = type_params.as_slice() {
+                return Some((name, value));
+            }
+        }
+
+        None
+    }
+
+    /// Checks if this is a `0x2::balance::Balance`, returning its coin type.
+    pub fn is_balance(&self) -> Option<&TypeTag> {
+        self.is_coin_type_wrapper("balance", "Balance")
+    }
+
+    /// Checks if this is a `0x2::coin::CoinMetadata`, returning its coin type.
+    pub fn is_coin_metadata(&self) -> Option<&TypeTag> {
+        self.is_coin_type_wrapper("coin", "CoinMetadata")
+    }
+
+    /// Checks if this is a `0x2::coin::TreasuryCap`, returning its coin type.
+    pub fn is_treasury_cap(&self) -> Option<&TypeTag> {
+        self.is_coin_type_wrapper("coin", "TreasuryCap")
+    }
+
+    fn is_coin_type_wrapper(&self, module: &str, name: &str) -> Option<&TypeTag> {
+        match self.type_params.as_slice() {
+            [coin_type]
+                if self.address == Address::TWO && self.module == *module && self.name == *name =>
+            {
+                Some(coin_type)
+            }
+            _ => None,
+        }
+    }
+
     /// Checks if this is a Coin type
     pub fn is_coin(&self) -> Option<&TypeTag> {
         let Self {
@@ -235,28 +456,210 @@ impl StructTag {
             None
         }
     }
+
+    /// Display this type with the leading zeros of its addresses removed, e.g.
+    /// `0x2::coin::Coin<0x2::bfc::BFC>`.
+    pub fn to_short_string(&self) -> String {
+        format!("{self:#}")
+    }
+
+    /// Rewrite the address of this struct and of every struct in its type parameters.
+    ///
+    /// `f` is called with each struct tag, after its type parameters have been rewritten, and
+    /// returns the new address for the struct, or `None` to leave it unchanged.
+    pub fn rewrite_addresses<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&StructTag) -> Option<Address>,
+    {
+        self.rewrite_addresses_with(&mut f)
+    }
+
+    fn rewrite_addresses_with<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&StructTag) -> Option<Address>,
+    {
+        for type_param in &mut self.type_params {
+            type_param.rewrite_addresses_with(f);
+        }
+
+        if let Some(address) = f(self) {
+            self.address = address;
+        }
+    }
+
+    /// Convert the types defined by the package with `original_id` from being addressed by the
+    /// package's original id to the id of the package version which defined them.
+    ///
+    /// Move code always refers to a package's types using the id of the first version of the
+    /// package, while on-chain objects and events are typed using the id of the version which
+    /// introduced the type. `type_origins` is the type origin table of any version of the package
+    /// which includes all of the types being converted, e.g. the latest version's
+    /// [`MovePackage::type_origin_table`](crate::MovePackage::type_origin_table).
+    ///
+    /// Structs which aren't addressed by `original_id`, or aren't found in `type_origins`, are
+    /// left unchanged.
+    pub fn into_defining_ids(
+        mut self,
+        original_id: &ObjectId,
+        type_origins: &[TypeOrigin],
+    ) -> Self {
+        self.rewrite_addresses(defining_id(original_id, type_origins));
+        self
+    }
+
+    /// Convert the types defined by any version of the package with `original_id` to being
+    /// addressed by the package's original id.
+    ///
+    /// This is the inverse of [`StructTag::into_defining_ids`].
+    pub fn into_original_ids(
+        mut self,
+        original_id: &ObjectId,
+        type_origins: &[TypeOrigin],
+    ) -> Self {
+        self.rewrite_addresses(original_id_of(original_id, type_origins));
+        self
+    }
+}
+
+fn defining_id<'a>(
+    original_id: &'a ObjectId,
+    type_origins: &'a [TypeOrigin],
+) -> impl FnMut(&StructTag) -> Option<Address> + 'a {
+    move |s| {
+        if s.address != Address::from(*original_id) {
+            return None;
+        }
+
+        type_origins
+            .iter()
+            .find(|origin| origin.module_name == s.module && origin.struct_name == s.name)
+            .map(|origin| origin.package.into())
+    }
+}
+
+fn original_id_of<'a>(
+    original_id: &'a ObjectId,
+    type_origins: &'a [TypeOrigin],
+) -> impl FnMut(&StructTag) -> Option<Address> + 'a {
+    move |s| {
+        type_origins
+            .iter()
+            .any(|origin| {
+                Address::from(origin.package) == s.address
+                    && origin.module_name == s.module
+                    && origin.struct_name == s.name
+            })
+            .then(|| (*original_id).into())
+    }
 }
 
 impl std::fmt::Display for StructTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}::{}::{}", self.address, self.module, self.name)?;
+        write_address(f, &self.address)?;
+        write!(f, "::{}::{}", self.module, self.name)?;
+        write_type_params(f, &self.type_params)
+    }
+}
 
-        if let Some(first_type) = self.type_params.first() {
-            write!(f, "<")?;
-            write!(f, "{first_type}")?;
-            for ty in self.type_params.iter().skip(1) {
-                write!(f, ", {ty}")?;
-            }
-            write!(f, ">")?;
+/// Write `address` in full, or with its leading zeros removed when using the alternate form.
+fn write_address(f: &mut std::fmt::Formatter<'_>, address: &Address) -> std::fmt::Result {
+    if f.alternate() {
+        f.write_str(&address.to_short_string())
+    } else {
+        write!(f, "{address}")
+    }
+}
+
+/// Write `<T0, T1, ..>`, if there are any type parameters, preserving the formatter's flags.
+fn write_type_params<T: std::fmt::Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    type_params: &[T],
+) -> std::fmt::Result {
+    if type_params.is_empty() {
+        return Ok(());
+    }
+
+    f.write_str("<")?;
+    for (i, ty) in type_params.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
         }
-        Ok(())
+        ty.fmt(f)?;
     }
+    f.write_str(">")
 }
 
 impl std::str::FromStr for StructTag {
     type Err = TypeParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse::parse_struct_tag(s).map_err(|_| TypeParseError { source: s.into() })
+        parse::parse_struct_tag(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn short_display() {
+        let tag: TypeTag = "vector<0x2::coin::Coin<0x00a1::token::TOKEN>>"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            tag.to_short_string(),
+            "vector<0x2::coin::Coin<0xa1::token::TOKEN>>"
+        );
+        assert_eq!(
+            tag.to_string(),
+            format!(
+                "vector<{}::coin::Coin<{}::token::TOKEN>>",
+                Address::TWO,
+                "0x00a1".parse::<Address>().unwrap()
+            )
+        );
+        assert_eq!(tag.to_short_string().parse::<TypeTag>().unwrap(), tag);
+
+        let zero: StructTag = "0x0::m::S".parse().unwrap();
+        assert_eq!(zero.to_short_string(), "0x0::m::S");
+    }
+
+    #[test]
+    fn defining_ids() {
+        let original_id: ObjectId = "0xa1".parse().unwrap();
+        let upgrade_id: ObjectId = "0xa2".parse().unwrap();
+        let type_origins = [
+            TypeOrigin {
+                module_name: Identifier::new("pool").unwrap(),
+                struct_name: Identifier::new("Pool").unwrap(),
+                package: original_id,
+            },
+            TypeOrigin {
+                module_name: Identifier::new("pool").unwrap(),
+                struct_name: Identifier::new("Receipt").unwrap(),
+                package: upgrade_id,
+            },
+        ];
+
+        let original: TypeTag = "0x2::coin::Coin<0xa1::pool::Pool<0xa1::pool::Receipt>>"
+            .parse()
+            .unwrap();
+        let defining: TypeTag = "0x2::coin::Coin<0xa1::pool::Pool<0xa2::pool::Receipt>>"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            original
+                .clone()
+                .into_defining_ids(&original_id, &type_origins),
+            defining
+        );
+        assert_eq!(
+            defining.into_original_ids(&original_id, &type_origins),
+            original
+        );
     }
 }
@@ -0,0 +1,84 @@
+use super::StructTag;
+use super::TypeTag;
+use crate::framework::MoveString;
+use crate::Address;
+use crate::ObjectId;
+use crate::U256;
+
+/// A Rust type with a statically known Move type.
+///
+/// This is used to determine the Move type of a value from its Rust type, e.g. the type of the
+/// name of a dynamic field. The BCS representation of an implementing type must be identical to
+/// that of the Move type returned by [`MoveType::type_tag`].
+pub trait MoveType {
+    fn type_tag() -> TypeTag;
+}
+
+macro_rules! impl_move_type {
+    ($($ty:ty => $tag:expr),* $(,)?) => {
+        $(
+            impl MoveType for $ty {
+                fn type_tag() -> TypeTag {
+                    $tag
+                }
+            }
+        )*
+    };
+}
+
+impl_move_type! {
+    bool => TypeTag::Bool,
+    u8 => TypeTag::U8,
+    u16 => TypeTag::U16,
+    u32 => TypeTag::U32,
+    u64 => TypeTag::U64,
+    u128 => TypeTag::U128,
+    U256 => TypeTag::U256,
+    Address => TypeTag::Address,
+    ObjectId => StructTag::id().into(),
+    str => StructTag::string().into(),
+    String => StructTag::string().into(),
+    MoveString => StructTag::string().into(),
+}
+
+impl<T: MoveType> MoveType for Vec<T> {
+    fn type_tag() -> TypeTag {
+        TypeTag::Vector(Box::new(T::type_tag()))
+    }
+}
+
+impl<T: MoveType> MoveType for [T] {
+    fn type_tag() -> TypeTag {
+        TypeTag::Vector(Box::new(T::type_tag()))
+    }
+}
+
+impl<T: MoveType + ?Sized> MoveType for &T {
+    fn type_tag() -> TypeTag {
+        T::type_tag()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn type_tags() {
+        assert_eq!(u64::type_tag().to_string(), "u64");
+        assert_eq!(U256::type_tag().to_string(), "u256");
+        assert_eq!(<Vec<Vec<u8>>>::type_tag().to_string(), "vector<vector<u8>>");
+        assert_eq!(<&str>::type_tag(), String::type_tag());
+        assert_eq!(
+            ObjectId::type_tag().to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000002::object::ID"
+        );
+        assert_eq!(
+            <[MoveString]>::type_tag().to_string(),
+            "vector<0x0000000000000000000000000000000000000000000000000000000000000001::string::String>"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use super::parse;
+use super::write_address;
+use super::write_type_params;
+use super::Address;
+use super::Identifier;
+use super::StructTag;
+use super::TypeParseError;
+use super::TypeTag;
+
+/// A type which may refer to the type parameters of an enclosing generic function or struct
+///
+/// Type parameters are referred to by their index and written `T0`, `T1`, etc, as found in the
+/// signatures of normalized Move functions and structs, e.g. `0x2::coin::Coin<T0>`. An open type
+/// can be instantiated into a [`TypeTag`] by substituting its type parameters with concrete
+/// type arguments.
+///
+/// ```
+/// use sui_sdk_types::OpenTypeTag;
+/// use sui_sdk_types::TypeTag;
+///
+/// let open: OpenTypeTag = "vector<0x2::coin::Coin<T0>>".parse().unwrap();
+/// let instantiated = open
+///     .instantiate(&["0x2::bfc::BFC".parse().unwrap()])
+///     .unwrap();
+///
+/// assert_eq!(
+///     instantiated,
+///     "vector<0x2::coin::Coin<0x2::bfc::BFC>>"
+///         .parse::<TypeTag>()
+///         .unwrap(),
+/// );
+/// ```
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Hash)]
+pub enum OpenTypeTag {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Bool,
+    Address,
+    Signer,
+    Vector(Box<OpenTypeTag>),
+    Struct(Box<OpenStructTag>),
+    /// The type parameter at the given index.
+    TypeParameter(u16),
+}
+
+impl OpenTypeTag {
+    /// Returns true if this type doesn't refer to any type parameters.
+    pub fn is_closed(&self) -> bool {
+        match self {
+            OpenTypeTag::Vector(t) => t.is_closed(),
+            OpenTypeTag::Struct(s) => s.is_closed(),
+            OpenTypeTag::TypeParameter(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Substitute each type parameter `Tn` with `type_args[n]`.
+    ///
+    /// Returns `None` if this type refers to a type parameter which is out of bounds of
+    /// `type_args`.
+    pub fn instantiate(&self, type_args: &[TypeTag]) -> Option<TypeTag> {
+        let tag = match self {
+            OpenTypeTag::U8 => TypeTag::U8,
+            OpenTypeTag::U16 => TypeTag::U16,
+            OpenTypeTag::U32 => TypeTag::U32,
+            OpenTypeTag::U64 => TypeTag::U64,
+            OpenTypeTag::U128 => TypeTag::U128,
+            OpenTypeTag::U256 => TypeTag::U256,
+            OpenTypeTag::Bool => TypeTag::Bool,
+            OpenTypeTag::Address => TypeTag::Address,
+            OpenTypeTag::Signer => TypeTag::Signer,
+            OpenTypeTag::Vector(t) => TypeTag::Vector(Box::new(t.instantiate(type_args)?)),
+            OpenTypeTag::Struct(s) => TypeTag::Struct(Box::new(s.instantiate(type_args)?)),
+            OpenTypeTag::TypeParameter(idx) => type_args.get(*idx as usize)?.clone(),
+        };
+
+        Some(tag)
+    }
+
+    /// Substitute each type parameter `Tn` with `type_args[n]`, which may themselves refer to
+    /// type parameters, e.g. when instantiating the signature of a generic function called from
+    /// another generic function.
+    ///
+    /// Returns `None` if this type refers to a type parameter which is out of bounds of
+    /// `type_args`.
+    pub fn substitute(&self, type_args: &[OpenTypeTag]) -> Option<OpenTypeTag> {
+        let tag = match self {
+            OpenTypeTag::Vector(t) => OpenTypeTag::Vector(Box::new(t.substitute(type_args)?)),
+            OpenTypeTag::Struct(s) => OpenTypeTag::Struct(Box::new(s.substitute(type_args)?)),
+            OpenTypeTag::TypeParameter(idx) => type_args.get(*idx as usize)?.clone(),
+            primitive => primitive.clone(),
+        };
+
+        Some(tag)
+    }
+}
+
+impl From<TypeTag> for OpenTypeTag {
+    fn from(value: TypeTag) -> Self {
+        match value {
+            TypeTag::U8 => OpenTypeTag::U8,
+            TypeTag::U16 => OpenTypeTag::U16,
+            TypeTag::U32 => OpenTypeTag::U32,
+            TypeTag::U64 => OpenTypeTag::U64,
+            TypeTag::U128 => OpenTypeTag::U128,
+            TypeTag::U256 => OpenTypeTag::U256,
+            TypeTag::Bool => OpenTypeTag::Bool,
+            TypeTag::Address => OpenTypeTag::Address,
+            TypeTag::Signer => OpenTypeTag::Signer,
+            TypeTag::Vector(t) => OpenTypeTag::Vector(Box::new((*t).into())),
+            TypeTag::Struct(s) => OpenTypeTag::Struct(Box::new((*s).into())),
+        }
+    }
+}
+
+impl From<OpenStructTag> for OpenTypeTag {
+    fn from(value: OpenStructTag) -> Self {
+        Self::Struct(Box::new(value))
+    }
+}
+
+impl std::fmt::Display for OpenTypeTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenTypeTag::U8 => write!(f, "u8"),
+            OpenTypeTag::U16 => write!(f, "u16"),
+            OpenTypeTag::U32 => write!(f, "u32"),
+            OpenTypeTag::U64 => write!(f, "u64"),
+            OpenTypeTag::U128 => write!(f, "u128"),
+            OpenTypeTag::U256 => write!(f, "u256"),
+            OpenTypeTag::Bool => write!(f, "bool"),
+            OpenTypeTag::Address => write!(f, "address"),
+            OpenTypeTag::Signer => write!(f, "signer"),
+            OpenTypeTag::Vector(t) => {
+                f.write_str("vector<")?;
+                t.fmt(f)?;
+                f.write_str(">")
+            }
+            OpenTypeTag::Struct(s) => s.fmt(f),
+            OpenTypeTag::TypeParameter(idx) => write!(f, "T{idx}"),
+        }
+    }
+}
+
+impl std::str::FromStr for OpenTypeTag {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_open_type_tag(s)
+    }
+}
+
+/// A struct type whose type parameters may refer to the type parameters of an enclosing generic
+/// function or struct
+///
+/// See [`OpenTypeTag`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpenStructTag {
+    pub address: Address,
+    pub module: Identifier,
+    pub name: Identifier,
+    pub type_params: Vec<OpenTypeTag>,
+}
+
+impl OpenStructTag {
+    /// Returns true if this type doesn't refer to any type parameters.
+    pub fn is_closed(&self) -> bool {
+        self.type_params.iter().all(OpenTypeTag::is_closed)
+    }
+
+    /// Substitute each type parameter `Tn` with `type_args[n]`.
+    ///
+    /// Returns `None` if this type refers to a type parameter which is out of bounds of
+    /// `type_args`.
+    pub fn instantiate(&self, type_args: &[TypeTag]) -> Option<StructTag> {
+        Some(StructTag {
+            address: self.address,
+            module: self.module.clone(),
+            name: self.name.clone(),
+            type_params: self
+                .type_params
+                .iter()
+                .map(|t| t.instantiate(type_args))
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    /// Substitute each type parameter `Tn` with `type_args[n]`, which may themselves refer to
+    /// type parameters.
+    ///
+    /// Returns `None` if this type refers to a type parameter which is out of bounds of
+    /// `type_args`.
+    pub fn substitute(&self, type_args: &[OpenTypeTag]) -> Option<OpenStructTag> {
+        Some(OpenStructTag {
+            address: self.address,
+            module: self.module.clone(),
+            name: self.name.clone(),
+            type_params: self
+                .type_params
+                .iter()
+                .map(|t| t.substitute(type_args))
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+impl From<StructTag> for OpenStructTag {
+    fn from(value: StructTag) -> Self {
+        Self {
+            address: value.address,
+            module: value.module,
+            name: value.name,
+            type_params: value.type_params.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for OpenStructTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_address(f, &self.address)?;
+        write!(f, "::{}::{}", self.module, self.name)?;
+        write_type_params(f, &self.type_params)
+    }
+}
+
+impl std::str::FromStr for OpenStructTag {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_open_struct_tag(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn instantiate() {
+        let open: OpenTypeTag = "0x2::dynamic_field::Field<T1, vector<T0>>".parse().unwrap();
+        assert!(!open.is_closed());
+        assert_eq!(
+            format!("{open:#}"),
+            "0x2::dynamic_field::Field<T1, vector<T0>>"
+        );
+
+        let type_args = [
+            TypeTag::U8,
+            TypeTag::Struct(Box::new(StructTag::gas_coin())),
+        ];
+        let tag = open.instantiate(&type_args).unwrap();
+        assert_eq!(
+            tag,
+            StructTag::dynamic_field(type_args[1].clone(), TypeTag::Vector(Box::new(TypeTag::U8)))
+                .into()
+        );
+
+        // Not enough type arguments
+        assert_eq!(open.instantiate(&type_args[..1]), None);
+    }
+
+    #[test]
+    fn substitute() {
+        let open: OpenTypeTag = "0x2::coin::Coin<T0>".parse().unwrap();
+        let substituted = open
+            .substitute(&["0x2::balance::Balance<T1>".parse().unwrap()])
+            .unwrap();
+        assert_eq!(
+            substituted,
+            "0x2::coin::Coin<0x2::balance::Balance<T1>>"
+                .parse()
+                .unwrap()
+        );
+
+        let closed = OpenTypeTag::from(TypeTag::Vector(Box::new(TypeTag::U64)));
+        assert!(closed.is_closed());
+        assert_eq!(
+            closed.instantiate(&[]),
+            Some(TypeTag::Vector(Box::new(TypeTag::U64)))
+        );
+    }
+}
@@ -1,14 +1,23 @@
 use super::Address;
 use super::Identifier;
+use super::OpenStructTag;
+use super::OpenTypeTag;
+use super::PatternKind;
 use super::StructTag;
+use super::TypeParseError;
+use super::TypePattern;
 use super::TypeTag;
 
+use winnow::ascii::dec_uint;
 use winnow::ascii::space0;
 use winnow::combinator::alt;
+use winnow::combinator::cut_err;
 use winnow::combinator::delimited;
-use winnow::combinator::eof;
 use winnow::combinator::opt;
+use winnow::combinator::preceded;
 use winnow::combinator::separated;
+use winnow::combinator::terminated;
+use winnow::error::ContextError;
 use winnow::stream::AsChar;
 use winnow::token::one_of;
 use winnow::token::take_while;
@@ -19,8 +28,19 @@ use winnow::Parser;
 // static ALLOWED_IDENTIFIERS: &str = r"(?:[a-zA-Z][a-zA-Z0-9_]*)|(?:_[a-zA-Z0-9_]+)";
 static MAX_IDENTIFIER_LENGTH: usize = 128;
 
-pub(super) fn parse_identifier(mut input: &str) -> ModalResult<&str> {
-    (identifier, eof).take().parse_next(&mut input)
+/// Run `parser` over the entirety of `input`, reporting the offset at which parsing failed.
+fn parse_complete<'s, O>(
+    mut parser: impl ModalParser<&'s str, O, ContextError>,
+    input: &'s str,
+) -> Result<O, TypeParseError> {
+    parser.parse(input).map_err(|e| TypeParseError {
+        source: input.into(),
+        position: e.offset(),
+    })
+}
+
+pub(super) fn parse_identifier(input: &str) -> Result<&str, TypeParseError> {
+    parse_complete(identifier, input)
 }
 
 fn identifier<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
@@ -32,9 +52,7 @@ fn identifier<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
     .parse_next(input)
 }
 
-fn valid_remainder<'a>(
-    minimum: usize,
-) -> impl ModalParser<&'a str, &'a str, winnow::error::ContextError> {
+fn valid_remainder<'a>(minimum: usize) -> impl ModalParser<&'a str, &'a str, ContextError> {
     move |input: &mut &'a str| {
         take_while(
             // Use .. instead of ..= since we've already processed a single character
@@ -51,11 +69,13 @@ fn parse_address<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
         .parse_next(input)
 }
 
-pub(super) fn parse_type_tag(mut input: &str) -> ModalResult<TypeTag> {
-    (type_tag, eof).parse_next(&mut input).map(|(t, _)| t)
+fn address(input: &mut &str) -> ModalResult<Address> {
+    parse_address
+        .try_map(|s| s.parse::<Address>())
+        .parse_next(input)
 }
 
-fn type_tag(input: &mut &str) -> ModalResult<TypeTag> {
+fn primitive(input: &mut &str) -> ModalResult<TypeTag> {
     alt((
         "u8".value(TypeTag::U8),
         "u16".value(TypeTag::U16),
@@ -66,41 +86,144 @@ fn type_tag(input: &mut &str) -> ModalResult<TypeTag> {
         "bool".value(TypeTag::Bool),
         "address".value(TypeTag::Address),
         "signer".value(TypeTag::Signer),
-        delimited("vector<", type_tag, ">").map(|ty| TypeTag::Vector(Box::new(ty))),
+    ))
+    .parse_next(input)
+}
+
+/// `vector<element>`, committing to the vector once `vector<` has been seen.
+fn vector<'s, O>(
+    element: impl ModalParser<&'s str, O, ContextError>,
+) -> impl ModalParser<&'s str, O, ContextError> {
+    preceded("vector<", cut_err(terminated(element, ">")))
+}
+
+/// An optional, comma separated list of type parameters, committing to the list once `<` has
+/// been seen.
+fn type_params<'s, O>(
+    element: impl ModalParser<&'s str, O, ContextError>,
+) -> impl ModalParser<&'s str, Option<Vec<O>>, ContextError> {
+    opt(preceded(
+        "<",
+        cut_err(terminated(
+            separated(1.., delimited(space0, element, space0), ","),
+            ">",
+        )),
+    ))
+}
+
+pub(super) fn parse_type_tag(input: &str) -> Result<TypeTag, TypeParseError> {
+    parse_complete(type_tag, input)
+}
+
+fn type_tag(input: &mut &str) -> ModalResult<TypeTag> {
+    alt((
+        primitive,
+        vector(type_tag).map(|ty| TypeTag::Vector(Box::new(ty))),
         struct_tag.map(|s| TypeTag::Struct(Box::new(s))),
     ))
     .parse_next(input)
 }
 
-pub(super) fn parse_struct_tag(mut input: &str) -> ModalResult<StructTag> {
-    (struct_tag, eof).parse_next(&mut input).map(|(s, _)| s)
+pub(super) fn parse_struct_tag(input: &str) -> Result<StructTag, TypeParseError> {
+    parse_complete(struct_tag, input)
 }
 
 fn struct_tag(input: &mut &str) -> ModalResult<StructTag> {
+    let (address, module, name) = struct_path.parse_next(input)?;
+
+    // optional generic
+    let generics = type_params(type_tag).parse_next(input)?.unwrap_or_default();
+
+    Ok(StructTag {
+        address,
+        module,
+        name,
+        type_params: generics,
+    })
+}
+
+fn struct_path(input: &mut &str) -> ModalResult<(Address, Identifier, Identifier)> {
     let (address, _, module, _, name) = (
-        parse_address.try_map(|s| s.parse::<Address>()),
+        address,
         "::",
         identifier.map(|ident| Identifier(ident.into())),
         "::",
         identifier.map(|ident| Identifier(ident.into())),
     )
         .parse_next(input)?;
+    Ok((address, module, name))
+}
 
-    // optional generic
-    let generics = opt(delimited("<", generics, ">"))
+pub(super) fn parse_open_type_tag(input: &str) -> Result<OpenTypeTag, TypeParseError> {
+    parse_complete(open_type_tag, input)
+}
+
+fn open_type_tag(input: &mut &str) -> ModalResult<OpenTypeTag> {
+    alt((
+        primitive.map(OpenTypeTag::from),
+        vector(open_type_tag).map(|ty| OpenTypeTag::Vector(Box::new(ty))),
+        preceded('T', dec_uint).map(OpenTypeTag::TypeParameter),
+        open_struct_tag.map(|s| OpenTypeTag::Struct(Box::new(s))),
+    ))
+    .parse_next(input)
+}
+
+pub(super) fn parse_open_struct_tag(input: &str) -> Result<OpenStructTag, TypeParseError> {
+    parse_complete(open_struct_tag, input)
+}
+
+fn open_struct_tag(input: &mut &str) -> ModalResult<OpenStructTag> {
+    let (address, module, name) = struct_path.parse_next(input)?;
+    let type_params = type_params(open_type_tag)
         .parse_next(input)?
         .unwrap_or_default();
 
-    Ok(StructTag {
+    Ok(OpenStructTag {
         address,
         module,
         name,
-        type_params: generics,
+        type_params,
     })
 }
 
-fn generics(input: &mut &str) -> ModalResult<Vec<TypeTag>> {
-    separated(1.., delimited(space0, type_tag, space0), ",").parse_next(input)
+pub(super) fn parse_type_pattern(input: &str) -> Result<TypePattern, TypeParseError> {
+    parse_complete(type_pattern, input).map(TypePattern)
+}
+
+fn type_pattern(input: &mut &str) -> ModalResult<PatternKind> {
+    alt((
+        '*'.value(PatternKind::Any),
+        primitive.map(PatternKind::Primitive),
+        vector(type_pattern).map(|ty| PatternKind::Vector(Box::new(ty))),
+        struct_pattern,
+    ))
+    .parse_next(input)
+}
+
+fn struct_pattern(input: &mut &str) -> ModalResult<PatternKind> {
+    let wildcard_or_identifier = || {
+        alt((
+            '*'.value(None),
+            identifier.map(|ident| Some(Identifier(ident.into()))),
+        ))
+    };
+
+    let (address, _, module, _, name) = (
+        address,
+        "::",
+        wildcard_or_identifier(),
+        "::",
+        wildcard_or_identifier(),
+    )
+        .parse_next(input)?;
+    let type_params = type_params(type_pattern).parse_next(input)?;
+
+    Ok(PatternKind::Struct {
+        address,
+        module,
+        name,
+        type_params,
+    })
 }
 
 //TODO add proptests
@@ -109,6 +232,7 @@ mod tests {
     use super::*;
 
     use std::str::FromStr;
+    use winnow::combinator::eof;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -229,4 +353,64 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parse_error_position() {
+        for (input, position) in [
+            ("", 0),
+            ("u8 ", 2),
+            ("vector<u8", 9),
+            ("vector<foo>", 7),
+            ("0x1::M::S<u8, foo>", 12),
+            ("0x1::M::S<u8>>", 13),
+            ("0x1::9M::S", 5),
+        ] {
+            let error = parse_type_tag(input).unwrap_err();
+            assert_eq!(error.input(), input);
+            assert_eq!(
+                error.position(),
+                position,
+                "input: {input:?}, error: {error}"
+            );
+        }
+
+        assert_eq!(Identifier::new("foo-bar").unwrap_err().position(), 3);
+    }
+
+    #[test]
+    fn test_open_type_tag() {
+        for s in [
+            "T0",
+            "vector<T1>",
+            "0x2::coin::Coin<T0>",
+            "0x2::dynamic_field::Field<T0, 0x2::coin::Coin<T12>>",
+            "0x1::T0::T1<u64>",
+        ] {
+            assert!(
+                parse_open_type_tag(s).is_ok(),
+                "Failed to parse open type {s}"
+            );
+        }
+
+        assert!(parse_open_type_tag("T").is_err());
+        assert!(parse_open_type_tag("T65536").is_err());
+        assert!(parse_type_tag("T0").is_err());
+    }
+
+    #[test]
+    fn test_type_pattern() {
+        for s in [
+            "*",
+            "vector<*>",
+            "0x2::coin::Coin<*>",
+            "0x2::coin::*",
+            "0x2::*::*",
+            "0x2::dynamic_field::Field<*, vector<u8>>",
+        ] {
+            assert!(parse_type_pattern(s).is_ok(), "Failed to parse pattern {s}");
+        }
+
+        assert!(parse_type_pattern("*::coin::Coin").is_err());
+        assert!(parse_type_pattern("0x2::coin::Coin<>").is_err());
+    }
 }
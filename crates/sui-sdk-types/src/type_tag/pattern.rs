@@ -0,0 +1,238 @@
+use super::parse;
+use super::write_address;
+use super::write_type_params;
+use super::Address;
+use super::Identifier;
+use super::StructTag;
+use super::TypeParseError;
+use super::TypeTag;
+
+/// A pattern which matches types, using `*` as a wildcard
+///
+/// Patterns are parsed from the same syntax as [`TypeTag`], with the following additions:
+///
+/// - `*` in place of a type matches any type, e.g. `0x2::coin::Coin<*>` matches a coin of any
+///   type.
+/// - `*` in place of a module or struct name matches any name, e.g. `0x2::coin::*` matches any
+///   struct defined in the `0x2::coin` module.
+/// - A struct without type parameters matches any instantiation of that struct, e.g.
+///   `0x2::dynamic_field::Field` matches dynamic fields with any name and value types.
+///
+/// ```
+/// use sui_sdk_types::StructTag;
+/// use sui_sdk_types::TypePattern;
+///
+/// let pattern: TypePattern = "0x2::coin::Coin<*>".parse().unwrap();
+/// assert!(pattern.matches_struct(&StructTag::gas_coin()));
+/// assert!(!pattern.matches_struct(&StructTag::clock()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypePattern(pub(super) PatternKind);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) enum PatternKind {
+    Any,
+    Primitive(TypeTag),
+    Vector(Box<PatternKind>),
+    Struct {
+        address: Address,
+        module: Option<Identifier>,
+        name: Option<Identifier>,
+        type_params: Option<Vec<PatternKind>>,
+    },
+}
+
+impl TypePattern {
+    /// A pattern which matches any type.
+    pub fn any() -> Self {
+        Self(PatternKind::Any)
+    }
+
+    /// Returns true if `type_tag` matches this pattern.
+    pub fn matches(&self, type_tag: &TypeTag) -> bool {
+        self.0.matches(type_tag)
+    }
+
+    /// Returns true if `struct_tag` matches this pattern.
+    pub fn matches_struct(&self, struct_tag: &StructTag) -> bool {
+        self.0.matches_struct(struct_tag)
+    }
+}
+
+impl PatternKind {
+    fn matches(&self, type_tag: &TypeTag) -> bool {
+        match (self, type_tag) {
+            (PatternKind::Any, _) => true,
+            (PatternKind::Primitive(primitive), type_tag) => primitive == type_tag,
+            (PatternKind::Vector(pattern), TypeTag::Vector(type_tag)) => pattern.matches(type_tag),
+            (PatternKind::Struct { .. }, TypeTag::Struct(struct_tag)) => {
+                self.matches_struct(struct_tag)
+            }
+            _ => false,
+        }
+    }
+
+    fn matches_struct(&self, struct_tag: &StructTag) -> bool {
+        match self {
+            PatternKind::Any => true,
+            PatternKind::Struct {
+                address,
+                module,
+                name,
+                type_params,
+            } => {
+                address == &struct_tag.address
+                    && module.as_ref().is_none_or(|m| m == &struct_tag.module)
+                    && name.as_ref().is_none_or(|n| n == &struct_tag.name)
+                    && type_params.as_ref().is_none_or(|type_params| {
+                        type_params.len() == struct_tag.type_params.len()
+                            && type_params
+                                .iter()
+                                .zip(&struct_tag.type_params)
+                                .all(|(pattern, type_tag)| pattern.matches(type_tag))
+                    })
+            }
+            PatternKind::Primitive(_) | PatternKind::Vector(_) => false,
+        }
+    }
+}
+
+impl From<TypeTag> for TypePattern {
+    fn from(value: TypeTag) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<StructTag> for TypePattern {
+    fn from(value: StructTag) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<TypeTag> for PatternKind {
+    fn from(value: TypeTag) -> Self {
+        match value {
+            TypeTag::Vector(t) => PatternKind::Vector(Box::new((*t).into())),
+            TypeTag::Struct(s) => (*s).into(),
+            primitive => PatternKind::Primitive(primitive),
+        }
+    }
+}
+
+impl From<StructTag> for PatternKind {
+    fn from(value: StructTag) -> Self {
+        // Move structs have a fixed number of type parameters, so a struct without any type
+        // parameters is matched exactly without needing to check them.
+        let type_params = (!value.type_params.is_empty())
+            .then(|| value.type_params.into_iter().map(Into::into).collect());
+
+        PatternKind::Struct {
+            address: value.address,
+            module: Some(value.module),
+            name: Some(value.name),
+            type_params,
+        }
+    }
+}
+
+impl std::fmt::Display for TypePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::fmt::Display for PatternKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternKind::Any => f.write_str("*"),
+            PatternKind::Primitive(primitive) => primitive.fmt(f),
+            PatternKind::Vector(pattern) => {
+                f.write_str("vector<")?;
+                pattern.fmt(f)?;
+                f.write_str(">")
+            }
+            PatternKind::Struct {
+                address,
+                module,
+                name,
+                type_params,
+            } => {
+                write_address(f, address)?;
+                match module {
+                    Some(module) => write!(f, "::{module}")?,
+                    None => f.write_str("::*")?,
+                }
+                match name {
+                    Some(name) => write!(f, "::{name}")?,
+                    None => f.write_str("::*")?,
+                }
+                match type_params {
+                    Some(type_params) => write_type_params(f, type_params),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for TypePattern {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_type_pattern(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn wildcards() {
+        let coin_type: TypeTag = "0x1234::token::TOKEN".parse().unwrap();
+        let coin: TypeTag = StructTag::coin(coin_type.clone()).into();
+        let field: TypeTag = StructTag::dynamic_field(TypeTag::U64, coin.clone()).into();
+
+        let cases = [
+            ("*", vec![true, true, true, true]),
+            ("u64", vec![false, false, false, true]),
+            ("0x2::coin::Coin<*>", vec![false, true, false, false]),
+            (
+                "0x2::coin::Coin<0x1234::token::TOKEN>",
+                vec![false, true, false, false],
+            ),
+            ("0x2::coin::Coin<u64>", vec![false, false, false, false]),
+            ("0x2::coin::*", vec![false, true, false, false]),
+            ("0x2::*::*", vec![false, true, true, false]),
+            ("0x2::dynamic_field::Field", vec![false, false, true, false]),
+            (
+                "0x2::dynamic_field::Field<u64, 0x2::coin::*>",
+                vec![false, false, true, false],
+            ),
+            (
+                "0x2::dynamic_field::Field<*>",
+                vec![false, false, false, false],
+            ),
+        ];
+
+        for (pattern, expected) in cases {
+            let pattern: TypePattern = pattern.parse().unwrap();
+            let matches = [&coin_type, &coin, &field, &TypeTag::U64]
+                .map(|type_tag| pattern.matches(type_tag))
+                .to_vec();
+            assert_eq!(matches, expected, "pattern: {pattern}");
+        }
+    }
+
+    #[test]
+    fn from_type_tag() {
+        let tag: TypeTag = "vector<0x2::coin::Coin<0x2::bfc::BFC>>".parse().unwrap();
+        let pattern = TypePattern::from(tag.clone());
+        assert!(pattern.matches(&tag));
+        assert_eq!(format!("{pattern:#}"), tag.to_short_string());
+        assert_eq!(pattern.to_string().parse::<TypePattern>().unwrap(), pattern);
+    }
+}
@@ -1,11 +1,357 @@
-// Before we can expose this in the public interface it likely needs to be wrapped so that the type
-// from our dependency doesn't leak
-pub(crate) type U256 = bnum::BUintD8<32>;
+type Inner = bnum::BUintD8<32>;
+
+/// An unsigned 256-bit integer, the representation of a Move `u256`
+///
+/// All arithmetic is available in checked and saturating variants. The arithmetic operators panic
+/// on overflow, division by zero, or shifting by 256 or more bits, regardless of whether overflow
+/// checks are enabled.
+///
+/// # BCS
+///
+/// The BCS serialized form for this type is defined by the following ABNF:
+///
+/// ```text
+/// u256 = 32OCTET ; little-endian
+/// ```
+///
+/// In human readable formats, e.g. JSON, a `U256` is serialized as a decimal string.
+///
+/// ```
+/// use sui_sdk_types::U256;
+///
+/// let reserve: U256 = "340282366920938463463374607431768211456".parse().unwrap();
+/// let amount = reserve.checked_mul(U256::from(3u64)).unwrap() / U256::from(4u64);
+///
+/// assert_eq!(
+///     amount.to_string(),
+///     "255211775190703847597530955573826158592"
+/// );
+/// assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+/// assert_eq!("0xff".parse::<U256>().unwrap(), U256::from(255u8));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "proptest", derive(test_strategy::Arbitrary))]
+pub struct U256(
+    #[cfg_attr(
+        feature = "proptest",
+        strategy(proptest::strategy::Strategy::prop_map(
+            proptest::arbitrary::any::<[u8; 32]>(),
+            |bytes| Inner::from_le(Inner::from_digits(bytes))
+        ))
+    )]
+    Inner,
+);
+
+impl U256 {
+    pub const LENGTH: usize = 32;
+    pub const ZERO: Self = Self(Inner::ZERO);
+    pub const ONE: Self = Self(Inner::ONE);
+    pub const MAX: Self = Self(Inner::MAX);
+
+    pub const fn from_le_bytes(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(Inner::from_le(Inner::from_digits(bytes)))
+    }
+
+    pub const fn from_be_bytes(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(Inner::from_be(Inner::from_digits(bytes)))
+    }
+
+    pub const fn to_le_bytes(self) -> [u8; Self::LENGTH] {
+        *self.0.to_le().digits()
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; Self::LENGTH] {
+        *self.0.to_be().digits()
+    }
+
+    /// Parse a string of digits in the given `radix`, which must be in the range `2..=36`.
+    pub const fn from_str_radix(s: &str, radix: u32) -> Result<Self, U256ParseError> {
+        match Inner::from_str_radix(s, radix) {
+            Ok(inner) => Ok(Self(inner)),
+            Err(e) => Err(U256ParseError(e)),
+        }
+    }
+
+    /// Format as a string of digits in the given `radix`, which must be in the range `2..=36`.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        self.0.to_str_radix(radix)
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_mul(rhs.0) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_div(rhs.0) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_rem(rhs.0) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_pow(self, exp: u32) -> Option<Self> {
+        match self.0.checked_pow(exp) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    /// Shift left by `rhs` bits, returning `None` if `rhs` is 256 or more.
+    ///
+    /// As with Move's `<<`, bits shifted out of the value are discarded.
+    pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
+        match self.0.checked_shl(rhs) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    /// Shift right by `rhs` bits, returning `None` if `rhs` is 256 or more.
+    pub const fn checked_shr(self, rhs: u32) -> Option<Self> {
+        match self.0.checked_shr(rhs) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    pub const fn saturating_mul(self, rhs: Self) -> Self {
+        Self(self.0.saturating_mul(rhs.0))
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl From<$primitive> for U256 {
+                fn from(value: $primitive) -> Self {
+                    let mut bytes = [0; Self::LENGTH];
+                    bytes[..std::mem::size_of::<$primitive>()].copy_from_slice(&value.to_le_bytes());
+                    Self::from_le_bytes(bytes)
+                }
+            }
+
+            impl TryFrom<U256> for $primitive {
+                type Error = U256OverflowError;
+
+                fn try_from(value: U256) -> Result<Self, Self::Error> {
+                    const SIZE: usize = std::mem::size_of::<$primitive>();
+                    let bytes = value.to_le_bytes();
+                    if bytes[SIZE..].iter().any(|byte| *byte != 0) {
+                        return Err(U256OverflowError);
+                    }
+                    Ok(<$primitive>::from_le_bytes(bytes[..SIZE].try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(u8, u16, u32, u64, u128);
+
+impl From<U256> for [u8; U256::LENGTH] {
+    /// The little-endian bytes of `value`.
+    fn from(value: U256) -> Self {
+        value.to_le_bytes()
+    }
+}
+
+impl From<[u8; U256::LENGTH]> for U256 {
+    /// Interpret `bytes` as a little-endian integer.
+    fn from(bytes: [u8; U256::LENGTH]) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+}
+
+macro_rules! impl_op {
+    ($trait:ident, $method:ident, $checked:ident, $rhs:ty, $message:literal) => {
+        impl std::ops::$trait<$rhs> for U256 {
+            type Output = Self;
+
+            fn $method(self, rhs: $rhs) -> Self {
+                self.$checked(rhs).expect($message)
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, checked_add, Self, "attempt to add with overflow");
+impl_op!(
+    Sub,
+    sub,
+    checked_sub,
+    Self,
+    "attempt to subtract with overflow"
+);
+impl_op!(
+    Mul,
+    mul,
+    checked_mul,
+    Self,
+    "attempt to multiply with overflow"
+);
+impl_op!(Div, div, checked_div, Self, "attempt to divide by zero");
+impl_op!(
+    Rem,
+    rem,
+    checked_rem,
+    Self,
+    "attempt to calculate the remainder with a divisor of zero"
+);
+impl_op!(
+    Shl,
+    shl,
+    checked_shl,
+    u32,
+    "attempt to shift left with overflow"
+);
+impl_op!(
+    Shr,
+    shr,
+    checked_shr,
+    u32,
+    "attempt to shift right with overflow"
+);
+
+impl std::str::FromStr for U256 {
+    type Err = U256ParseError;
+
+    /// Parse a decimal string, or a hex string prefixed with `0x`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(s, 10),
+        }
+    }
+}
+
+impl std::fmt::Display for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Debug for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("U256")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl std::fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::UpperHex for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct U256ParseError(bnum::errors::ParseIntError);
+
+impl std::fmt::Display for U256ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for U256ParseError {}
+
+/// Error returned when a [`U256`] is too large to be converted into a smaller integer type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U256OverflowError;
+
+impl std::fmt::Display for U256OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("u256 value out of range of the target integer type")
+    }
+}
+
+impl std::error::Error for U256OverflowError {}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod serialization {
+    use super::U256;
+
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    impl Serialize for U256 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                self.to_le_bytes().serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for U256 {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                let s = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            } else {
+                <[u8; U256::LENGTH]>::deserialize(deserializer).map(U256::from_le_bytes)
+            }
+        }
+    }
+}
 
 // This is a constant time assert to ensure that the backing storage for U256 is 32 bytes long
 #[allow(unused)]
 const ASSERT_32_BYTES: () = {
-    let u256 = U256::ZERO;
+    let u256 = Inner::ZERO;
 
     let _digits: &[u8; 32] = u256.digits();
 };
@@ -27,7 +373,7 @@ const ASSERT_ENDIANNESS: () = {
         true
     }
 
-    let one_platform = U256::ONE;
+    let one_platform = Inner::ONE;
     let one_le = {
         let mut buf = [0; 32];
         buf[0] = 1;
@@ -51,13 +397,17 @@ const ASSERT_ENDIANNESS: () = {
     // From little endian
     assert!(const_bytes_equal(
         one_platform.digits().as_slice(),
-        U256::from_le(U256::from_digits(one_le)).digits().as_slice()
+        Inner::from_le(Inner::from_digits(one_le))
+            .digits()
+            .as_slice()
     ));
 
     // From big endian
     assert!(const_bytes_equal(
         one_platform.digits().as_slice(),
-        U256::from_be(U256::from_digits(one_be)).digits().as_slice()
+        Inner::from_be(Inner::from_digits(one_be))
+            .digits()
+            .as_slice()
     ));
 };
 
@@ -74,7 +424,6 @@ mod test {
 
     #[test]
     fn endianness() {
-        let one_platform = U256::ONE;
         let one_le = {
             let mut buf = [0; 32];
             buf[0] = 1;
@@ -87,18 +436,82 @@ mod test {
             buf
         };
 
-        // To little endian
-        let le = one_platform.to_le();
-        assert_eq!(one_le, *le.digits());
+        assert_eq!(one_le, U256::ONE.to_le_bytes());
+        assert_eq!(one_be, U256::ONE.to_be_bytes());
+        assert_eq!(U256::ONE, U256::from_le_bytes(one_le));
+        assert_eq!(U256::ONE, U256::from_be_bytes(one_be));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let two = U256::from(2u8);
+        assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+        assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+        assert_eq!(U256::MAX.checked_mul(two), None);
+        assert_eq!(U256::ONE.checked_div(U256::ZERO), None);
+        assert_eq!(U256::ONE.checked_rem(U256::ZERO), None);
+        assert_eq!(U256::ONE.checked_shl(256), None);
+        assert_eq!(U256::ONE.checked_shr(256), None);
+        assert_eq!(two.checked_pow(256), None);
+
+        assert_eq!(U256::MAX.saturating_add(U256::ONE), U256::MAX);
+        assert_eq!(U256::ZERO.saturating_sub(U256::ONE), U256::ZERO);
+
+        assert_eq!((U256::ONE << 255) >> 254, two);
+        assert_eq!(U256::MAX << 255, U256::ONE << 255);
+        assert_eq!(U256::from(7u64) % two, U256::ONE);
+        assert_eq!(
+            U256::from(u128::MAX) + U256::ONE,
+            two.checked_pow(128).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_overflow() {
+        let _ = U256::MAX + U256::ONE;
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(u128::try_from(U256::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(
+            u128::try_from(U256::from(u128::MAX) + U256::ONE),
+            Err(U256OverflowError)
+        );
+        assert_eq!(u8::try_from(U256::from(255u64)), Ok(255));
+        assert_eq!(u8::try_from(U256::from(256u64)), Err(U256OverflowError));
+    }
+
+    #[test]
+    fn formatting() {
+        let value = U256::from(0xabcdu64);
+        assert_eq!(value.to_string(), "43981");
+        assert_eq!(format!("{value:x}"), "abcd");
+        assert_eq!(format!("{value:#X}"), "0xABCD");
+        assert_eq!(format!("{value:?}"), "U256(43981)");
+        assert_eq!(U256::from_str("0xabcd").unwrap(), value);
+        assert_eq!(U256::from_str("43981").unwrap(), value);
+        assert!(U256::from_str("0x").is_err());
+        assert!(U256::from_str("-1").is_err());
+        assert_eq!(U256::MAX.to_str_radix(16), "f".repeat(64));
+    }
 
-        // To big endian
-        let be = one_platform.to_be();
-        assert_eq!(one_be, *be.digits());
+    #[test]
+    fn serialization() {
+        let value = U256::from(u64::MAX) + U256::ONE;
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            "\"18446744073709551616\""
+        );
+        assert_eq!(
+            serde_json::from_str::<U256>("\"18446744073709551616\"").unwrap(),
+            value
+        );
 
-        // From little endian
-        assert_eq!(one_platform, U256::from_le(U256::from_digits(one_le)));
-        // From big endian
-        assert_eq!(one_platform, U256::from_be(U256::from_digits(one_be)));
+        let bcs = bcs::to_bytes(&value).unwrap();
+        assert_eq!(bcs, value.to_le_bytes());
+        assert_eq!(bcs::from_bytes::<U256>(&bcs).unwrap(), value);
     }
 
     #[proptest]
@@ -127,4 +540,24 @@ mod test {
         assert_eq!(from_str, u256);
         assert_eq!(radix10, from_str.to_string());
     }
+
+    #[proptest]
+    fn arithmetic_matches_biguint(a: U256, b: U256) {
+        let big = |value: U256| BigUint::from_bytes_le(&value.to_le_bytes());
+        let max = big(U256::MAX);
+
+        let sum = big(a) + big(b);
+        assert_eq!(a.checked_add(b).map(big), (sum <= max).then_some(sum));
+
+        let product = big(a) * big(b);
+        assert_eq!(
+            a.checked_mul(b).map(big),
+            (product <= max).then_some(product)
+        );
+
+        if !b.is_zero() {
+            assert_eq!(big(a / b), big(a) / big(b));
+            assert_eq!(big(a % b), big(a) % big(b));
+        }
+    }
 }
@@ -0,0 +1,1174 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Analysis of what a transaction does on behalf of its sender, to inform the decision to sign it.
+//!
+//! A [`TransactionAnalyzer`] inspects a [`Transaction`] and produces a [`TransactionAnalysis`]
+//! listing the objects and coins the sender gives up, the packages it calls, publishes or
+//! upgrades, who pays for gas, and a list of [`Risk`]s which a policy engine can evaluate.
+//!
+//! The analysis is static: it only knows what can be inferred from the transaction itself, along
+//! with any objects and dry run balance changes provided to the analyzer. Move calls may move
+//! value in ways which can't be determined statically, so providing the balance changes from a
+//! dry run of the transaction is recommended.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sui_types::framework::Coin;
+use sui_types::Address;
+use sui_types::Argument;
+use sui_types::BalanceChange;
+use sui_types::Command;
+use sui_types::Identifier;
+use sui_types::Input;
+use sui_types::Object;
+use sui_types::ObjectId;
+use sui_types::ProgrammableTransaction;
+use sui_types::StructTag;
+use sui_types::Transaction;
+use sui_types::TransactionExpiration;
+use sui_types::TransactionKind;
+use sui_types::TypeTag;
+use sui_types::Version;
+
+/// Analyzes transactions before they are signed.
+///
+/// ```
+/// use sui_transaction_builder::analyzer::RiskLevel;
+/// use sui_transaction_builder::analyzer::TransactionAnalyzer;
+/// # use sui_types::Transaction;
+///
+/// # fn check(transaction: &Transaction, dry_run_balance_changes: Vec<sui_types::BalanceChange>) {
+/// let analysis = TransactionAnalyzer::new()
+///     .with_balance_changes(dry_run_balance_changes)
+///     .analyze(transaction);
+///
+/// if analysis.risk_level() >= Some(RiskLevel::High) {
+///     // require additional approval
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TransactionAnalyzer {
+    balance_changes: Option<Vec<BalanceChange>>,
+    objects: HashMap<ObjectId, KnownObject>,
+}
+
+#[derive(Clone, Debug)]
+struct KnownObject {
+    type_: StructTag,
+    /// The balance of the object, if it is a coin.
+    balance: Option<u64>,
+}
+
+impl TransactionAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the balance changes from a dry run of the transaction to determine the coins leaving
+    /// the sender, instead of estimating them from the transaction's commands.
+    pub fn with_balance_changes<I>(mut self, balance_changes: I) -> Self
+    where
+        I: IntoIterator<Item = BalanceChange>,
+    {
+        self.balance_changes = Some(balance_changes.into_iter().collect());
+        self
+    }
+
+    /// Provide the current state of an object used by the transaction, allowing the analyzer to
+    /// identify coins, their balances, and upgrade caps.
+    pub fn with_object(mut self, object: &Object) -> Self {
+        if let Some(move_struct) = object.as_struct() {
+            self.objects.insert(
+                object.object_id(),
+                KnownObject {
+                    type_: move_struct.object_type().clone(),
+                    balance: Coin::try_from_object(object).map(|coin| coin.balance()),
+                },
+            );
+        }
+        self
+    }
+
+    /// Provide the current state of the objects used by the transaction, see
+    /// [`TransactionAnalyzer::with_object`].
+    pub fn with_objects<'a, I>(self, objects: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Object>,
+    {
+        objects
+            .into_iter()
+            .fold(self, |analyzer, object| analyzer.with_object(object))
+    }
+
+    /// Analyze `transaction`.
+    pub fn analyze(&self, transaction: &Transaction) -> TransactionAnalysis {
+        let gas = GasSummary {
+            owner: transaction.gas_payment.owner,
+            sponsored: transaction.gas_payment.owner != transaction.sender,
+            budget: transaction.gas_payment.budget,
+            price: transaction.gas_payment.price,
+            payment: transaction
+                .gas_payment
+                .objects
+                .iter()
+                .map(|object| *object.object_id())
+                .collect(),
+        };
+
+        let mut analysis = TransactionAnalysis {
+            sender: transaction.sender,
+            gas,
+            expiration: transaction.expiration,
+            objects_given_up: Vec::new(),
+            coin_outflows: Vec::new(),
+            move_calls: Vec::new(),
+            packages_called: BTreeSet::new(),
+            published_packages: Vec::new(),
+            upgrades: Vec::new(),
+            upgrade_cap_usage: Vec::new(),
+            risks: Vec::new(),
+        };
+
+        match &transaction.kind {
+            TransactionKind::ProgrammableTransaction(ptb) => {
+                PtbAnalysis {
+                    analyzer: self,
+                    ptb,
+                    sender: transaction.sender,
+                    analysis: &mut analysis,
+                }
+                .run();
+            }
+            _ => analysis.push_risk(RiskKind::NotProgrammable),
+        }
+
+        if let Some(balance_changes) = &self.balance_changes {
+            analysis.coin_outflows = balance_changes
+                .iter()
+                .filter(|change| change.address == transaction.sender && change.amount < 0)
+                .map(|change| CoinOutflow {
+                    coin_type: Some(change.coin_type.clone()),
+                    amount: Some(change.amount.unsigned_abs()),
+                    source: CoinOutflowSource::BalanceChange,
+                })
+                .collect();
+        }
+        for outflow in analysis.coin_outflows.clone() {
+            match (outflow.coin_type, outflow.amount) {
+                (Some(coin_type), Some(amount)) => {
+                    analysis.push_risk(RiskKind::CoinOutflow { coin_type, amount })
+                }
+                _ => analysis.push_risk(RiskKind::UnknownCoinOutflow),
+            }
+        }
+
+        if analysis.gas.sponsored {
+            analysis.push_risk(RiskKind::SponsoredGas {
+                sponsor: analysis.gas.owner,
+            });
+        }
+        if analysis.expiration == TransactionExpiration::None {
+            analysis.push_risk(RiskKind::NoExpiration);
+        }
+
+        analysis
+    }
+}
+
+/// The result of analyzing a transaction with a [`TransactionAnalyzer`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TransactionAnalysis {
+    pub sender: Address,
+    pub gas: GasSummary,
+    pub expiration: TransactionExpiration,
+    /// Objects the sender transfers to another address, merges into another coin, or passes to a
+    /// Move function which may take them by value.
+    pub objects_given_up: Vec<ObjectOutflow>,
+    /// Coins leaving the sender, either from the dry run balance changes provided to the analyzer
+    /// or estimated from the coins transferred by the transaction.
+    pub coin_outflows: Vec<CoinOutflow>,
+    /// Every Move function called, in order.
+    pub move_calls: Vec<MoveCallSummary>,
+    pub packages_called: BTreeSet<ObjectId>,
+    pub published_packages: Vec<PublishSummary>,
+    pub upgrades: Vec<UpgradeSummary>,
+    pub upgrade_cap_usage: Vec<UpgradeCapUsage>,
+    pub risks: Vec<Risk>,
+}
+
+impl TransactionAnalysis {
+    /// The highest level of any identified risk, or `None` if no risks were identified.
+    pub fn risk_level(&self) -> Option<RiskLevel> {
+        self.risks.iter().map(|risk| risk.level).max()
+    }
+
+    fn push_risk(&mut self, kind: RiskKind) {
+        let risk = Risk {
+            level: kind.level(),
+            kind,
+        };
+        if !self.risks.contains(&risk) {
+            self.risks.push(risk);
+        }
+    }
+}
+
+/// Who pays for a transaction's gas and how much it may cost.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GasSummary {
+    /// The owner of the gas coins, who pays for the transaction.
+    pub owner: Address,
+    /// True if the gas is paid by someone other than the sender.
+    pub sponsored: bool,
+    /// The maximum amount of gas, in MIST, the transaction may use.
+    pub budget: u64,
+    pub price: u64,
+    pub payment: Vec<ObjectId>,
+}
+
+/// An object the sender gives up.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ObjectOutflow {
+    pub object: OutflowObject,
+    pub action: OutflowAction,
+}
+
+/// The object being given up.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutflowObject {
+    /// The transaction's gas coin.
+    GasCoin,
+    /// An object input to the transaction.
+    Input {
+        object_id: ObjectId,
+        version: Version,
+        /// The type of the object, if it was provided to the analyzer.
+        object_type: Option<StructTag>,
+    },
+    /// The result of an earlier command, e.g. a coin split from one of the sender's coins.
+    Result {
+        command: u16,
+        /// The index of the result, for commands with multiple results.
+        result: Option<u16>,
+    },
+}
+
+/// How an object is given up.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutflowAction {
+    /// Transferred to `recipient`, which is `None` if the recipient is computed by the
+    /// transaction.
+    Transferred { recipient: Option<Address> },
+    /// Merged into another coin.
+    Merged,
+    /// Passed to a Move function, which may take it by value.
+    PassedToMoveCall {
+        package: ObjectId,
+        module: Identifier,
+        function: Identifier,
+    },
+}
+
+/// A coin balance leaving the sender.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CoinOutflow {
+    /// The type of the coin, e.g. `0x2::bfc::BFC`, if known.
+    pub coin_type: Option<TypeTag>,
+    /// The amount leaving the sender, if known.
+    pub amount: Option<u128>,
+    pub source: CoinOutflowSource,
+}
+
+/// How a [`CoinOutflow`] was determined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinOutflowSource {
+    /// From the dry run balance changes provided to the analyzer.
+    BalanceChange,
+    /// Estimated from a coin transferred by the transaction.
+    Transfer,
+}
+
+/// A Move function called by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MoveCallSummary {
+    pub package: ObjectId,
+    pub module: Identifier,
+    pub function: Identifier,
+    pub type_arguments: Vec<TypeTag>,
+}
+
+/// A package published by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PublishSummary {
+    pub modules: usize,
+    pub dependencies: Vec<ObjectId>,
+}
+
+/// A package upgraded by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeSummary {
+    /// The package being upgraded.
+    pub package: ObjectId,
+    pub modules: usize,
+    pub dependencies: Vec<ObjectId>,
+}
+
+/// A use of a `0x2::package::UpgradeCap`, which controls the upgrades of a package.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeCapUsage {
+    /// The upgrade cap, if it is an input to the transaction.
+    pub cap: Option<ObjectId>,
+    pub action: UpgradeCapAction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UpgradeCapAction {
+    /// A function of the `0x2::package` module, e.g. `authorize_upgrade` or `make_immutable`.
+    PackageFunction { function: Identifier },
+    /// The upgrade cap is transferred to `recipient`.
+    Transferred { recipient: Option<Address> },
+    /// The upgrade cap is passed to a function outside of the `0x2::package` module.
+    PassedToMoveCall {
+        package: ObjectId,
+        module: Identifier,
+        function: Identifier,
+    },
+}
+
+/// A risk identified by a [`TransactionAnalyzer`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Risk {
+    pub level: RiskLevel,
+    #[serde(flatten)]
+    pub kind: RiskKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RiskKind {
+    /// The transaction isn't a programmable transaction, and so can't be analyzed.
+    NotProgrammable,
+    /// The gas coin, and so the sender's entire balance of it, is transferred.
+    TransfersGasCoin {
+        recipient: Option<Address>,
+    },
+    /// A coin leaves the sender but its type or amount can't be determined.
+    UnknownCoinOutflow,
+    /// `amount` of `coin_type` leaves the sender.
+    CoinOutflow {
+        coin_type: TypeTag,
+        amount: u128,
+    },
+    /// An object other than a coin, or an input object whose type is unknown, is transferred.
+    TransfersObject {
+        object_id: ObjectId,
+        recipient: Option<Address>,
+    },
+    /// The result of a command other than `SplitCoins`, which may be any object including a coin,
+    /// is transferred.
+    TransfersResult {
+        command: u16,
+        result: Option<u16>,
+        recipient: Option<Address>,
+    },
+    /// The gas coin is passed to a Move function, which may take any amount from it.
+    PassesGasCoinToMoveCall {
+        package: ObjectId,
+    },
+    /// An object owned by the sender is passed to a Move function, which may take it by value.
+    PassesObjectToMoveCall {
+        object_id: ObjectId,
+        package: ObjectId,
+    },
+    /// A function outside of the Move framework packages is called.
+    CallsPackage {
+        package: ObjectId,
+    },
+    PublishesPackage,
+    UpgradesPackage {
+        package: ObjectId,
+    },
+    UsesUpgradeCap {
+        cap: Option<ObjectId>,
+    },
+    /// A shared object is accessed mutably.
+    MutatesSharedObject {
+        object_id: ObjectId,
+    },
+    /// The gas is paid by `sponsor` rather than the sender.
+    SponsoredGas {
+        sponsor: Address,
+    },
+    /// The transaction doesn't expire, and so may be executed at any point in the future.
+    NoExpiration,
+}
+
+impl RiskKind {
+    pub fn level(&self) -> RiskLevel {
+        match self {
+            RiskKind::NotProgrammable
+            | RiskKind::TransfersGasCoin { .. }
+            | RiskKind::PassesGasCoinToMoveCall { .. }
+            | RiskKind::UnknownCoinOutflow
+            | RiskKind::UpgradesPackage { .. }
+            | RiskKind::UsesUpgradeCap { .. } => RiskLevel::High,
+            RiskKind::CoinOutflow { .. }
+            | RiskKind::TransfersObject { .. }
+            | RiskKind::TransfersResult { .. }
+            | RiskKind::PassesObjectToMoveCall { .. }
+            | RiskKind::CallsPackage { .. }
+            | RiskKind::PublishesPackage => RiskLevel::Medium,
+            RiskKind::MutatesSharedObject { .. }
+            | RiskKind::SponsoredGas { .. }
+            | RiskKind::NoExpiration => RiskLevel::Low,
+        }
+    }
+}
+
+/// Functions of the `0x2::package` module which make use of an `UpgradeCap`.
+const UPGRADE_CAP_FUNCTIONS: &[&str] = &[
+    "authorize_upgrade",
+    "commit_upgrade",
+    "make_immutable",
+    "only_additive_upgrades",
+    "only_dep_upgrades",
+];
+
+struct PtbAnalysis<'a> {
+    analyzer: &'a TransactionAnalyzer,
+    ptb: &'a ProgrammableTransaction,
+    sender: Address,
+    analysis: &'a mut TransactionAnalysis,
+}
+
+impl<'a> PtbAnalysis<'a> {
+    fn run(mut self) {
+        for input in &self.ptb.inputs {
+            if let Input::Shared {
+                object_id,
+                mutable: true,
+                ..
+            } = input
+            {
+                self.analysis.push_risk(RiskKind::MutatesSharedObject {
+                    object_id: *object_id,
+                });
+            }
+        }
+
+        for (index, command) in self.ptb.commands.iter().enumerate() {
+            match command {
+                Command::MoveCall(call) => {
+                    self.analysis.move_calls.push(MoveCallSummary {
+                        package: call.package,
+                        module: call.module.clone(),
+                        function: call.function.clone(),
+                        type_arguments: call.type_arguments.clone(),
+                    });
+                    self.analysis.packages_called.insert(call.package);
+                    if !is_framework(&call.package) {
+                        self.analysis.push_risk(RiskKind::CallsPackage {
+                            package: call.package,
+                        });
+                    }
+
+                    if call
+                        .arguments
+                        .iter()
+                        .any(|argument| self.uses_gas(index, argument))
+                    {
+                        self.analysis.push_risk(RiskKind::PassesGasCoinToMoveCall {
+                            package: call.package,
+                        });
+                        self.coin_outflow(Some(gas_coin_type()), None);
+                        self.give_up(
+                            OutflowObject::GasCoin,
+                            OutflowAction::PassedToMoveCall {
+                                package: call.package,
+                                module: call.module.clone(),
+                                function: call.function.clone(),
+                            },
+                        );
+                    }
+
+                    let is_package_function = Address::from(call.package) == Address::TWO
+                        && call.module.as_str() == "package"
+                        && UPGRADE_CAP_FUNCTIONS.contains(&call.function.as_str());
+                    if is_package_function {
+                        let cap = call
+                            .arguments
+                            .first()
+                            .and_then(|argument| self.input_object(argument))
+                            .map(|(object_id, _)| object_id);
+                        self.use_upgrade_cap(
+                            cap,
+                            UpgradeCapAction::PackageFunction {
+                                function: call.function.clone(),
+                            },
+                        );
+                        continue;
+                    }
+
+                    for argument in &call.arguments {
+                        let Some((object_id, version)) = self.owned_input_object(argument) else {
+                            continue;
+                        };
+                        if self.is_upgrade_cap(&object_id) {
+                            self.use_upgrade_cap(
+                                Some(object_id),
+                                UpgradeCapAction::PassedToMoveCall {
+                                    package: call.package,
+                                    module: call.module.clone(),
+                                    function: call.function.clone(),
+                                },
+                            );
+                        }
+                        self.analysis.push_risk(RiskKind::PassesObjectToMoveCall {
+                            object_id,
+                            package: call.package,
+                        });
+                        self.give_up(
+                            self.input_outflow(object_id, version),
+                            OutflowAction::PassedToMoveCall {
+                                package: call.package,
+                                module: call.module.clone(),
+                                function: call.function.clone(),
+                            },
+                        );
+                    }
+                }
+                Command::TransferObjects(transfer) => {
+                    let recipient = self.ptb.pure_address(&transfer.address);
+                    if recipient == Some(self.sender) {
+                        continue;
+                    }
+                    for object in &transfer.objects {
+                        self.transfer(index, object, recipient);
+                    }
+                }
+                Command::MergeCoins(merge) => {
+                    for coin in &merge.coins_to_merge {
+                        if let Some((object_id, version)) = self.owned_input_object(coin) {
+                            self.give_up(
+                                self.input_outflow(object_id, version),
+                                OutflowAction::Merged,
+                            );
+                        }
+                    }
+                }
+                Command::Publish(publish) => {
+                    self.analysis.published_packages.push(PublishSummary {
+                        modules: publish.modules.len(),
+                        dependencies: publish.dependencies.clone(),
+                    });
+                    self.analysis.push_risk(RiskKind::PublishesPackage);
+                }
+                Command::Upgrade(upgrade) => {
+                    self.analysis.upgrades.push(UpgradeSummary {
+                        package: upgrade.package,
+                        modules: upgrade.modules.len(),
+                        dependencies: upgrade.dependencies.clone(),
+                    });
+                    self.analysis.push_risk(RiskKind::UpgradesPackage {
+                        package: upgrade.package,
+                    });
+                }
+                Command::SplitCoins(_) | Command::MakeMoveVector(_) => {}
+            }
+        }
+    }
+
+    /// Account for command `index` transferring `object` to `recipient`.
+    fn transfer(&mut self, index: usize, object: &Argument, recipient: Option<Address>) {
+        let action = OutflowAction::Transferred { recipient };
+
+        match object {
+            Argument::Gas => {
+                self.analysis
+                    .push_risk(RiskKind::TransfersGasCoin { recipient });
+                self.coin_outflow(Some(gas_coin_type()), None);
+                self.give_up(OutflowObject::GasCoin, action);
+            }
+            Argument::Input(_) => {
+                let Some((object_id, version)) = self.owned_input_object(object) else {
+                    return;
+                };
+                match self.analyzer.objects.get(&object_id) {
+                    Some(known) if known.type_.is_coin().is_some() => {
+                        let coin_type = known.type_.is_coin().cloned();
+                        self.coin_outflow(coin_type, known.balance.map(u128::from));
+                    }
+                    known => {
+                        // Without the object's data it may well be a coin
+                        if known.is_none() {
+                            self.coin_outflow(None, None);
+                        }
+                        if self.is_upgrade_cap(&object_id) {
+                            self.use_upgrade_cap(
+                                Some(object_id),
+                                UpgradeCapAction::Transferred { recipient },
+                            );
+                        }
+                        self.analysis.push_risk(RiskKind::TransfersObject {
+                            object_id,
+                            recipient,
+                        });
+                    }
+                }
+                self.give_up(self.input_outflow(object_id, version), action);
+            }
+            Argument::Result(command) | Argument::NestedResult(command, _) => {
+                let result = match object {
+                    Argument::NestedResult(_, result) => Some(*result),
+                    _ => None,
+                };
+                if let Some(Command::SplitCoins(split)) = self.result_command(index, object) {
+                    let amount = self
+                        .ptb
+                        .split_amounts(split)
+                        .and_then(|amounts| match result {
+                            Some(result) => amounts.get(result as usize).copied().map(u128::from),
+                            None => Some(amounts.into_iter().map(u128::from).sum()),
+                        });
+                    let coin_type = self.coin_type(*command as usize, &split.coin);
+                    self.coin_outflow(coin_type, amount);
+                } else {
+                    self.coin_outflow(None, None);
+                    self.analysis.push_risk(RiskKind::TransfersResult {
+                        command: *command,
+                        result,
+                        recipient,
+                    });
+                }
+                self.give_up(
+                    OutflowObject::Result {
+                        command: *command,
+                        result,
+                    },
+                    action,
+                );
+            }
+        }
+    }
+
+    fn give_up(&mut self, object: OutflowObject, action: OutflowAction) {
+        self.analysis
+            .objects_given_up
+            .push(ObjectOutflow { object, action });
+    }
+
+    fn coin_outflow(&mut self, coin_type: Option<TypeTag>, amount: Option<u128>) {
+        // Dry run balance changes take precedence over estimates
+        if self.analyzer.balance_changes.is_some() {
+            return;
+        }
+        self.analysis.coin_outflows.push(CoinOutflow {
+            coin_type,
+            amount,
+            source: CoinOutflowSource::Transfer,
+        });
+    }
+
+    fn use_upgrade_cap(&mut self, cap: Option<ObjectId>, action: UpgradeCapAction) {
+        self.analysis
+            .upgrade_cap_usage
+            .push(UpgradeCapUsage { cap, action });
+        self.analysis.push_risk(RiskKind::UsesUpgradeCap { cap });
+    }
+
+    fn input_outflow(&self, object_id: ObjectId, version: Version) -> OutflowObject {
+        OutflowObject::Input {
+            object_id,
+            version,
+            object_type: self
+                .analyzer
+                .objects
+                .get(&object_id)
+                .map(|known| known.type_.clone()),
+        }
+    }
+
+    fn is_upgrade_cap(&self, object_id: &ObjectId) -> bool {
+        self.analyzer
+            .objects
+            .get(object_id)
+            .is_some_and(|known| known.type_ == StructTag::upgrade_cap())
+    }
+
+    /// The id and version of the object input referred to by `argument`, if any.
+    fn input_object(&self, argument: &Argument) -> Option<(ObjectId, Version)> {
+        let Argument::Input(index) = argument else {
+            return None;
+        };
+        match self.ptb.inputs.get(*index as usize)? {
+            Input::ImmutableOrOwned(object) | Input::Receiving(object) => {
+                Some((*object.object_id(), object.version()))
+            }
+            Input::Shared {
+                object_id,
+                initial_shared_version,
+                ..
+            } => Some((*object_id, *initial_shared_version)),
+            Input::Pure { .. } => None,
+        }
+    }
+
+    /// The id and version of the owned object input referred to by `argument`, if any.
+    fn owned_input_object(&self, argument: &Argument) -> Option<(ObjectId, Version)> {
+        let Argument::Input(index) = argument else {
+            return None;
+        };
+        match self.ptb.inputs.get(*index as usize)? {
+            Input::ImmutableOrOwned(object) => Some((*object.object_id(), object.version())),
+            _ => None,
+        }
+    }
+
+    /// The command whose result is referred to by `argument` when used by command `index`.
+    ///
+    /// Only earlier commands are returned, a command can't use its own result or the result of a
+    /// later command, so following results always terminates.
+    fn result_command(&self, index: usize, argument: &Argument) -> Option<&'a Command> {
+        let (Argument::Result(command) | Argument::NestedResult(command, _)) = argument else {
+            return None;
+        };
+        let command = *command as usize;
+        if command < index {
+            self.ptb.commands.get(command)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `argument`, as used by command `index`, is the gas coin, or a vector
+    /// containing it.
+    fn uses_gas(&self, index: usize, argument: &Argument) -> bool {
+        match argument {
+            Argument::Gas => true,
+            Argument::Input(_) => false,
+            Argument::Result(command) | Argument::NestedResult(command, _) => {
+                match self.result_command(index, argument) {
+                    Some(Command::MakeMoveVector(make)) => make
+                        .elements
+                        .iter()
+                        .any(|element| self.uses_gas(*command as usize, element)),
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// The type of the coin referred to by `argument`, as used by command `index`, if it can be
+    /// determined.
+    fn coin_type(&self, index: usize, argument: &Argument) -> Option<TypeTag> {
+        match argument {
+            Argument::Gas => Some(gas_coin_type()),
+            Argument::Input(_) => {
+                let (object_id, _) = self.input_object(argument)?;
+                self.analyzer
+                    .objects
+                    .get(&object_id)?
+                    .type_
+                    .is_coin()
+                    .cloned()
+            }
+            Argument::Result(command) | Argument::NestedResult(command, _) => {
+                match self.result_command(index, argument)? {
+                    Command::SplitCoins(split) => self.coin_type(*command as usize, &split.coin),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+fn gas_coin_type() -> TypeTag {
+    StructTag::gas_coin()
+        .is_coin()
+        .cloned()
+        .expect("gas coin is a coin")
+}
+
+/// Returns true if `package` is one of the Move framework packages, `0x1`, `0x2` or `0x3`.
+fn is_framework(package: &ObjectId) -> bool {
+    [Address::ONE, Address::TWO, Address::THREE].contains(&Address::from(*package))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::GasPayment;
+    use sui_types::MakeMoveVector;
+    use sui_types::MoveCall;
+    use sui_types::ObjectDigest;
+    use sui_types::ObjectReference;
+    use sui_types::SplitCoins;
+    use sui_types::TransferObjects;
+
+    use crate::unresolved;
+    use crate::Function;
+    use crate::Serialized;
+    use crate::TransactionBuilder;
+
+    fn finish(mut builder: TransactionBuilder, sender: Address) -> Transaction {
+        builder.set_sender(sender);
+        builder.set_gas_budget(10_000_000);
+        builder.set_gas_price(1000);
+        builder.add_gas_objects(vec![unresolved::Input::owned(
+            "0x100".parse().unwrap(),
+            1,
+            ObjectDigest::ZERO,
+        )]);
+        builder.finish().unwrap()
+    }
+
+    #[test]
+    fn split_and_transfer() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let recipient: Address = "0xb0b".parse().unwrap();
+
+        let mut builder = TransactionBuilder::new();
+        let amount = builder.input(Serialized(&5_000u64));
+        let coins = builder.split_coins(builder.gas(), vec![amount]);
+        let to_self = builder.split_coins(builder.gas(), vec![amount]);
+        let recipient_arg = builder.input(Serialized(&recipient));
+        let sender_arg = builder.input(Serialized(&sender));
+        builder.transfer_objects(vec![coins], recipient_arg);
+        builder.transfer_objects(vec![to_self], sender_arg);
+        let transaction = finish(builder, sender);
+
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert_eq!(
+            analysis.coin_outflows,
+            vec![CoinOutflow {
+                coin_type: Some(gas_coin_type()),
+                amount: Some(5_000),
+                source: CoinOutflowSource::Transfer,
+            }]
+        );
+        assert_eq!(
+            analysis.objects_given_up,
+            vec![ObjectOutflow {
+                object: OutflowObject::Result {
+                    command: 0,
+                    result: None,
+                },
+                action: OutflowAction::Transferred {
+                    recipient: Some(recipient),
+                },
+            }]
+        );
+        assert_eq!(analysis.risk_level(), Some(RiskLevel::Medium));
+        assert!(!analysis.gas.sponsored);
+
+        // Balance changes from a dry run replace the estimate
+        let analysis = TransactionAnalyzer::new()
+            .with_balance_changes([
+                BalanceChange {
+                    address: sender,
+                    coin_type: gas_coin_type(),
+                    amount: -6_000,
+                },
+                BalanceChange {
+                    address: recipient,
+                    coin_type: gas_coin_type(),
+                    amount: 5_000,
+                },
+            ])
+            .analyze(&transaction);
+        assert_eq!(
+            analysis.coin_outflows,
+            vec![CoinOutflow {
+                coin_type: Some(gas_coin_type()),
+                amount: Some(6_000),
+                source: CoinOutflowSource::BalanceChange,
+            }]
+        );
+    }
+
+    #[test]
+    fn gas_coin_and_objects() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let sponsor: Address = "0x5b0".parse().unwrap();
+        let recipient: Address = "0xb0b".parse().unwrap();
+        let nft = ObjectReference::new("0x1234".parse().unwrap(), 9, ObjectDigest::ZERO);
+
+        let transaction = Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    Input::ImmutableOrOwned(nft.clone()),
+                    Input::Pure {
+                        value: recipient.as_bytes().to_vec(),
+                    },
+                ],
+                commands: vec![
+                    Command::MoveCall(MoveCall {
+                        package: "0xdee9".parse().unwrap(),
+                        module: Identifier::new("market").unwrap(),
+                        function: Identifier::new("list").unwrap(),
+                        type_arguments: vec![],
+                        arguments: vec![Argument::Input(0)],
+                    }),
+                    Command::TransferObjects(TransferObjects {
+                        objects: vec![Argument::Gas],
+                        address: Argument::Input(1),
+                    }),
+                ],
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![],
+                owner: sponsor,
+                price: 1000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        };
+
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert_eq!(analysis.objects_given_up.len(), 2);
+        assert_eq!(
+            analysis.objects_given_up[0].object,
+            OutflowObject::Input {
+                object_id: *nft.object_id(),
+                version: 9,
+                object_type: None,
+            }
+        );
+        assert_eq!(analysis.objects_given_up[1].object, OutflowObject::GasCoin);
+        assert_eq!(
+            analysis.packages_called,
+            BTreeSet::from(["0xdee9".parse().unwrap()])
+        );
+        assert!(analysis.gas.sponsored);
+        assert_eq!(analysis.risk_level(), Some(RiskLevel::High));
+
+        let kinds = analysis
+            .risks
+            .iter()
+            .map(|risk| risk.kind.clone())
+            .collect::<Vec<_>>();
+        assert!(kinds.contains(&RiskKind::TransfersGasCoin {
+            recipient: Some(recipient)
+        }));
+        assert!(kinds.contains(&RiskKind::UnknownCoinOutflow));
+        assert!(kinds.contains(&RiskKind::SponsoredGas { sponsor }));
+        assert!(kinds.contains(&RiskKind::NoExpiration));
+
+        let json = serde_json::to_value(&analysis.risks[0]).unwrap();
+        assert_eq!(json["level"], "medium");
+        assert_eq!(json["kind"], "calls_package");
+    }
+
+    #[test]
+    fn gas_coin_split_by_move_call() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let attacker: Address = "0xbad".parse().unwrap();
+
+        // `0x2::coin::split(Gas, amount)` returns a coin which isn't the result of a `SplitCoins`
+        let mut builder = TransactionBuilder::new();
+        let amount = builder.input(Serialized(&1_000_000u64));
+        let coin = builder.move_call(
+            Function::new(
+                Address::TWO,
+                Identifier::new("coin").unwrap(),
+                Identifier::new("split").unwrap(),
+                vec![gas_coin_type()],
+            ),
+            vec![builder.gas(), amount],
+        );
+        let attacker_arg = builder.input(Serialized(&attacker));
+        builder.transfer_objects(vec![coin], attacker_arg);
+        let transaction = finish(builder, sender);
+
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert_eq!(analysis.risk_level(), Some(RiskLevel::High));
+        let kinds = analysis
+            .risks
+            .iter()
+            .map(|risk| risk.kind.clone())
+            .collect::<Vec<_>>();
+        assert!(kinds.contains(&RiskKind::PassesGasCoinToMoveCall {
+            package: Address::TWO.into(),
+        }));
+        assert!(kinds.contains(&RiskKind::UnknownCoinOutflow));
+        assert!(kinds.contains(&RiskKind::TransfersResult {
+            command: 0,
+            result: None,
+            recipient: Some(attacker),
+        }));
+        assert_eq!(analysis.objects_given_up.len(), 2);
+        assert_eq!(analysis.objects_given_up[0].object, OutflowObject::GasCoin);
+        assert_eq!(
+            analysis.objects_given_up[1].object,
+            OutflowObject::Result {
+                command: 0,
+                result: None,
+            }
+        );
+    }
+
+    #[test]
+    fn transfer_of_unknown_input() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let recipient: Address = "0xb0b".parse().unwrap();
+        let object_id: ObjectId = "0xc0171".parse().unwrap();
+
+        let mut builder = TransactionBuilder::new();
+        let object = builder.input(unresolved::Input::owned(object_id, 2, ObjectDigest::ZERO));
+        let recipient_arg = builder.input(Serialized(&recipient));
+        builder.transfer_objects(vec![object], recipient_arg);
+        let transaction = finish(builder, sender);
+
+        // Without the object's data, it may be a coin of any amount
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert_eq!(analysis.risk_level(), Some(RiskLevel::High));
+        assert!(analysis
+            .risks
+            .iter()
+            .any(|risk| risk.kind == RiskKind::UnknownCoinOutflow));
+        assert!(analysis.risks.iter().any(|risk| risk.kind
+            == RiskKind::TransfersObject {
+                object_id,
+                recipient: Some(recipient),
+            }));
+    }
+
+    #[test]
+    fn upgrade() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let cap: ObjectId = "0xcab".parse().unwrap();
+        let package: ObjectId = "0xbeef".parse().unwrap();
+        let function = |name| {
+            Function::new(
+                Address::TWO,
+                Identifier::new("package").unwrap(),
+                Identifier::new(name).unwrap(),
+                vec![],
+            )
+        };
+
+        let mut builder = TransactionBuilder::new();
+        let cap_arg = builder.input(unresolved::Input::owned(cap, 3, ObjectDigest::ZERO));
+        let policy = builder.input(Serialized(&0u8));
+        let digest = builder.input(Serialized(&vec![0u8; 32]));
+        let ticket =
+            builder.move_call(function("authorize_upgrade"), vec![cap_arg, policy, digest]);
+        let receipt = builder.upgrade(vec![vec![0; 4]], vec![Address::TWO.into()], package, ticket);
+        builder.move_call(function("commit_upgrade"), vec![cap_arg, receipt]);
+        let transaction = finish(builder, sender);
+
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert_eq!(
+            analysis.upgrades,
+            vec![UpgradeSummary {
+                package,
+                modules: 1,
+                dependencies: vec![Address::TWO.into()],
+            }]
+        );
+        assert_eq!(analysis.upgrade_cap_usage.len(), 2);
+        assert_eq!(analysis.upgrade_cap_usage[0].cap, Some(cap));
+        assert!(analysis
+            .risks
+            .iter()
+            .any(|risk| risk.kind == RiskKind::UpgradesPackage { package }));
+        assert!(analysis.objects_given_up.is_empty());
+    }
+
+    fn programmable(sender: Address, inputs: Vec<Input>, commands: Vec<Command>) -> Transaction {
+        Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs,
+                commands,
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![],
+                owner: sender,
+                price: 1000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    fn make_move_vector(elements: Vec<Argument>) -> Command {
+        Command::MakeMoveVector(MakeMoveVector {
+            type_: None,
+            elements,
+        })
+    }
+
+    #[test]
+    fn self_referencing_result() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let transaction = programmable(
+            sender,
+            vec![],
+            vec![
+                make_move_vector(vec![Argument::Result(0)]),
+                Command::MoveCall(MoveCall {
+                    package: "0xdee9".parse().unwrap(),
+                    module: Identifier::new("market").unwrap(),
+                    function: Identifier::new("list").unwrap(),
+                    type_arguments: vec![],
+                    arguments: vec![Argument::Result(0)],
+                }),
+            ],
+        );
+
+        // Invalid references are treated as unknown instead of being followed forever
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert!(!analysis
+            .risks
+            .iter()
+            .any(|risk| matches!(risk.kind, RiskKind::PassesGasCoinToMoveCall { .. })));
+    }
+
+    #[test]
+    fn forward_referencing_result() {
+        let sender: Address = "0xa11ce".parse().unwrap();
+        let recipient: Address = "0xb0b".parse().unwrap();
+        let transaction = programmable(
+            sender,
+            vec![Input::Pure {
+                value: recipient.as_bytes().to_vec(),
+            }],
+            vec![
+                make_move_vector(vec![Argument::Result(1)]),
+                make_move_vector(vec![Argument::NestedResult(0, 0)]),
+                Command::SplitCoins(SplitCoins {
+                    coin: Argument::Result(3),
+                    amounts: vec![Argument::Input(0)],
+                }),
+                Command::TransferObjects(TransferObjects {
+                    objects: vec![Argument::Result(2), Argument::Result(3)],
+                    address: Argument::Input(0),
+                }),
+            ],
+        );
+
+        let analysis = TransactionAnalyzer::new().analyze(&transaction);
+        assert!(analysis
+            .risks
+            .iter()
+            .any(|risk| risk.kind == RiskKind::UnknownCoinOutflow));
+    }
+}
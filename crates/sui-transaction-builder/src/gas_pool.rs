@@ -0,0 +1,943 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of gas coins for sending many transactions concurrently from a single address.
+//!
+//! Two transactions which use the same gas coin at the same time will equivocate and lock the coin
+//! until the end of the epoch. A [`GasPool`] prevents this by leasing each coin exclusively to a
+//! single in-flight transaction:
+//!
+//! ```rust,ignore
+//! let lease = pool.lease(budget, now_ms)?;
+//! builder.add_gas_objects([lease.input()]);
+//! let transaction = builder.finish()?;
+//!
+//! match client.execute_tx(vec![signature], &transaction).await {
+//!     // Update the coin's reference and balance from the effects
+//!     Ok(Some(effects)) => pool.release_with_effects(lease, &effects)?,
+//!     // The state of the coin is unknown, drop it until the next refresh
+//!     _ => pool.discard(lease),
+//! }
+//! ```
+//!
+//! The pool can also build transactions which split a large coin into many gas coins and which
+//! merge dust coins back together. Its state can be snapshotted and restored after a crash, with any
+//! coins which were leased at the time of the crash being reloaded from the chain using
+//! [`GasPool::refresh`].
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sui_types::Address;
+use sui_types::Argument;
+use sui_types::ExecutionStatus;
+use sui_types::IdOperation;
+use sui_types::ObjectId;
+use sui_types::ObjectOut;
+use sui_types::ObjectReference;
+use sui_types::Owner;
+use sui_types::Transaction;
+use sui_types::TransactionEffects;
+
+use crate::unresolved;
+use crate::Serialized;
+use crate::TransactionBuilder;
+
+/// An error returned by a [`GasPool`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GasPoolError {
+    #[error("No available gas coin has a balance of at least {0}")]
+    NoCoinAvailable(u64),
+    #[error("Cannot split a coin into fewer than 2 coins")]
+    InvalidSplitCount,
+    #[error("Lease {0} is not held by this pool")]
+    UnknownLease(u64),
+    #[error("Gas coin {0} is missing from the transaction effects")]
+    MissingGasObject(ObjectId),
+    #[error("Failed to build transaction: {0}")]
+    Transaction(String),
+}
+
+/// A gas coin owned by the pool's address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasCoin {
+    pub reference: ObjectReference,
+    pub balance: u64,
+}
+
+impl GasCoin {
+    pub fn new(reference: ObjectReference, balance: u64) -> Self {
+        Self { reference, balance }
+    }
+
+    pub fn object_id(&self) -> &ObjectId {
+        self.reference.object_id()
+    }
+}
+
+/// Exclusive use of a gas coin for a single transaction.
+///
+/// A lease must be returned to the pool using [`GasPool::release`], [`GasPool::release_with_effects`]
+/// or [`GasPool::discard`]. Leases which are never returned are reclaimed by
+/// [`GasPool::reclaim_expired`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct GasLease {
+    id: u64,
+    coin: GasCoin,
+    kind: LeaseKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum LeaseKind {
+    Transaction,
+    Split { amount: u64, count: usize },
+    Merge { coins: Vec<GasCoin> },
+}
+
+impl GasLease {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn coin(&self) -> &GasCoin {
+        &self.coin
+    }
+
+    pub fn reference(&self) -> &ObjectReference {
+        &self.coin.reference
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.coin.balance
+    }
+
+    /// The leased coin as a gas object for a [`TransactionBuilder`].
+    pub fn input(&self) -> unresolved::Input {
+        let (object_id, version, digest) = self.coin.reference.clone().into_parts();
+        unresolved::Input::owned(object_id, version, digest)
+    }
+
+    /// All of the coins held by this lease.
+    fn coins(&self) -> impl Iterator<Item = &GasCoin> {
+        let merged = match &self.kind {
+            LeaseKind::Merge { coins } => coins.as_slice(),
+            _ => &[],
+        };
+        std::iter::once(&self.coin).chain(merged)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct LeaseRecord {
+    id: u64,
+    acquired_at_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    available: BTreeMap<ObjectId, GasCoin>,
+    leased: BTreeMap<ObjectId, LeaseRecord>,
+    stale: BTreeSet<ObjectId>,
+    next_lease_id: u64,
+}
+
+impl State {
+    fn lease(&mut self, coin: GasCoin, now_ms: u64, kind: LeaseKind) -> GasLease {
+        let id = self.next_lease_id;
+        self.next_lease_id += 1;
+
+        let lease = GasLease { id, coin, kind };
+        for coin in lease.coins() {
+            self.available.remove(coin.object_id());
+            self.leased.insert(
+                *coin.object_id(),
+                LeaseRecord {
+                    id,
+                    acquired_at_ms: now_ms,
+                },
+            );
+        }
+        lease
+    }
+
+    fn check_lease(&self, lease: &GasLease) -> Result<(), GasPoolError> {
+        match self.leased.get(lease.coin.object_id()) {
+            Some(record) if record.id == lease.id => Ok(()),
+            _ => Err(GasPoolError::UnknownLease(lease.id)),
+        }
+    }
+
+    fn end_lease(&mut self, lease: &GasLease) {
+        for coin in lease.coins() {
+            self.leased.remove(coin.object_id());
+        }
+    }
+}
+
+/// A snapshot of a [`GasPool`] which can be persisted and used to recover the pool after a crash.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasPoolSnapshot {
+    pub owner: Address,
+    /// Coins which weren't leased at the time of the snapshot.
+    pub available: Vec<GasCoin>,
+    /// Coins which were leased, or otherwise have an unknown state, at the time of the snapshot.
+    pub leased: Vec<ObjectId>,
+}
+
+/// Manages a set of gas coins owned by a single address, leasing each coin exclusively to a single
+/// in-flight transaction.
+///
+/// All methods take `&self` so that a pool can be shared between tasks, e.g. using an `Arc`.
+/// Timestamps are provided by the caller, in milliseconds, and are only used to expire leases.
+#[derive(Debug)]
+pub struct GasPool {
+    owner: Address,
+    min_balance: u64,
+    lease_timeout_ms: Option<u64>,
+    state: Mutex<State>,
+}
+
+impl GasPool {
+    pub fn new(owner: Address) -> Self {
+        Self {
+            owner,
+            min_balance: 0,
+            lease_timeout_ms: None,
+            state: Default::default(),
+        }
+    }
+
+    /// Restore a pool from a snapshot.
+    ///
+    /// Coins which were leased at the time of the snapshot are not leased again until their
+    /// current state has been loaded using [`GasPool::refresh`].
+    pub fn from_snapshot(snapshot: GasPoolSnapshot) -> Self {
+        let pool = Self::new(snapshot.owner).with_coins(snapshot.available);
+        pool.state().stale.extend(snapshot.leased);
+        pool
+    }
+
+    pub fn with_coins<I: IntoIterator<Item = GasCoin>>(self, coins: I) -> Self {
+        for coin in coins {
+            self.add_coin(coin);
+        }
+        self
+    }
+
+    /// Coins with a balance below `min_balance` are considered dust; they are never leased for
+    /// transactions and are merged by [`GasPool::merge_dust`].
+    pub fn with_min_balance(mut self, min_balance: u64) -> Self {
+        self.min_balance = min_balance;
+        self
+    }
+
+    /// Leases held for longer than `lease_timeout_ms` are reclaimed by
+    /// [`GasPool::reclaim_expired`].
+    pub fn with_lease_timeout_ms(mut self, lease_timeout_ms: u64) -> Self {
+        self.lease_timeout_ms = Some(lease_timeout_ms);
+        self
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // The state is always left consistent, so a poisoned lock can be used as is
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Add a coin to the pool, replacing any existing entry for the same coin which isn't leased.
+    pub fn add_coin(&self, coin: GasCoin) {
+        let mut state = self.state();
+        if !state.leased.contains_key(coin.object_id()) {
+            state.stale.remove(coin.object_id());
+            state.available.insert(*coin.object_id(), coin);
+        }
+    }
+
+    /// The number of coins available to be leased, including dust.
+    pub fn available(&self) -> usize {
+        self.state().available.len()
+    }
+
+    /// The number of coins currently leased.
+    pub fn leased(&self) -> usize {
+        self.state().leased.len()
+    }
+
+    /// Whether there are coins with an unknown state which need to be reloaded using
+    /// [`GasPool::refresh`].
+    pub fn needs_refresh(&self) -> bool {
+        !self.state().stale.is_empty()
+    }
+
+    /// The total balance of the coins available to be leased.
+    pub fn available_balance(&self) -> u64 {
+        self.state()
+            .available
+            .values()
+            .fold(0, |total, coin| total.saturating_add(coin.balance))
+    }
+
+    /// Lease the available coin with the smallest balance which can cover `budget`.
+    pub fn lease(&self, budget: u64, now_ms: u64) -> Result<GasLease, GasPoolError> {
+        let mut state = self.state();
+        let min_balance = budget.max(self.min_balance);
+        let coin = state
+            .available
+            .values()
+            .filter(|coin| coin.balance >= min_balance)
+            .min_by_key(|coin| coin.balance)
+            .cloned()
+            .ok_or(GasPoolError::NoCoinAvailable(min_balance))?;
+
+        Ok(state.lease(coin, now_ms, LeaseKind::Transaction))
+    }
+
+    /// Return a lease whose transaction was never submitted, leaving the coin unchanged.
+    pub fn release(&self, lease: GasLease) -> Result<(), GasPoolError> {
+        let mut state = self.state();
+        state.check_lease(&lease)?;
+        state.end_lease(&lease);
+        for coin in lease.coins() {
+            state.available.insert(*coin.object_id(), coin.clone());
+        }
+        Ok(())
+    }
+
+    /// Return a lease whose transaction has been executed, updating the leased coins from the
+    /// transaction's effects. Coins created by a split and owned by the pool's address are added to
+    /// the pool.
+    ///
+    /// If the leased gas coin is missing from the effects, the effects don't belong to the leased
+    /// transaction and all of the leased coins are treated as having an unknown state.
+    pub fn release_with_effects(
+        &self,
+        lease: GasLease,
+        effects: &TransactionEffects,
+    ) -> Result<(), GasPoolError> {
+        let mut state = self.state();
+        state.check_lease(&lease)?;
+        state.end_lease(&lease);
+
+        let changes = ObjectChanges::new(effects, self.owner);
+        let gas_object_id = lease.coin.object_id();
+        if !changes.written.contains_key(gas_object_id) && !changes.deleted.contains(gas_object_id)
+        {
+            // Keep the pool consistent by treating the coins as having an unknown state
+            state
+                .stale
+                .extend(lease.coins().map(|coin| *coin.object_id()));
+            return Err(GasPoolError::MissingGasObject(*gas_object_id));
+        }
+
+        let success = matches!(effects.status(), ExecutionStatus::Success);
+        let gas = effects.gas_summary();
+
+        let mut balance = lease
+            .coin
+            .balance
+            .saturating_add(gas.storage_rebate)
+            .saturating_sub(gas.gas_used());
+        if success {
+            match &lease.kind {
+                LeaseKind::Transaction => {}
+                LeaseKind::Split { amount, count } => {
+                    balance = balance.saturating_sub(amount.saturating_mul(*count as u64 - 1));
+                    for reference in &changes.created {
+                        state.available.insert(
+                            *reference.object_id(),
+                            GasCoin::new(reference.clone(), *amount),
+                        );
+                    }
+                }
+                LeaseKind::Merge { coins } => {
+                    balance = coins.iter().fold(balance, |balance, coin| {
+                        balance.saturating_add(coin.balance)
+                    });
+                }
+            }
+        }
+
+        for coin in lease.coins() {
+            let object_id = coin.object_id();
+            match changes.written.get(object_id) {
+                Some(reference) => {
+                    let balance = if object_id == lease.coin.object_id() {
+                        balance
+                    } else {
+                        coin.balance
+                    };
+                    state
+                        .available
+                        .insert(*object_id, GasCoin::new(reference.clone(), balance));
+                }
+                None if changes.deleted.contains(object_id) => {}
+                None => {
+                    state.available.insert(*object_id, coin.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return a lease whose transaction is in an unknown state, e.g. because submission timed
+    /// out. The leased coins won't be leased again until reloaded using [`GasPool::refresh`].
+    pub fn discard(&self, lease: GasLease) {
+        let mut state = self.state();
+        if state.check_lease(&lease).is_ok() {
+            state.end_lease(&lease);
+        }
+        state
+            .stale
+            .extend(lease.coins().map(|coin| *coin.object_id()));
+    }
+
+    /// Reclaim leases held for longer than the lease timeout, returning the reclaimed coins.
+    ///
+    /// Reclaimed coins are treated as having an unknown state and won't be leased again until
+    /// reloaded using [`GasPool::refresh`].
+    pub fn reclaim_expired(&self, now_ms: u64) -> Vec<ObjectId> {
+        let Some(timeout) = self.lease_timeout_ms else {
+            return Vec::new();
+        };
+
+        let mut state = self.state();
+        let expired = state
+            .leased
+            .iter()
+            .filter(|(_, record)| now_ms.saturating_sub(record.acquired_at_ms) >= timeout)
+            .map(|(object_id, _)| *object_id)
+            .collect::<Vec<_>>();
+        for object_id in &expired {
+            state.leased.remove(object_id);
+            state.stale.insert(*object_id);
+        }
+        expired
+    }
+
+    /// Replace the state of all coins which aren't currently leased with `coins`, the gas coins
+    /// currently owned by the pool's address as loaded from the chain.
+    ///
+    /// Coins which are no longer owned by the pool's address are removed.
+    pub fn refresh<I: IntoIterator<Item = GasCoin>>(&self, coins: I) {
+        let mut state = self.state();
+        state.available.clear();
+        state.stale.clear();
+        for coin in coins {
+            if !state.leased.contains_key(coin.object_id()) {
+                state.available.insert(*coin.object_id(), coin);
+            }
+        }
+    }
+
+    /// Snapshot the state of the pool so that it can be recovered after a crash.
+    pub fn snapshot(&self) -> GasPoolSnapshot {
+        let state = self.state();
+        GasPoolSnapshot {
+            owner: self.owner,
+            available: state.available.values().cloned().collect(),
+            leased: state.leased.keys().chain(&state.stale).copied().collect(),
+        }
+    }
+
+    /// Build a transaction which splits the available coin with the largest balance into `count`
+    /// coins of roughly equal balance, paying for gas using that coin.
+    ///
+    /// Fails with [`GasPoolError::NoCoinAvailable`] if no coin has enough balance left after the
+    /// gas budget to give each of the `count` coins a non-zero balance.
+    ///
+    /// The coin is leased until the transaction's effects are provided to
+    /// [`GasPool::release_with_effects`], which adds the new coins to the pool.
+    pub fn split(
+        &self,
+        count: usize,
+        gas_price: u64,
+        gas_budget: u64,
+        now_ms: u64,
+    ) -> Result<(Transaction, GasLease), GasPoolError> {
+        if count < 2 {
+            return Err(GasPoolError::InvalidSplitCount);
+        }
+
+        // Every new coin needs a balance of at least 1
+        let min_balance = gas_budget.saturating_add(count as u64);
+        let mut state = self.state();
+        let coin = state
+            .available
+            .values()
+            .max_by_key(|coin| coin.balance)
+            .filter(|coin| coin.balance >= min_balance)
+            .cloned()
+            .ok_or(GasPoolError::NoCoinAvailable(min_balance))?;
+        let amount = (coin.balance - gas_budget) / count as u64;
+
+        let mut builder = TransactionBuilder::new();
+        let amounts = (1..count)
+            .map(|_| builder.input(Serialized(&amount)))
+            .collect();
+        builder.split_coins(builder.gas(), amounts);
+        let coins = (0..count - 1)
+            .map(|i| Argument::NestedResult(0, i as u16))
+            .collect();
+        let owner = builder.input(Serialized(&self.owner));
+        builder.transfer_objects(coins, owner);
+
+        let transaction = self.finish(builder, &coin, gas_price, gas_budget)?;
+        let lease = state.lease(coin, now_ms, LeaseKind::Split { amount, count });
+        Ok((transaction, lease))
+    }
+
+    /// Build a transaction which merges all available dust coins into the available coin with the
+    /// largest balance, paying for gas using that coin. Returns `None` if there is nothing to
+    /// merge, either because there are no dust coins or because every coin is dust.
+    ///
+    /// The coins are leased until the transaction's effects are provided to
+    /// [`GasPool::release_with_effects`].
+    pub fn merge_dust(
+        &self,
+        gas_price: u64,
+        gas_budget: u64,
+        now_ms: u64,
+    ) -> Result<Option<(Transaction, GasLease)>, GasPoolError> {
+        let mut state = self.state();
+        let Some(coin) = state
+            .available
+            .values()
+            .filter(|coin| coin.balance >= self.min_balance)
+            .max_by_key(|coin| coin.balance)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        if coin.balance < gas_budget {
+            return Err(GasPoolError::NoCoinAvailable(gas_budget));
+        }
+        let dust = state
+            .available
+            .values()
+            .filter(|dust| dust.balance < self.min_balance && dust.object_id() != coin.object_id())
+            .cloned()
+            .collect::<Vec<_>>();
+        if dust.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = TransactionBuilder::new();
+        let coins = dust
+            .iter()
+            .map(|dust| {
+                let (object_id, version, digest) = dust.reference.clone().into_parts();
+                builder.input(unresolved::Input::owned(object_id, version, digest))
+            })
+            .collect();
+        builder.merge_coins(builder.gas(), coins);
+
+        let transaction = self.finish(builder, &coin, gas_price, gas_budget)?;
+        let lease = state.lease(coin, now_ms, LeaseKind::Merge { coins: dust });
+        Ok(Some((transaction, lease)))
+    }
+
+    /// Finish building a transaction from the pool's address, paying for gas using `gas`.
+    fn finish(
+        &self,
+        mut builder: TransactionBuilder,
+        gas: &GasCoin,
+        gas_price: u64,
+        gas_budget: u64,
+    ) -> Result<Transaction, GasPoolError> {
+        let (object_id, version, digest) = gas.reference.clone().into_parts();
+        builder.set_sender(self.owner);
+        builder.add_gas_objects([unresolved::Input::owned(object_id, version, digest)]);
+        builder.set_gas_price(gas_price);
+        builder.set_gas_budget(gas_budget);
+        builder
+            .finish()
+            .map_err(|e| GasPoolError::Transaction(e.to_string()))
+    }
+
+    /// Reload the pool's coins from the chain, see [`GasPool::refresh`].
+    #[cfg(feature = "graphql")]
+    pub async fn refresh_from_graphql(
+        &self,
+        client: &sui_graphql_client::Client,
+    ) -> Result<(), sui_graphql_client::error::Error> {
+        use sui_graphql_client::query_types::ObjectFilter;
+        use sui_graphql_client::PaginationFilter;
+        use sui_types::framework::Coin;
+        use sui_types::StructTag;
+
+        let coin_type = StructTag::gas_coin().to_string();
+        let mut coins = Vec::new();
+        let mut cursor = None;
+        loop {
+            let filter = ObjectFilter {
+                type_: Some(&coin_type),
+                owner: Some(self.owner),
+                object_ids: None,
+            };
+            let page = client
+                .objects(
+                    Some(filter),
+                    PaginationFilter {
+                        cursor: cursor.take(),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            let (page_info, objects) = page.into_parts();
+            coins.extend(objects.iter().filter_map(|object| {
+                let coin = Coin::try_from_object(object)?;
+                let reference =
+                    ObjectReference::new(object.object_id(), object.version(), object.digest());
+                Some(GasCoin::new(reference, coin.balance()))
+            }));
+
+            if !page_info.has_next_page {
+                break;
+            }
+            cursor = page_info.end_cursor;
+        }
+
+        self.refresh(coins);
+        Ok(())
+    }
+}
+
+/// The owned objects written or deleted by a transaction.
+struct ObjectChanges {
+    written: BTreeMap<ObjectId, ObjectReference>,
+    /// Objects created by the transaction and owned by the pool's address.
+    created: Vec<ObjectReference>,
+    deleted: BTreeSet<ObjectId>,
+}
+
+impl ObjectChanges {
+    fn new(effects: &TransactionEffects, owner: Address) -> Self {
+        let mut changes = Self {
+            written: BTreeMap::new(),
+            created: Vec::new(),
+            deleted: BTreeSet::new(),
+        };
+
+        match effects {
+            TransactionEffects::V1(effects) => {
+                for object in effects
+                    .created
+                    .iter()
+                    .chain(&effects.mutated)
+                    .chain(&effects.unwrapped)
+                {
+                    changes
+                        .written
+                        .insert(*object.reference.object_id(), object.reference.clone());
+                }
+                changes.created = effects
+                    .created
+                    .iter()
+                    .filter(|object| object.owner == Owner::Address(owner))
+                    .map(|object| object.reference.clone())
+                    .collect();
+                changes.deleted = effects
+                    .deleted
+                    .iter()
+                    .chain(&effects.wrapped)
+                    .map(|reference| *reference.object_id())
+                    .collect();
+            }
+            TransactionEffects::V2(effects) => {
+                for object in &effects.changed_objects {
+                    match &object.output_state {
+                        ObjectOut::ObjectWrite {
+                            digest,
+                            owner: object_owner,
+                        } => {
+                            let reference = ObjectReference::new(
+                                object.object_id,
+                                effects.lamport_version,
+                                *digest,
+                            );
+                            if object.id_operation == IdOperation::Created
+                                && *object_owner == Owner::Address(owner)
+                            {
+                                changes.created.push(reference.clone());
+                            }
+                            changes.written.insert(object.object_id, reference);
+                        }
+                        ObjectOut::NotExist => {
+                            changes.deleted.insert(object.object_id);
+                        }
+                        ObjectOut::PackageWrite { .. } => {}
+                    }
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::ChangedObject;
+    use sui_types::Command;
+    use sui_types::GasCostSummary;
+    use sui_types::ObjectDigest;
+    use sui_types::ObjectIn;
+    use sui_types::TransactionDigest;
+    use sui_types::TransactionEffectsV2;
+    use sui_types::TransactionKind;
+
+    use super::*;
+
+    const OWNER: Address = Address::TWO;
+
+    fn coin(id: u8, balance: u64) -> GasCoin {
+        GasCoin::new(
+            ObjectReference::new(ObjectId::new([id; 32]), 1, ObjectDigest::new([id; 32])),
+            balance,
+        )
+    }
+
+    fn changed(id: u8, output_state: ObjectOut, id_operation: IdOperation) -> ChangedObject {
+        ChangedObject {
+            object_id: ObjectId::new([id; 32]),
+            input_state: ObjectIn::NotExist,
+            output_state,
+            id_operation,
+        }
+    }
+
+    fn written(id: u8) -> ObjectOut {
+        ObjectOut::ObjectWrite {
+            digest: ObjectDigest::new([id + 100; 32]),
+            owner: Owner::Address(OWNER),
+        }
+    }
+
+    fn fake_effects(changed_objects: Vec<ChangedObject>, gas_used: u64) -> TransactionEffects {
+        TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+            status: ExecutionStatus::Success,
+            epoch: 0,
+            gas_used: GasCostSummary::new(0, 0, gas_used, 0, 0, 0),
+            transaction_digest: TransactionDigest::ZERO,
+            gas_object_index: Some(0),
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: 7,
+            changed_objects,
+            unchanged_shared_objects: vec![],
+            auxiliary_data_digest: None,
+        }))
+    }
+
+    #[test]
+    fn leases_are_exclusive() {
+        let pool = GasPool::new(OWNER).with_coins([coin(1, 100), coin(2, 1_000)]);
+
+        let small = pool.lease(50, 0).unwrap();
+        assert_eq!(small.coin(), &coin(1, 100));
+        let large = pool.lease(50, 0).unwrap();
+        assert_eq!(large.coin(), &coin(2, 1_000));
+        assert_eq!(pool.lease(50, 0), Err(GasPoolError::NoCoinAvailable(50)));
+        assert_eq!(pool.leased(), 2);
+
+        pool.release(small).unwrap();
+        let effects = fake_effects(vec![changed(2, written(2), IdOperation::None)], 10);
+        pool.release_with_effects(large, &effects).unwrap();
+        assert_eq!(pool.available(), 2);
+        assert_eq!(pool.available_balance(), 1_090);
+
+        let lease = pool.lease(500, 0).unwrap();
+        assert_eq!(lease.reference().version(), 7);
+        assert_eq!(lease.reference().digest(), &ObjectDigest::new([102; 32]));
+    }
+
+    #[test]
+    fn split_and_merge_dust() {
+        let pool = GasPool::new(OWNER)
+            .with_coins([coin(1, 1_000), coin(2, 5), coin(3, 5)])
+            .with_min_balance(50);
+
+        let (transaction, lease) = pool.split(3, 1, 100, 0).unwrap();
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            panic!("expected a programmable transaction");
+        };
+        assert!(matches!(ptb.commands[0], Command::SplitCoins(_)));
+        assert_eq!(transaction.gas_payment.objects, [coin(1, 1_000).reference]);
+
+        let effects = fake_effects(
+            vec![
+                changed(1, written(1), IdOperation::None),
+                changed(4, written(4), IdOperation::Created),
+                changed(5, written(5), IdOperation::Created),
+            ],
+            10,
+        );
+        pool.release_with_effects(lease, &effects).unwrap();
+        assert_eq!(pool.available(), 5);
+        // The 900 left after the budget is split 3 ways, with the gas coin also keeping the
+        // unused budget
+        assert_eq!(pool.lease(300, 0).unwrap().balance(), 300);
+        assert_eq!(
+            pool.available_balance(),
+            (1_000 - 10 - 2 * 300) + 300 + 5 + 5
+        );
+
+        let (transaction, lease) = pool.merge_dust(1, 100, 0).unwrap().unwrap();
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            panic!("expected a programmable transaction");
+        };
+        assert_eq!(ptb.inputs.len(), 2);
+        assert_eq!(pool.leased(), 4);
+
+        let effects = fake_effects(
+            vec![
+                changed(1, written(1), IdOperation::None),
+                changed(2, ObjectOut::NotExist, IdOperation::Deleted),
+                changed(3, ObjectOut::NotExist, IdOperation::Deleted),
+            ],
+            10,
+        );
+        pool.release_with_effects(lease, &effects).unwrap();
+        assert_eq!(pool.available(), 2);
+        assert_eq!(pool.available_balance(), (390 - 10 + 5 + 5) + 300);
+        assert_eq!(pool.merge_dust(1, 100, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn split_rejects_zero_balance_coins() {
+        let pool = GasPool::new(OWNER).with_coins([coin(1, 103)]);
+
+        // Only 3 is left after the budget, too little for 4 coins
+        assert_eq!(
+            pool.split(4, 1, 100, 0),
+            Err(GasPoolError::NoCoinAvailable(104))
+        );
+        assert_eq!(pool.available(), 1);
+
+        let (transaction, _) = pool.split(3, 1, 100, 0).unwrap();
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            panic!("expected a programmable transaction");
+        };
+        let Command::SplitCoins(split) = &ptb.commands[0] else {
+            panic!("expected a split");
+        };
+        assert_eq!(ptb.split_amounts(split), Some(vec![1, 1]));
+    }
+
+    #[test]
+    fn missing_gas_object_marks_all_coins_stale() {
+        let pool = GasPool::new(OWNER)
+            .with_coins([coin(1, 1_000), coin(2, 5), coin(3, 5)])
+            .with_min_balance(50);
+
+        let (_, lease) = pool.merge_dust(1, 100, 0).unwrap().unwrap();
+        // Effects which don't mention the gas coin belong to some other transaction
+        let effects = fake_effects(
+            vec![changed(2, ObjectOut::NotExist, IdOperation::Deleted)],
+            10,
+        );
+        assert_eq!(
+            pool.release_with_effects(lease, &effects),
+            Err(GasPoolError::MissingGasObject(ObjectId::new([1; 32])))
+        );
+        assert_eq!(pool.available(), 0);
+        assert_eq!(pool.leased(), 0);
+        assert!(pool.needs_refresh());
+        let mut stale = pool.snapshot().leased;
+        stale.sort();
+        assert_eq!(
+            stale,
+            [
+                ObjectId::new([1; 32]),
+                ObjectId::new([2; 32]),
+                ObjectId::new([3; 32])
+            ]
+        );
+    }
+
+    #[test]
+    fn split_ignores_coins_owned_by_others() {
+        let pool = GasPool::new(OWNER).with_coins([coin(1, 1_000)]);
+
+        let (_, lease) = pool.split(2, 1, 100, 0).unwrap();
+        let effects = fake_effects(
+            vec![
+                changed(1, written(1), IdOperation::None),
+                changed(
+                    4,
+                    ObjectOut::ObjectWrite {
+                        digest: ObjectDigest::new([104; 32]),
+                        owner: Owner::Address(Address::THREE),
+                    },
+                    IdOperation::Created,
+                ),
+            ],
+            10,
+        );
+        pool.release_with_effects(lease, &effects).unwrap();
+        assert_eq!(pool.available(), 1);
+    }
+
+    #[test]
+    fn merge_dust_without_a_coin_to_merge_into() {
+        let pool = GasPool::new(OWNER)
+            .with_coins([coin(1, 5), coin(2, 5)])
+            .with_min_balance(50);
+        assert_eq!(pool.merge_dust(1, 100, 0).unwrap(), None);
+        assert_eq!(pool.leased(), 0);
+
+        // A coin which isn't dust but can't cover the budget
+        pool.add_coin(coin(3, 60));
+        assert_eq!(
+            pool.merge_dust(1, 100, 0),
+            Err(GasPoolError::NoCoinAvailable(100))
+        );
+        assert_eq!(pool.leased(), 0);
+    }
+
+    #[test]
+    fn available_balance_saturates() {
+        let pool = GasPool::new(OWNER).with_coins([coin(1, u64::MAX), coin(2, 1)]);
+        assert_eq!(pool.available_balance(), u64::MAX);
+    }
+
+    #[test]
+    fn recover_after_crash() {
+        let pool = GasPool::new(OWNER)
+            .with_coins([coin(1, 100), coin(2, 100), coin(3, 100)])
+            .with_lease_timeout_ms(1_000);
+
+        let first = pool.lease(10, 0).unwrap();
+        let _second = pool.lease(10, 500).unwrap();
+        pool.discard(first);
+        assert_eq!(pool.reclaim_expired(1_000), Vec::<ObjectId>::new());
+        assert_eq!(pool.reclaim_expired(1_500), [ObjectId::new([2; 32])]);
+        assert!(pool.needs_refresh());
+
+        // Simulate a crash by restoring the pool from a snapshot
+        let snapshot = pool.snapshot();
+        let pool = GasPool::from_snapshot(
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap(),
+        );
+        assert_eq!(pool.available(), 1);
+        assert!(pool.needs_refresh());
+
+        let mut refreshed = coin(1, 90);
+        refreshed.reference =
+            ObjectReference::new(ObjectId::new([1; 32]), 2, ObjectDigest::new([101; 32]));
+        pool.refresh([refreshed.clone(), coin(3, 100)]);
+        assert!(!pool.needs_refresh());
+        assert_eq!(pool.available(), 2);
+        assert_eq!(pool.lease(10, 0).unwrap().coin(), &refreshed);
+    }
+}
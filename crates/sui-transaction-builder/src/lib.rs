@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod analyzer;
 mod error;
+pub mod gas_pool;
+pub mod sponsor;
 pub mod unresolved;
 
 use error::Error;
@@ -20,6 +23,7 @@ use sui_types::Publish;
 use sui_types::SplitCoins;
 use sui_types::Transaction;
 use sui_types::TransactionExpiration;
+use sui_types::TransactionKind;
 use sui_types::TransferObjects;
 use sui_types::TypeTag;
 use sui_types::Upgrade;
@@ -287,7 +291,7 @@ impl TransactionBuilder {
 
     /// Assuming everything is resolved, convert this transaction into the
     /// resolved form. Returns a [`Transaction`] if successful, or an `Error` if not.
-    pub fn finish(self) -> Result<Transaction, Error> {
+    pub fn finish(mut self) -> Result<Transaction, Error> {
         let Some(sender) = self.sender else {
             return Err(Error::MissingSender);
         };
@@ -300,37 +304,46 @@ impl TransactionBuilder {
         let Some(price) = self.gas_price else {
             return Err(Error::MissingGasPrice);
         };
+        let expiration = self.expiration;
+        let sponsor = self.sponsor;
+        let gas = std::mem::take(&mut self.gas);
 
         Ok(Transaction {
-            kind: sui_types::TransactionKind::ProgrammableTransaction(
-                sui_types::ProgrammableTransaction {
-                    inputs: self
-                        .inputs
-                        .into_iter()
-                        .map(try_from_unresolved_input_arg)
-                        .collect::<Result<Vec<_>, _>>()?,
-                    commands: self.commands,
-                },
-            ),
+            kind: self.finish_kind()?,
             sender,
             gas_payment: {
                 GasPayment {
-                    objects: self
-                        .gas
+                    objects: gas
                         .into_iter()
                         .map(try_from_gas_unresolved_input_to_unresolved_obj_ref)
                         .collect::<Result<Vec<_>, _>>()?
                         .into_iter()
                         .map(try_from_unresolved_obj_ref)
                         .collect::<Result<Vec<_>, _>>()?,
-                    owner: self.sponsor.unwrap_or(sender),
+                    owner: sponsor.unwrap_or(sender),
                     price,
                     budget,
                 }
             },
-            expiration: self.expiration,
+            expiration,
         })
     }
+
+    /// Assuming everything is resolved, convert the inputs and commands of this transaction into a
+    /// [`TransactionKind`], ignoring the sender and any gas information. This is used to build the
+    /// transaction a sponsor is asked to pay for, see the [`sponsor`] module.
+    pub fn finish_kind(self) -> Result<TransactionKind, Error> {
+        Ok(TransactionKind::ProgrammableTransaction(
+            sui_types::ProgrammableTransaction {
+                inputs: self
+                    .inputs
+                    .into_iter()
+                    .map(try_from_unresolved_input_arg)
+                    .collect::<Result<Vec<_>, _>>()?,
+                commands: self.commands,
+            },
+        ))
+    }
 }
 
 impl Function {
@@ -0,0 +1,715 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for sponsored transactions, where a sponsor pays for the gas of a sender's transaction.
+//!
+//! The flow is as follows:
+//!
+//! 1. The sender builds the [`TransactionKind`] they want to execute, e.g. using
+//!    [`TransactionBuilder::finish_kind`], and sends a [`SponsorRequest`] to the sponsor.
+//! 2. The sponsor checks the request against its [`SponsorPolicy`], adds its gas objects, gas price
+//!    and gas budget, signs the resulting [`Transaction`] and returns a [`SponsoredTransaction`].
+//! 3. The sender checks that the sponsor didn't alter their request using
+//!    [`SponsorRequest::check_transaction`], signs the transaction and adds their signature to the
+//!    [`SponsoredTransaction`], which verifies it, orders the signatures correctly and produces a
+//!    [`SignedTransaction`] ready to be executed.
+//!
+//! A sponsor service can implement [`GasStation`], and [`LocalGasStation`] can be used to run a
+//! sponsor in process, e.g. for testing. Signatures are verified using any
+//! [`Verifier<UserSignature>`](signature::Verifier), such as `sui_crypto::UserSignatureVerifier`.
+//!
+//! [`TransactionBuilder::finish_kind`]: crate::TransactionBuilder::finish_kind
+
+use std::collections::BTreeSet;
+use std::future::Future;
+
+use base64ct::Encoding;
+use serde::Deserialize;
+use serde::Serialize;
+use signature::Verifier;
+use sui_types::Address;
+use sui_types::Argument;
+use sui_types::Command;
+use sui_types::GasPayment;
+use sui_types::Identifier;
+use sui_types::Input;
+use sui_types::ObjectId;
+use sui_types::ObjectReference;
+use sui_types::SignedTransaction;
+use sui_types::Transaction;
+use sui_types::TransactionExpiration;
+use sui_types::TransactionKind;
+use sui_types::UserSignature;
+
+/// An error encountered while sponsoring a transaction or assembling its signatures.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SponsorError {
+    #[error("Only programmable transactions can be sponsored")]
+    UnsupportedTransactionKind,
+    #[error("Missing gas objects")]
+    MissingGasObjects,
+    #[error("Missing gas budget")]
+    MissingGasBudget,
+    #[error("Gas budget {budget} exceeds the maximum budget of {max}")]
+    BudgetExceedsLimit { budget: u64, max: u64 },
+    #[error("Command {index} ({kind:?}) is not allowed")]
+    CommandNotAllowed { index: usize, kind: CommandKind },
+    #[error("Command {index} calls {package}::{module}::{function} which is not allowed")]
+    MoveCallNotAllowed {
+        index: usize,
+        package: ObjectId,
+        module: Identifier,
+        function: Identifier,
+    },
+    #[error("Command {index} uses the sponsor's gas coin")]
+    GasCoinUsed { index: usize },
+    #[error("Sponsor gas object {0} is used as an input")]
+    GasObjectUsedAsInput(ObjectId),
+    #[error("Transaction does not match the sponsor request: {0}")]
+    RequestMismatch(&'static str),
+    #[error("Signature is from {0} which is neither the sender nor the sponsor")]
+    UnexpectedSigner(Address),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Missing signature from {0}")]
+    MissingSignature(Address),
+}
+
+/// The kind of a [`Command`], used to restrict the commands a sponsor is willing to pay for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CommandKind {
+    MoveCall,
+    TransferObjects,
+    SplitCoins,
+    MergeCoins,
+    Publish,
+    MakeMoveVector,
+    Upgrade,
+}
+
+impl From<&Command> for CommandKind {
+    fn from(command: &Command) -> Self {
+        match command {
+            Command::MoveCall(_) => Self::MoveCall,
+            Command::TransferObjects(_) => Self::TransferObjects,
+            Command::SplitCoins(_) => Self::SplitCoins,
+            Command::MergeCoins(_) => Self::MergeCoins,
+            Command::Publish(_) => Self::Publish,
+            Command::MakeMoveVector(_) => Self::MakeMoveVector,
+            Command::Upgrade(_) => Self::Upgrade,
+        }
+    }
+}
+
+/// A request from a sender for a sponsor to pay for the gas of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SponsorRequest {
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The transaction to be executed.
+    pub kind: TransactionKind,
+    /// The gas budget requested by the sender. If None, the sponsor chooses the budget.
+    pub gas_budget: Option<u64>,
+    /// The expiration of the transaction.
+    pub expiration: TransactionExpiration,
+}
+
+impl SponsorRequest {
+    pub fn new(sender: Address, kind: TransactionKind) -> Self {
+        Self {
+            sender,
+            kind,
+            gas_budget: None,
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    /// Request a specific gas budget.
+    pub fn with_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    /// Set the expiration of the transaction to be a specific epoch.
+    pub fn with_expiration(mut self, epoch: u64) -> Self {
+        self.expiration = TransactionExpiration::Epoch(epoch);
+        self
+    }
+
+    /// Check that `transaction` executes this request on behalf of the sender, only differing in
+    /// its gas payment.
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<(), SponsorError> {
+        if transaction.sender != self.sender {
+            return Err(SponsorError::RequestMismatch("sender"));
+        }
+        if transaction.kind != self.kind {
+            return Err(SponsorError::RequestMismatch("transaction kind"));
+        }
+        if transaction.expiration != self.expiration {
+            return Err(SponsorError::RequestMismatch("expiration"));
+        }
+        if self
+            .gas_budget
+            .is_some_and(|budget| budget != transaction.gas_payment.budget)
+        {
+            return Err(SponsorError::RequestMismatch("gas budget"));
+        }
+        if transaction.gas_payment.owner == self.sender {
+            return Err(SponsorError::RequestMismatch("gas owner"));
+        }
+        Ok(())
+    }
+}
+
+/// The rules a sponsor enforces on the transactions it pays for.
+///
+/// By default the only rule enforced is that the sender cannot use the sponsor's gas coin, either
+/// via [`Argument::Gas`] or by using one of the sponsor's gas objects as an input.
+#[derive(Clone, Debug, Default)]
+pub struct SponsorPolicy {
+    max_budget: Option<u64>,
+    allowed_commands: Option<BTreeSet<CommandKind>>,
+    allowed_packages: Option<BTreeSet<ObjectId>>,
+    allow_gas_coin_usage: bool,
+}
+
+impl SponsorPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject transactions with a gas budget larger than `max_budget`.
+    pub fn with_max_budget(mut self, max_budget: u64) -> Self {
+        self.max_budget = Some(max_budget);
+        self
+    }
+
+    /// Only allow the provided kinds of commands.
+    pub fn with_allowed_commands<I: IntoIterator<Item = CommandKind>>(mut self, kinds: I) -> Self {
+        self.allowed_commands = Some(kinds.into_iter().collect());
+        self
+    }
+
+    /// Only allow Move calls to functions in the provided packages.
+    pub fn with_allowed_packages<I: IntoIterator<Item = ObjectId>>(mut self, packages: I) -> Self {
+        self.allowed_packages = Some(packages.into_iter().collect());
+        self
+    }
+
+    /// Allow commands to use the sponsor's gas coin via [`Argument::Gas`].
+    ///
+    /// This allows the sender to transfer or split off the sponsor's funds, so it should only be
+    /// enabled for trusted senders.
+    pub fn with_gas_coin_usage(mut self, allow: bool) -> Self {
+        self.allow_gas_coin_usage = allow;
+        self
+    }
+
+    pub fn max_budget(&self) -> Option<u64> {
+        self.max_budget
+    }
+
+    /// Check a sponsored `transaction` against this policy.
+    pub fn check(&self, transaction: &Transaction) -> Result<(), SponsorError> {
+        if let Some(max) = self.max_budget {
+            let budget = transaction.gas_payment.budget;
+            if budget > max {
+                return Err(SponsorError::BudgetExceedsLimit { budget, max });
+            }
+        }
+
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            return Err(SponsorError::UnsupportedTransactionKind);
+        };
+
+        for input in &ptb.inputs {
+            let (Input::ImmutableOrOwned(object) | Input::Receiving(object)) = input else {
+                continue;
+            };
+            if transaction
+                .gas_payment
+                .objects
+                .iter()
+                .any(|gas| gas.object_id() == object.object_id())
+            {
+                return Err(SponsorError::GasObjectUsedAsInput(*object.object_id()));
+            }
+        }
+
+        for (index, command) in ptb.commands.iter().enumerate() {
+            let kind = CommandKind::from(command);
+            if self
+                .allowed_commands
+                .as_ref()
+                .is_some_and(|allowed| !allowed.contains(&kind))
+            {
+                return Err(SponsorError::CommandNotAllowed { index, kind });
+            }
+
+            if let (Command::MoveCall(call), Some(allowed)) = (command, &self.allowed_packages) {
+                if !allowed.contains(&call.package) {
+                    return Err(SponsorError::MoveCallNotAllowed {
+                        index,
+                        package: call.package,
+                        module: call.module.clone(),
+                        function: call.function.clone(),
+                    });
+                }
+            }
+
+            if !self.allow_gas_coin_usage && command_arguments(command).any(|a| a == &Argument::Gas)
+            {
+                return Err(SponsorError::GasCoinUsed { index });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn command_arguments(command: &Command) -> Box<dyn Iterator<Item = &Argument> + '_> {
+    match command {
+        Command::MoveCall(call) => Box::new(call.arguments.iter()),
+        Command::TransferObjects(transfer) => Box::new(
+            transfer
+                .objects
+                .iter()
+                .chain(std::iter::once(&transfer.address)),
+        ),
+        Command::SplitCoins(split) => {
+            Box::new(std::iter::once(&split.coin).chain(split.amounts.iter()))
+        }
+        Command::MergeCoins(merge) => {
+            Box::new(std::iter::once(&merge.coin).chain(merge.coins_to_merge.iter()))
+        }
+        Command::MakeMoveVector(vector) => Box::new(vector.elements.iter()),
+        Command::Upgrade(upgrade) => Box::new(std::iter::once(&upgrade.ticket)),
+        Command::Publish(_) => Box::new(std::iter::empty()),
+    }
+}
+
+/// A sponsor which pays for the gas of transactions that pass its [`SponsorPolicy`].
+#[derive(Clone, Debug)]
+pub struct Sponsor {
+    address: Address,
+    gas_objects: Vec<ObjectReference>,
+    gas_price: u64,
+    gas_budget: Option<u64>,
+    policy: SponsorPolicy,
+}
+
+impl Sponsor {
+    pub fn new(address: Address, gas_price: u64) -> Self {
+        Self {
+            address,
+            gas_objects: Vec::new(),
+            gas_price,
+            gas_budget: None,
+            policy: SponsorPolicy::default(),
+        }
+    }
+
+    /// The gas objects used to pay for transactions which don't have gas objects provided via
+    /// [`Sponsor::sponsor_with_gas`].
+    pub fn with_gas_objects<I: IntoIterator<Item = ObjectReference>>(mut self, gas: I) -> Self {
+        self.gas_objects = gas.into_iter().collect();
+        self
+    }
+
+    /// The gas budget used when a request doesn't specify one.
+    pub fn with_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    pub fn with_policy(mut self, policy: SponsorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn policy(&self) -> &SponsorPolicy {
+        &self.policy
+    }
+
+    /// Build the transaction for `request`, paying for gas using the sponsor's gas objects.
+    pub fn sponsor(&self, request: &SponsorRequest) -> Result<Transaction, SponsorError> {
+        self.sponsor_with_gas(request, self.gas_objects.clone())
+    }
+
+    /// Build the transaction for `request`, paying for gas using the provided gas objects.
+    pub fn sponsor_with_gas(
+        &self,
+        request: &SponsorRequest,
+        gas_objects: Vec<ObjectReference>,
+    ) -> Result<Transaction, SponsorError> {
+        if gas_objects.is_empty() {
+            return Err(SponsorError::MissingGasObjects);
+        }
+
+        let budget = request
+            .gas_budget
+            .or(self.gas_budget)
+            .or(self.policy.max_budget)
+            .ok_or(SponsorError::MissingGasBudget)?;
+
+        let transaction = Transaction {
+            kind: request.kind.clone(),
+            sender: request.sender,
+            gas_payment: GasPayment {
+                objects: gas_objects,
+                owner: self.address,
+                price: self.gas_price,
+                budget,
+            },
+            expiration: request.expiration,
+        };
+
+        self.policy.check(&transaction)?;
+        Ok(transaction)
+    }
+}
+
+/// A sponsored transaction along with the signatures collected for it so far.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SponsoredTransaction {
+    transaction: Transaction,
+    sender_signature: Option<UserSignature>,
+    sponsor_signature: Option<UserSignature>,
+}
+
+impl SponsoredTransaction {
+    pub fn new(transaction: Transaction) -> Self {
+        Self {
+            transaction,
+            sender_signature: None,
+            sponsor_signature: None,
+        }
+    }
+
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// The base64 encoded BCS bytes of the transaction, as used by wallets and JSON-RPC.
+    pub fn transaction_bytes(&self) -> String {
+        base64ct::Base64::encode_string(
+            &bcs::to_bytes(&self.transaction).expect("serialization should not fail"),
+        )
+    }
+
+    pub fn sender(&self) -> Address {
+        self.transaction.sender
+    }
+
+    pub fn sponsor(&self) -> Address {
+        self.transaction.gas_payment.owner
+    }
+
+    pub fn sender_signature(&self) -> Option<&UserSignature> {
+        self.sender_signature.as_ref()
+    }
+
+    pub fn sponsor_signature(&self) -> Option<&UserSignature> {
+        self.sponsor_signature.as_ref()
+    }
+
+    /// Add a signature from either the sender or the sponsor, determined by the address the
+    /// signature was produced by.
+    ///
+    /// The signature is verified against the transaction using `verifier` before being added.
+    pub fn add_signature<V: Verifier<UserSignature>>(
+        &mut self,
+        signature: UserSignature,
+        verifier: &V,
+    ) -> Result<(), SponsorError> {
+        verifier
+            .verify(&self.transaction.signing_digest(), &signature)
+            .map_err(|e| SponsorError::InvalidSignature(e.to_string()))?;
+        self.insert_signature(signature)
+    }
+
+    pub fn with_signature<V: Verifier<UserSignature>>(
+        mut self,
+        signature: UserSignature,
+        verifier: &V,
+    ) -> Result<Self, SponsorError> {
+        self.add_signature(signature, verifier)?;
+        Ok(self)
+    }
+
+    /// Add a signature without verifying it, only checking that it was produced by the sender or
+    /// the sponsor.
+    fn insert_signature(&mut self, signature: UserSignature) -> Result<(), SponsorError> {
+        let addresses = signature
+            .derive_addresses()
+            .map_err(|e| SponsorError::InvalidSignature(e.to_string()))?;
+        if addresses.contains(&self.sender()) {
+            self.sender_signature = Some(signature);
+        } else if addresses.contains(&self.sponsor()) {
+            self.sponsor_signature = Some(signature);
+        } else {
+            return Err(SponsorError::UnexpectedSigner(addresses[0]));
+        }
+        Ok(())
+    }
+
+    /// Combine the transaction with the sender's and the sponsor's signatures, in that order.
+    pub fn finish(self) -> Result<SignedTransaction, SponsorError> {
+        let sender_signature = self
+            .sender_signature
+            .ok_or(SponsorError::MissingSignature(self.transaction.sender))?;
+        let sponsor_signature = self
+            .sponsor_signature
+            .ok_or(SponsorError::MissingSignature(
+                self.transaction.gas_payment.owner,
+            ))?;
+
+        Ok(SignedTransaction {
+            transaction: self.transaction,
+            signatures: vec![sender_signature, sponsor_signature],
+        })
+    }
+}
+
+/// A service which sponsors transactions.
+pub trait GasStation {
+    type Error;
+
+    /// Sponsor `request`, returning the transaction signed by the sponsor.
+    fn sponsor_transaction(
+        &self,
+        request: SponsorRequest,
+    ) -> impl Future<Output = Result<SponsoredTransaction, Self::Error>>;
+}
+
+/// A [`GasStation`] which runs a [`Sponsor`] in process, signing transactions with the provided
+/// signing function.
+///
+/// The signature produced by the signing function is trusted and added without being verified.
+///
+/// ```rust,ignore
+/// let station = LocalGasStation::new(sponsor, |transaction: &Transaction| {
+///     sponsor_key.sign_transaction(transaction)
+/// });
+/// ```
+pub struct LocalGasStation<F> {
+    sponsor: Sponsor,
+    sign: F,
+}
+
+impl<F, E> LocalGasStation<F>
+where
+    F: Fn(&Transaction) -> Result<UserSignature, E>,
+    E: std::fmt::Display,
+{
+    pub fn new(sponsor: Sponsor, sign: F) -> Self {
+        Self { sponsor, sign }
+    }
+
+    pub fn sponsor(&self) -> &Sponsor {
+        &self.sponsor
+    }
+
+    pub fn sponsor_mut(&mut self) -> &mut Sponsor {
+        &mut self.sponsor
+    }
+}
+
+impl<F, E> GasStation for LocalGasStation<F>
+where
+    F: Fn(&Transaction) -> Result<UserSignature, E>,
+    E: std::fmt::Display,
+{
+    type Error = SponsorError;
+
+    async fn sponsor_transaction(
+        &self,
+        request: SponsorRequest,
+    ) -> Result<SponsoredTransaction, SponsorError> {
+        let transaction = self.sponsor.sponsor(&request)?;
+        let signature =
+            (self.sign)(&transaction).map_err(|e| SponsorError::InvalidSignature(e.to_string()))?;
+        let mut sponsored = SponsoredTransaction::new(transaction);
+        sponsored.insert_signature(signature)?;
+        Ok(sponsored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_crypto::ed25519::Ed25519PrivateKey;
+    use sui_crypto::ed25519::Ed25519Verifier;
+    use sui_crypto::SuiSigner;
+    use sui_types::ObjectDigest;
+
+    use super::*;
+    use crate::unresolved;
+    use crate::Function;
+    use crate::Serialized;
+    use crate::TransactionBuilder;
+
+    fn gas_object(id: u8) -> ObjectReference {
+        ObjectReference::new(ObjectId::new([id; 32]), 1, ObjectDigest::new([id; 32]))
+    }
+
+    fn transfer_kind(sender_coin: ObjectReference, recipient: Address) -> TransactionKind {
+        let mut builder = TransactionBuilder::new();
+        let coin = builder.input(unresolved::Input::owned(
+            *sender_coin.object_id(),
+            sender_coin.version(),
+            *sender_coin.digest(),
+        ));
+        let recipient = builder.input(Serialized(&recipient));
+        builder.transfer_objects(vec![coin], recipient);
+        builder.finish_kind().unwrap()
+    }
+
+    #[tokio::test]
+    async fn sponsored_transaction_flow() {
+        let sender_key = Ed25519PrivateKey::new([1; 32]);
+        let sender = sender_key.public_key().derive_address();
+        let sponsor_key = Ed25519PrivateKey::new([2; 32]);
+        let sponsor = Sponsor::new(sponsor_key.public_key().derive_address(), 1000)
+            .with_gas_objects([gas_object(9)])
+            .with_policy(SponsorPolicy::new().with_max_budget(50_000_000));
+        let station = LocalGasStation::new(sponsor, |transaction: &Transaction| {
+            sponsor_key.sign_transaction(transaction)
+        });
+
+        let request = SponsorRequest::new(sender, transfer_kind(gas_object(1), Address::ZERO))
+            .with_expiration(10);
+        let sponsored = station.sponsor_transaction(request.clone()).await.unwrap();
+        assert!(sponsored.sender_signature().is_none());
+        assert!(sponsored.sponsor_signature().is_some());
+
+        // The sender checks the sponsor didn't alter their request before signing
+        let transaction = sponsored.transaction();
+        request.check_transaction(transaction).unwrap();
+        assert_eq!(transaction.gas_payment.budget, 50_000_000);
+        assert_eq!(transaction.gas_payment.objects, [gas_object(9)]);
+        let mut tampered = transaction.clone();
+        tampered.expiration = TransactionExpiration::None;
+        request.check_transaction(&tampered).unwrap_err();
+
+        let verifier = Ed25519Verifier::default();
+        let signature = sender_key.sign_transaction(transaction).unwrap();
+        let signed = sponsored
+            .clone()
+            .with_signature(signature.clone(), &verifier)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(
+            signed.signatures,
+            [signature, sponsored.sponsor_signature().unwrap().clone()]
+        );
+        assert_eq!(
+            base64ct::Base64::decode_vec(&sponsored.transaction_bytes()).unwrap(),
+            bcs::to_bytes(&signed.transaction).unwrap()
+        );
+
+        // Signatures from anyone else are rejected
+        let other = Ed25519PrivateKey::new([3; 32]);
+        let err = sponsored
+            .clone()
+            .with_signature(other.sign_transaction(transaction).unwrap(), &verifier)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SponsorError::UnexpectedSigner(other.public_key().derive_address())
+        );
+
+        // As are signatures from the sender over a different transaction
+        let err = sponsored
+            .clone()
+            .with_signature(sender_key.sign_transaction(&tampered).unwrap(), &verifier)
+            .unwrap_err();
+        assert!(matches!(err, SponsorError::InvalidSignature(_)));
+        sponsored.finish().unwrap_err();
+    }
+
+    #[test]
+    fn sponsor_policy() {
+        let sender = Address::ZERO;
+        let sponsor = Sponsor::new(Address::TWO, 1000)
+            .with_gas_objects([gas_object(9)])
+            .with_gas_budget(1_000)
+            .with_policy(
+                SponsorPolicy::new()
+                    .with_max_budget(10_000)
+                    .with_allowed_commands([CommandKind::MoveCall, CommandKind::TransferObjects])
+                    .with_allowed_packages([ObjectId::from(Address::TWO)]),
+            );
+
+        let kind = transfer_kind(gas_object(1), Address::ZERO);
+        sponsor
+            .sponsor(&SponsorRequest::new(sender, kind.clone()))
+            .unwrap();
+
+        let err = sponsor
+            .sponsor(&SponsorRequest::new(sender, kind.clone()).with_gas_budget(20_000))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SponsorError::BudgetExceedsLimit {
+                budget: 20_000,
+                max: 10_000
+            }
+        );
+
+        let err = sponsor
+            .sponsor_with_gas(&SponsorRequest::new(sender, kind), vec![gas_object(1)])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SponsorError::GasObjectUsedAsInput(ObjectId::new([1; 32]))
+        );
+
+        let mut builder = TransactionBuilder::new();
+        let amount = builder.input(Serialized(&1u64));
+        let coins = builder.split_coins(builder.gas(), vec![amount]);
+        let recipient = builder.input(Serialized(&sender));
+        builder.transfer_objects(vec![coins], recipient);
+        let err = sponsor
+            .sponsor(&SponsorRequest::new(sender, builder.finish_kind().unwrap()))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SponsorError::CommandNotAllowed {
+                index: 0,
+                kind: CommandKind::SplitCoins
+            }
+        );
+
+        let mut builder = TransactionBuilder::new();
+        let recipient = builder.input(Serialized(&sender));
+        builder.transfer_objects(vec![builder.gas()], recipient);
+        let err = sponsor
+            .sponsor(&SponsorRequest::new(sender, builder.finish_kind().unwrap()))
+            .unwrap_err();
+        assert_eq!(err, SponsorError::GasCoinUsed { index: 0 });
+
+        let mut builder = TransactionBuilder::new();
+        builder.move_call(
+            Function::new(
+                Address::THREE,
+                "module".parse().unwrap(),
+                "function".parse().unwrap(),
+                vec![],
+            ),
+            vec![],
+        );
+        let err = sponsor
+            .sponsor(&SponsorRequest::new(sender, builder.finish_kind().unwrap()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SponsorError::MoveCallNotAllowed { index: 0, .. }
+        ));
+    }
+}